@@ -39,8 +39,12 @@ where
 	C::Api: BlockBuilder<Block>,
 	C::Api: pallet_trading_rpc::TradingRuntimeApi<Block>,
 	C::Api: pallet_prices_rpc::PricesRuntimeApi<Block>,
+	C::Api: pallet_market_rpc::MarketsRuntimeApi<Block>,
+	C::Api: pallet_asset_rpc::AssetsRuntimeApi<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_asset_rpc::{AssetsApiServer, AssetsPallet};
+	use pallet_market_rpc::{MarketsApiServer, MarketsPallet};
 	use pallet_prices_rpc::{PricesApiServer, PricesPallet};
 	use pallet_trading_rpc::{TradingApiServer, TradingPallet};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
@@ -52,7 +56,9 @@ where
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	module.merge(TradingPallet::new(client.clone()).into_rpc())?;
-	module.merge(PricesPallet::new(client).into_rpc())?;
+	module.merge(PricesPallet::new(client.clone()).into_rpc())?;
+	module.merge(MarketsPallet::new(client.clone()).into_rpc())?;
+	module.merge(AssetsPallet::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
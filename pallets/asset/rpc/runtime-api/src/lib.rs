@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::dispatch::Vec;
+use pallet_support::types::AssetInfo;
+use primitive_types::U256;
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime file (the `runtime-api/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	pub trait AssetsApi {
+		fn get_asset(asset_id: U256) -> Option<AssetInfo>;
+		fn get_collaterals() -> Vec<AssetInfo>;
+		fn get_asset_by_address(chain: U256, address: U256) -> Option<AssetInfo>;
+	}
+}
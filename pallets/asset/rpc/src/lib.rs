@@ -0,0 +1,91 @@
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_asset_runtime_api::AssetsApi as AssetsRuntimeApi;
+use pallet_support::types::AssetInfo;
+use primitive_types::U256;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait AssetsApi<BlockHash> {
+	#[method(name = "assets_get_asset")]
+	fn get_asset(&self, asset_id: U256, at: Option<BlockHash>) -> RpcResult<Option<AssetInfo>>;
+
+	#[method(name = "assets_get_collaterals")]
+	fn get_collaterals(&self, at: Option<BlockHash>) -> RpcResult<Vec<AssetInfo>>;
+
+	#[method(name = "assets_get_asset_by_address")]
+	fn get_asset_by_address(
+		&self,
+		chain: U256,
+		address: U256,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AssetInfo>>;
+}
+
+/// A struct that implements the `AssetsApi`.
+pub struct AssetsPallet<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> AssetsPallet<C, Block> {
+	/// Create new `AssetsPallet` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> AssetsApiServer<<Block as BlockT>::Hash> for AssetsPallet<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AssetsRuntimeApi<Block>,
+{
+	fn get_asset(
+		&self,
+		asset_id: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AssetInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_asset(at, asset_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_collaterals(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<AssetInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_collaterals(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_asset_by_address(
+		&self,
+		chain: U256,
+		address: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AssetInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_asset_by_address(at, chain, address).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
@@ -0,0 +1,104 @@
+//! Benchmarking setup for pallet-asset
+//!
+//! Asset management calls are only enabled with the `dev` feature, so their benchmarks are
+//! skipped unless the runtime is built with `--features runtime-benchmarks,dev`.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{AssetMap, AssetsCount, DELETION_LIMIT, IS_DEV_ENABLED};
+#[allow(unused)]
+use crate::Pallet as Assets;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	test_helpers::asset_helper::usdc, traits::AssetInterface, types::ExtendedAsset,
+};
+
+fn get_asset(id: u128) -> ExtendedAsset {
+	let mut extended_asset = usdc();
+	extended_asset.asset.id = id;
+
+	extended_asset
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn consume_nonce() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		consume_nonce(RawOrigin::Signed(caller));
+	}
+
+	#[benchmark]
+	fn replace_all_assets(a: Linear<1, 100>) -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Worst case clears as many existing assets as a single call can remove
+		for id in 0..DELETION_LIMIT {
+			Assets::<T>::add_asset_internal(get_asset(1000 + id as u128)).unwrap();
+		}
+		let assets: Vec<ExtendedAsset> = (1..=a).map(|id| get_asset(id as u128)).collect();
+
+		#[extrinsic_call]
+		replace_all_assets(RawOrigin::Signed(caller), assets);
+
+		assert_eq!(AssetsCount::<T>::get(), a as u64);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_asset() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let id = usdc().asset.id;
+		Assets::<T>::add_asset_internal(usdc()).unwrap();
+
+		#[extrinsic_call]
+		remove_asset(RawOrigin::Signed(caller), id);
+
+		assert!(!AssetMap::<T>::contains_key(id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_asset() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		Assets::<T>::add_asset_internal(usdc()).unwrap();
+		let updated_asset = usdc().set_version(2);
+
+		#[extrinsic_call]
+		update_asset(RawOrigin::Signed(caller), updated_asset.clone());
+
+		assert_eq!(AssetMap::<T>::get(updated_asset.asset.id), Some(updated_asset));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_asset() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		add_asset(RawOrigin::Signed(caller), usdc());
+
+		assert_eq!(AssetsCount::<T>::get(), 1);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Assets, crate::mock::new_test_ext(), crate::mock::Test);
+}
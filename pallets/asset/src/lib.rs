@@ -8,17 +8,25 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use core::option::Option;
 	use frame_support::{dispatch::Vec, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
 	use pallet_support::{
-		traits::AssetInterface,
+		traits::{AssetInterface, MarketInterface, TradingAccountInterface},
 		types::{Asset, ExtendedAsset},
 	};
+	use primitive_types::U256;
+
+	use super::WeightInfo;
 
-	static DELETION_LIMIT: u32 = 100;
+	pub(super) static DELETION_LIMIT: u32 = 100;
 	static DEFAULT_ASSET: u128 = 1431520323;
 
 	#[cfg(not(feature = "dev"))]
@@ -33,6 +41,9 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type MarketPallet: MarketInterface;
+		type TradingAccountPallet: TradingAccountInterface;
+		type WeightInfo: WeightInfo;
 	}
 
 	/// Stores the number of valid assets in the system
@@ -45,6 +56,11 @@ pub mod pallet {
 	#[pallet::getter(fn assets)]
 	pub(super) type AssetMap<T: Config> = StorageMap<_, Twox64Concat, u128, ExtendedAsset>;
 
+	/// Maps the unique_id to the assets removed in archived mode
+	#[pallet::storage]
+	#[pallet::getter(fn archived_assets)]
+	pub(super) type ArchivedAssetMap<T: Config> = StorageMap<_, Twox64Concat, u128, ExtendedAsset>;
+
 	/// Stores the default collateral in the system
 	#[pallet::storage]
 	#[pallet::getter(fn default_collateral_asset)]
@@ -60,6 +76,10 @@ pub mod pallet {
 		InvalidAsset,
 		/// Invalid Call to dev mode only function
 		DevOnlyCall,
+		/// Asset cannot be removed while a market refers to it
+		AssetUsedByMarket,
+		/// Asset cannot be removed while accounts hold a balance of it
+		AssetHasBalances,
 	}
 
 	#[pallet::event]
@@ -68,20 +88,21 @@ pub mod pallet {
 		AssetCreated { asset: ExtendedAsset },
 		AssetUpdated { asset: ExtendedAsset },
 		AssetRemoved { asset: ExtendedAsset },
+		AssetArchived { asset: ExtendedAsset },
 	}
 
 	// Pallet callable functions
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		// For back-end to retrieve nonce
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::consume_nonce())]
 		pub fn consume_nonce(_: OriginFor<T>) -> DispatchResult {
 			Ok(())
 		}
 
 		// TODO(merkle-groot): To be removed in production
 		/// Replace all assets
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::replace_all_assets(assets.len() as u32))]
 		pub fn replace_all_assets(
 			origin: OriginFor<T>,
 			assets: Vec<ExtendedAsset>,
@@ -114,7 +135,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::remove_asset())]
 		pub fn remove_asset(origin: OriginFor<T>, id: u128) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -127,12 +148,12 @@ pub mod pallet {
 			}
 
 			// Remove the asset
-			Self::remove_asset_internal(id);
+			Self::remove_asset_internal(id)?;
 			Ok(())
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_asset())]
 		pub fn update_asset(origin: OriginFor<T>, extended_asset: ExtendedAsset) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -150,7 +171,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_asset())]
 		pub fn add_asset(origin: OriginFor<T>, extended_asset: ExtendedAsset) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -163,6 +184,44 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> Pallet<T> {
+		pub fn get_collaterals() -> Vec<ExtendedAsset> {
+			AssetMap::<T>::iter_values()
+				.filter(|extended_asset| extended_asset.asset.is_collateral)
+				.collect()
+		}
+
+		pub fn get_asset_by_address(chain: u128, address: U256) -> Option<ExtendedAsset> {
+			AssetMap::<T>::iter_values().find(|extended_asset| {
+				extended_asset.asset_addresses.iter().any(|asset_address| {
+					asset_address.chain == chain && asset_address.address == address
+				})
+			})
+		}
+
+		/// Removes an asset from the asset map if no market or balance depends on it
+		fn take_removable_asset(id: u128) -> Result<ExtendedAsset, DispatchError> {
+			let extended_asset = AssetMap::<T>::get(id).ok_or(Error::<T>::InvalidAsset)?;
+
+			// Markets of the asset, archived ones included, must be removed first
+			ensure!(!T::MarketPallet::is_asset_used(id), Error::<T>::AssetUsedByMarket);
+
+			// Funds of the users would be orphaned by the removal
+			ensure!(!T::TradingAccountPallet::has_balances(id), Error::<T>::AssetHasBalances);
+
+			// Remove asset from the asset map
+			AssetMap::<T>::remove(id);
+
+			// Get the number of assets available
+			let length: u64 = AssetsCount::<T>::get();
+
+			// Decrease the asset count
+			AssetsCount::<T>::put(length - 1);
+
+			Ok(extended_asset)
+		}
+	}
+
 	impl<T: Config> AssetInterface for Pallet<T> {
 		fn add_asset_internal(extended_asset: ExtendedAsset) -> DispatchResult {
 			// Check if the asset exists
@@ -199,21 +258,26 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn remove_asset_internal(id: u128) {
+		fn remove_asset_internal(id: u128) -> DispatchResult {
 			// Get the asset to be emitted in the event
-			let extended_asset = AssetMap::<T>::get(id).unwrap();
+			let extended_asset = Self::take_removable_asset(id)?;
 
-			// Remove asset from the asset map
-			AssetMap::<T>::remove(id);
+			// Emit the asset removed event
+			Self::deposit_event(Event::AssetRemoved { asset: extended_asset });
 
-			// Get the number of assets available
-			let length: u64 = AssetsCount::<T>::get();
+			Ok(())
+		}
 
-			// Decrease the asset count
-			AssetsCount::<T>::put(length - 1);
+		fn archive_asset_internal(id: u128) -> DispatchResult {
+			let extended_asset = Self::take_removable_asset(id)?;
 
-			// Emit the asset removed event
-			Self::deposit_event(Event::AssetRemoved { asset: extended_asset });
+			// Keep the asset in the archive so that its history stays queryable
+			ArchivedAssetMap::<T>::insert(id, extended_asset.clone());
+
+			// Emit the asset archived event
+			Self::deposit_event(Event::AssetArchived { asset: extended_asset });
+
+			Ok(())
 		}
 
 		fn get_default_collateral() -> u128 {
@@ -1,9 +1,17 @@
 use crate as assets;
 use frame_support::traits::{ConstU16, ConstU64};
+use pallet_support::{
+    traits::{MarketInterface, TradingAccountInterface},
+    types::{
+        BalanceChangeReason, Direction, ExtendedMarket, FeeRecipients, FundModifyType, Market,
+        MarketState, ReferralDetails, TradingAccount, TradingAccountMinimal, VolumeType,
+    },
+};
+use primitive_types::U256;
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    BuildStorage,
+    BuildStorage, DispatchResult, FixedI128,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -45,6 +53,256 @@ impl frame_system::Config for Test {
 
 impl assets::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type MarketPallet = MarketStub;
+    type TradingAccountPallet = TradingAccountStub;
+    type WeightInfo = ();
+}
+
+// Asset removal only asks other pallets whether the asset is still in use
+pub struct MarketStub;
+impl MarketInterface for MarketStub {
+    fn get_market(_id: u128) -> Option<Market> {
+        unimplemented!()
+    }
+    fn add_market_internal(_extended_market: ExtendedMarket) -> DispatchResult {
+        unimplemented!()
+    }
+    fn update_market_internal(_extended_market: ExtendedMarket) -> DispatchResult {
+        unimplemented!()
+    }
+    fn update_market_with_notice_internal(_extended_market: ExtendedMarket) -> DispatchResult {
+        unimplemented!()
+    }
+    fn remove_market_internal(_id: u128) -> DispatchResult {
+        unimplemented!()
+    }
+    fn archive_market_internal(_id: u128) -> DispatchResult {
+        unimplemented!()
+    }
+    fn validate_market_details(_market: &Market) -> DispatchResult {
+        unimplemented!()
+    }
+    fn get_all_markets() -> Vec<u128> {
+        unimplemented!()
+    }
+    fn get_all_markets_by_state(_states: &[MarketState]) -> Vec<u128> {
+        unimplemented!()
+    }
+    fn is_asset_used(_asset_id: u128) -> bool {
+        false
+    }
+}
+
+pub struct TradingAccountStub;
+impl TradingAccountInterface for TradingAccountStub {
+    type VolumeError = ();
+    fn deposit_internal(
+        _trading_account: TradingAccountMinimal,
+        _collateral_id: u128,
+        _amount: FixedI128,
+    ) {
+        unimplemented!()
+    }
+    fn is_registered_user(_account: U256) -> bool {
+        unimplemented!()
+    }
+    fn get_balance(_account: U256, _asset_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn get_unused_balance(_account: U256, _asset_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn get_locked_margin(_account: U256, _asset_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn get_trading_account_id(_trading_account: TradingAccountMinimal) -> U256 {
+        unimplemented!()
+    }
+    fn set_locked_margin(_account: U256, _asset_id: u128, _amount: FixedI128) {
+        unimplemented!()
+    }
+    fn transfer(
+        _account_id: U256,
+        _collateral_id: u128,
+        _market_id: u128,
+        _amount: FixedI128,
+        _reason: BalanceChangeReason,
+    ) {
+        unimplemented!()
+    }
+    fn transfer_from(
+        _account_id: U256,
+        _collateral_id: u128,
+        _market_id: u128,
+        _amount: FixedI128,
+        _reason: BalanceChangeReason,
+    ) {
+        unimplemented!()
+    }
+    fn get_account(_account_id: &U256) -> Option<TradingAccount> {
+        unimplemented!()
+    }
+    fn get_public_key(_account: &U256) -> Option<U256> {
+        unimplemented!()
+    }
+    fn get_margin_info(
+        _account_id: U256,
+        _collateral_id: u128,
+        _new_position_maintanence_requirement: FixedI128,
+        _new_position_margin: FixedI128,
+    ) -> (bool, FixedI128, FixedI128, FixedI128, FixedI128, FixedI128) {
+        unimplemented!()
+    }
+    fn get_isolated_margin_info(
+        _account_id: U256,
+        _market_id: u128,
+        _direction: Direction,
+        _new_position_maintanence_requirement: FixedI128,
+        _new_position_margin: FixedI128,
+    ) -> (bool, FixedI128, FixedI128) {
+        unimplemented!()
+    }
+    fn get_account_list(_start_index: u128, _end_index: u128) -> Vec<U256> {
+        unimplemented!()
+    }
+    fn add_deferred_balance(_account_id: U256, _collateral_id: u128) -> DispatchResult {
+        unimplemented!()
+    }
+    fn get_accounts_count() -> u128 {
+        unimplemented!()
+    }
+    fn has_balances(_asset_id: u128) -> bool {
+        false
+    }
+    fn get_collaterals_of_user(_account_id: U256) -> Vec<u128> {
+        unimplemented!()
+    }
+    fn get_amount_to_withdraw(_account_id: U256, _collateral_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn update_and_get_cumulative_volume(
+        _monetary_account_address: U256,
+        _market_id: u128,
+        _new_volume: FixedI128,
+        _volume_update_type: VolumeType,
+    ) -> Result<FixedI128, Self::VolumeError> {
+        unimplemented!()
+    }
+    fn update_and_get_user_and_master_volume(
+        _account_id: U256,
+        _market_id: u128,
+        _new_volume: FixedI128,
+    ) -> Result<(FixedI128, FixedI128), Self::VolumeError> {
+        unimplemented!()
+    }
+    fn get_30day_user_volume(
+        _account_id: U256,
+        _market_id: u128,
+    ) -> Result<FixedI128, Self::VolumeError> {
+        unimplemented!()
+    }
+    fn get_30day_master_volume(
+        _monetary_account_address: U256,
+        _market_id: u128,
+    ) -> Result<FixedI128, Self::VolumeError> {
+        unimplemented!()
+    }
+    fn get_30day_volume(
+        _monetary_account_address: U256,
+        _market_id: u128,
+        _volume_type: VolumeType,
+    ) -> Result<FixedI128, Self::VolumeError> {
+        unimplemented!()
+    }
+    fn add_referral_internal(
+        _referral_account_address: U256,
+        _referral_details: ReferralDetails,
+        _referral_code: U256,
+    ) -> bool {
+        unimplemented!()
+    }
+    fn update_master_account_level_internal(_master_account_address: U256, _level: u8) {
+        unimplemented!()
+    }
+    fn get_fee_discount(_trading_account_id: U256) -> FixedI128 {
+        unimplemented!()
+    }
+    fn get_account_address_and_referral_details(_account_id: U256) -> Option<ReferralDetails> {
+        unimplemented!()
+    }
+    fn get_master_account_level(_account_address: U256) -> u8 {
+        unimplemented!()
+    }
+    fn update_master_fee_share(
+        _account_address: U256,
+        _collateral_id: u128,
+        _current_fee_share: FixedI128,
+    ) {
+        unimplemented!()
+    }
+    fn handle_fee_split(
+        _account_id: U256,
+        _collateral_id: u128,
+        _market_id: u128,
+        _amount: FixedI128,
+        _fee_share_amount: FixedI128,
+        _rebate_amount: FixedI128,
+    ) {
+        unimplemented!()
+    }
+    fn handle_insurance_fund_update(
+        _collateral_id: u128,
+        _market_id: u128,
+        _amount: FixedI128,
+        _modify_type: FundModifyType,
+    ) {
+        unimplemented!()
+    }
+    fn update_fee_split_details_internal(
+        _market_id: u128,
+        _insurance_fund: U256,
+        _fee_split: FixedI128,
+    ) {
+        unimplemented!()
+    }
+    fn update_fee_recipients_internal(_market_id: u128, _fee_recipients: FeeRecipients) -> bool {
+        unimplemented!()
+    }
+    fn get_fee_split_details(_market_id: u128) -> (U256, FixedI128) {
+        unimplemented!()
+    }
+    fn get_insurance_fund_balance(_insurance_fund: U256, _collateral_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn update_insurance_fund_balance_internal(
+        _insurance_fund: U256,
+        _collateral_id: u128,
+        _amount: FixedI128,
+    ) {
+        unimplemented!()
+    }
+    fn transfer_collateral(
+        _account_id: U256,
+        _collateral_id: u128,
+        _amount: FixedI128,
+        _reason: BalanceChangeReason,
+    ) {
+        unimplemented!()
+    }
+    fn deposit_to_insurance_fund(
+        _insurance_fund: U256,
+        _collateral_id: u128,
+        _amount: FixedI128,
+        _reason: BalanceChangeReason,
+    ) {
+        unimplemented!()
+    }
+    fn get_unallocated_revenue(_collateral_id: u128) -> FixedI128 {
+        unimplemented!()
+    }
+    fn update_unallocated_revenue(_collateral_id: u128, _amount: FixedI128) {
+        unimplemented!()
+    }
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,9 +1,11 @@
-use crate::mock::*;
+use crate::{mock::*, Event};
 use frame_support::assert_ok;
 use pallet_support::{
-	test_helpers::asset_helper::{btc, eth, link, usdc},
+	test_helpers::asset_helper::{btc, eth, link, usdc, usdt},
+	traits::AssetInterface,
 	types::ExtendedAsset,
 };
+use primitive_types::U256;
 
 fn setup() -> (sp_io::TestExternalities, Vec<ExtendedAsset>) {
 	// Create a new test environment
@@ -216,3 +218,58 @@ fn test_remove_already_removed_asset() {
 		assert_ok!(AssetModule::remove_asset(RuntimeOrigin::signed(1), eth_asset.asset.id));
 	});
 }
+
+#[test]
+fn test_get_collaterals() {
+	let (mut env, assets) = setup();
+
+	env.execute_with(|| {
+		assert_ok!(AssetModule::replace_all_assets(RuntimeOrigin::signed(1), assets));
+		assert_ok!(AssetModule::add_asset(RuntimeOrigin::signed(1), usdt()));
+
+		// Only usdc and usdt are collaterals
+		let mut collaterals = AssetModule::get_collaterals();
+		collaterals.sort_by_key(|extended_asset| extended_asset.asset.id);
+		assert_eq!(collaterals, vec![usdc(), usdt()]);
+	});
+}
+
+#[test]
+fn test_get_asset_by_address() {
+	let (mut env, assets) = setup();
+	let usdc_asset = &assets[1];
+	let usdc_address = &usdc_asset.asset_addresses[0];
+
+	env.execute_with(|| {
+		assert_ok!(AssetModule::replace_all_assets(RuntimeOrigin::signed(1), assets.clone()));
+
+		assert_eq!(
+			AssetModule::get_asset_by_address(usdc_address.chain, usdc_address.address),
+			Some(usdc_asset.clone())
+		);
+
+		// Address is only resolved on the chain it was registered for
+		assert_eq!(
+			AssetModule::get_asset_by_address(usdc_address.chain + 1, usdc_address.address),
+			None
+		);
+		assert_eq!(AssetModule::get_asset_by_address(usdc_address.chain, U256::from(999)), None);
+	});
+}
+
+#[test]
+fn test_archive_asset() {
+	let (mut env, assets) = setup();
+
+	env.execute_with(|| {
+		assert_ok!(AssetModule::replace_all_assets(RuntimeOrigin::signed(1), assets));
+
+		assert_ok!(AssetModule::archive_asset_internal(link().asset.id));
+
+		// The asset is no longer available but stays queryable
+		assert_eq!(AssetModule::assets_count(), 3);
+		assert_eq!(AssetModule::get_asset(link().asset.id), None);
+		assert_eq!(AssetModule::archived_assets(link().asset.id).unwrap(), link());
+		System::assert_last_event(Event::AssetArchived { asset: link() }.into());
+	});
+}
@@ -0,0 +1,103 @@
+//! Weights for pallet_asset
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_asset.
+pub trait WeightInfo {
+	fn consume_nonce() -> Weight;
+	fn replace_all_assets(a: u32, ) -> Weight;
+	fn remove_asset() -> Weight;
+	fn update_asset() -> Weight;
+	fn add_asset() -> Weight;
+}
+
+/// Weights for pallet_asset using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn consume_nonce() -> Weight {
+		Weight::from_parts(5_000_000, 0)
+	}
+	/// Storage: Assets AssetMap (r:101 w:200)
+	/// Storage: Assets AssetsCount (r:0 w:1)
+	/// The range of component `a` is `[1, 100]`.
+	fn replace_all_assets(a: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(100_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(101_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	/// Storage: Assets AssetsCount (r:1 w:1)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: Markets ArchivedMarketMap (r:100 w:0)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:0)
+	fn remove_asset() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(203_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	fn update_asset() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	/// Storage: Assets AssetsCount (r:1 w:1)
+	fn add_asset() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn consume_nonce() -> Weight {
+		Weight::from_parts(5_000_000, 0)
+	}
+	/// Storage: Assets AssetMap (r:101 w:200)
+	/// Storage: Assets AssetsCount (r:0 w:1)
+	/// The range of component `a` is `[1, 100]`.
+	fn replace_all_assets(a: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(100_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(101_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	/// Storage: Assets AssetsCount (r:1 w:1)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: Markets ArchivedMarketMap (r:100 w:0)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:0)
+	fn remove_asset() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(203_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	fn update_asset() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetMap (r:1 w:1)
+	/// Storage: Assets AssetsCount (r:1 w:1)
+	fn add_asset() -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
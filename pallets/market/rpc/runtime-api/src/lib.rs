@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::dispatch::Vec;
+use pallet_support::types::{MarketInfo, MarketState, ScheduledMarketChange};
+use primitive_types::U256;
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime file (the `runtime-api/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+	pub trait MarketsApi {
+		fn get_market(market_id: U256) -> Option<MarketInfo>;
+		fn get_markets_by_state(states: Vec<MarketState>) -> Vec<MarketInfo>;
+		fn get_pending_market_changes() -> Vec<ScheduledMarketChange>;
+	}
+}
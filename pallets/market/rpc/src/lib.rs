@@ -0,0 +1,95 @@
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_market_runtime_api::MarketsApi as MarketsRuntimeApi;
+use pallet_support::types::{MarketInfo, MarketState, ScheduledMarketChange};
+use primitive_types::U256;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait MarketsApi<BlockHash> {
+	#[method(name = "markets_get_market")]
+	fn get_market(&self, market_id: U256, at: Option<BlockHash>) -> RpcResult<Option<MarketInfo>>;
+
+	#[method(name = "markets_get_markets_by_state")]
+	fn get_markets_by_state(
+		&self,
+		states: Vec<MarketState>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<MarketInfo>>;
+
+	#[method(name = "markets_get_pending_changes")]
+	fn get_pending_market_changes(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ScheduledMarketChange>>;
+}
+
+/// A struct that implements the `MarketsApi`.
+pub struct MarketsPallet<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> MarketsPallet<C, Block> {
+	/// Create new `MarketsPallet` instance with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> MarketsApiServer<<Block as BlockT>::Hash> for MarketsPallet<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: MarketsRuntimeApi<Block>,
+{
+	fn get_market(
+		&self,
+		market_id: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<MarketInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_market(at, market_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_markets_by_state(
+		&self,
+		states: Vec<MarketState>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<MarketInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_markets_by_state(at, states).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_pending_market_changes(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ScheduledMarketChange>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_pending_market_changes(at).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+const RUNTIME_ERROR: i32 = 1;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
@@ -0,0 +1,153 @@
+//! Benchmarking setup for pallet-market
+//!
+//! Market management calls are only enabled with the `dev` feature, so their benchmarks are
+//! skipped unless the runtime is built with `--features runtime-benchmarks,dev`.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{
+	MarketChangeNoticePeriod, MarketChangeTimestamps, MarketMap, MarketsCount,
+	ScheduledMarketChangeMap, DELETION_LIMIT, IS_DEV_ENABLED,
+};
+#[allow(unused)]
+use crate::Pallet as Markets;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	test_helpers::{
+		asset_helper::{btc, usdc},
+		market_helper::btc_usdc,
+	},
+	traits::{AssetInterface, MarketInterface},
+	types::{ExtendedMarket, MarketState},
+};
+use sp_arithmetic::fixed_point::FixedI128;
+
+fn setup_assets<T: Config>() {
+	T::AssetPallet::add_asset_internal(usdc()).unwrap();
+	T::AssetPallet::add_asset_internal(btc()).unwrap();
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn replace_all_markets(m: Linear<1, 100>) -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		setup_assets::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Worst case clears as many existing markets as a single call can remove
+		for id in 0..DELETION_LIMIT {
+			Markets::<T>::add_market_internal(btc_usdc().set_id(1000 + id as u128)).unwrap();
+		}
+		let markets: Vec<ExtendedMarket> =
+			(1..=m).map(|id| btc_usdc().set_id(id as u128)).collect();
+
+		#[extrinsic_call]
+		replace_all_markets(RawOrigin::Signed(caller), markets);
+
+		assert_eq!(MarketsCount::<T>::get(), m as u64);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_market() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		setup_assets::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		add_market(RawOrigin::Signed(caller), btc_usdc());
+
+		assert_eq!(MarketsCount::<T>::get(), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_market() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		setup_assets::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		Markets::<T>::add_market_internal(btc_usdc()).unwrap();
+		// Worst case replaces a change of risk parameters scheduled under another timestamp
+		MarketChangeNoticePeriod::<T>::put(7200);
+		Markets::<T>::update_market_with_notice_internal(
+			btc_usdc().set_currently_allowed_leverage(FixedI128::from(4)),
+		)
+		.unwrap();
+		MarketChangeNoticePeriod::<T>::put(3600);
+		let updated_market = btc_usdc().set_currently_allowed_leverage(FixedI128::from(5));
+
+		#[extrinsic_call]
+		update_market(RawOrigin::Signed(caller), updated_market.clone());
+
+		assert!(ScheduledMarketChangeMap::<T>::contains_key(updated_market.market.id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_market() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		setup_assets::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let id = btc_usdc().market.id;
+		Markets::<T>::add_market_internal(btc_usdc()).unwrap();
+
+		#[extrinsic_call]
+		remove_market(RawOrigin::Signed(caller), id);
+
+		assert!(!MarketMap::<T>::contains_key(id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_market_state() {
+		setup_assets::<T>();
+		let id = btc_usdc().market.id;
+		Markets::<T>::add_market_internal(btc_usdc()).unwrap();
+
+		#[extrinsic_call]
+		set_market_state(RawOrigin::Root, id, MarketState::ReduceOnly);
+
+		assert_eq!(MarketMap::<T>::get(id).unwrap().market.state, MarketState::ReduceOnly);
+	}
+
+	#[benchmark]
+	fn set_market_change_notice_period() {
+		#[extrinsic_call]
+		set_market_change_notice_period(RawOrigin::Root, 3600);
+
+		assert_eq!(MarketChangeNoticePeriod::<T>::get(), 3600);
+	}
+
+	#[benchmark]
+	fn cancel_market_change() {
+		setup_assets::<T>();
+		let id = btc_usdc().market.id;
+		Markets::<T>::add_market_internal(btc_usdc()).unwrap();
+		MarketChangeNoticePeriod::<T>::put(3600);
+		Markets::<T>::update_market_with_notice_internal(
+			btc_usdc().set_currently_allowed_leverage(FixedI128::from(5)),
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		cancel_market_change(RawOrigin::Root, id);
+
+		assert!(!ScheduledMarketChangeMap::<T>::contains_key(id));
+		assert!(MarketChangeTimestamps::<T>::get().is_empty());
+	}
+
+	impl_benchmark_test_suite!(Markets, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -8,21 +8,34 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod migrations;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use core::option::Option;
 	use frame_support::{
 		dispatch::Vec,
 		pallet_prelude::{DispatchResult, *},
+		traits::UnixTime,
 	};
 	use frame_system::pallet_prelude::*;
 	use pallet_support::{
-		traits::{AssetInterface, MarketInterface, PricesInterface},
-		types::{ExtendedMarket, Market},
+		traits::{AssetInterface, MarketInterface, PricesInterface, TradingInterface},
+		types::{ExtendedMarket, Market, MarketState, ScheduledMarketChange},
 	};
 	use sp_arithmetic::fixed_point::FixedI128;
 
-	static DELETION_LIMIT: u32 = 100;
+	use super::WeightInfo;
+
+	pub(super) static DELETION_LIMIT: u32 = 100;
+
+	// Maximum no.of scheduled market changes applied in on_initialize of a single block
+	pub(super) static MAX_MARKET_CHANGES_APPLIED: u32 = 20;
 
 	#[cfg(not(feature = "dev"))]
 	pub const IS_DEV_ENABLED: bool = false;
@@ -30,7 +43,10 @@ pub mod pallet {
 	#[cfg(feature = "dev")]
 	pub const IS_DEV_ENABLED: bool = true;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -38,6 +54,9 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type AssetPallet: AssetInterface;
 		type PricesPallet: PricesInterface;
+		type TradingPallet: TradingInterface;
+		type TimeProvider: UnixTime;
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -49,6 +68,34 @@ pub mod pallet {
 	#[pallet::getter(fn markets)]
 	pub(super) type MarketMap<T: Config> = StorageMap<_, Twox64Concat, u128, ExtendedMarket>;
 
+	/// Maps the unique_id to the markets removed in archived mode
+	#[pallet::storage]
+	#[pallet::getter(fn archived_markets)]
+	pub(super) type ArchivedMarketMap<T: Config> =
+		StorageMap<_, Twox64Concat, u128, ExtendedMarket>;
+
+	/// Time in seconds between scheduling a change of risk parameters and applying it
+	#[pallet::storage]
+	#[pallet::getter(fn market_change_notice_period)]
+	pub(super) type MarketChangeNoticePeriod<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Maps the market id to the change of risk parameters waiting to be applied
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_market_changes)]
+	pub(super) type ScheduledMarketChangeMap<T: Config> =
+		StorageMap<_, Twox64Concat, u128, ScheduledMarketChange>;
+
+	/// Maps the effective timestamp to the markets whose changes are applied at it
+	#[pallet::storage]
+	#[pallet::getter(fn market_changes_by_timestamp)]
+	pub(super) type MarketChangesByTimestamp<T: Config> =
+		StorageMap<_, Twox64Concat, u64, Vec<u128>, ValueQuery>;
+
+	/// Effective timestamps of the scheduled changes in ascending order
+	#[pallet::storage]
+	#[pallet::getter(fn market_change_timestamps)]
+	pub(super) type MarketChangeTimestamps<T: Config> = StorageValue<_, Vec<u64>, ValueQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Each market must have a unique identifier
@@ -69,6 +116,16 @@ pub mod pallet {
 		NotAdmin,
 		/// Invalid Call to dev mode only function
 		DevOnlyCall,
+		/// Invalid value for max open interest
+		InvalidMaxOpenInterest,
+		/// Market cannot move from its current state to the requested one
+		InvalidMarketStateTransition,
+		/// No change is scheduled for the market
+		MarketChangeNotFound,
+		/// Market cannot be removed while it has open interest
+		MarketHasOpenInterest,
+		/// Market cannot be removed while it has open positions
+		MarketHasOpenPositions,
 	}
 
 	#[pallet::event]
@@ -80,6 +137,98 @@ pub mod pallet {
 		MarketUpdated { market: ExtendedMarket },
 		/// Market successfully removed
 		MarketRemoved { market: ExtendedMarket },
+		/// Market removed and kept in the archive
+		MarketArchived { market: ExtendedMarket },
+		/// Market moved to a new state in its lifecycle
+		MarketStateUpdated { market_id: u128, previous_state: MarketState, state: MarketState },
+		/// Notice period for changes of risk parameters updated
+		MarketChangeNoticePeriodSet { notice_period: u64 },
+		/// Change of risk parameters queued until the effective timestamp
+		MarketChangeScheduled { market: Market, effective_timestamp: u64 },
+		/// Scheduled change of risk parameters applied to the market
+		MarketChangeApplied { market_id: u128 },
+		/// Scheduled change of risk parameters could not be applied and was dropped
+		MarketChangeFailed { market_id: u128 },
+		/// Scheduled change of risk parameters cancelled
+		MarketChangeCancelled { market_id: u128 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// During on_initialize, now() returns the timestamp of the parent block,
+			// so a change is applied in the block following the first one past its timestamp
+			let current_timestamp = T::TimeProvider::now().as_secs();
+			// Reads of the current timestamp and the effective timestamps
+			let mut reads: u64 = 2;
+			let mut writes: u64 = 0;
+
+			// Timestamps are kept sorted, so the due ones are at the front
+			let mut timestamps = MarketChangeTimestamps::<T>::get();
+			let due_count = timestamps.partition_point(|&timestamp| timestamp <= current_timestamp);
+			if due_count == 0 {
+				return T::DbWeight::get().reads(reads)
+			}
+
+			// Changes beyond the per block limit are left for the following blocks
+			let mut applied_count: u32 = 0;
+			let mut completed_count: usize = 0;
+			for &effective_timestamp in timestamps[..due_count].iter() {
+				if applied_count == MAX_MARKET_CHANGES_APPLIED {
+					break
+				}
+
+				let mut market_ids = MarketChangesByTimestamp::<T>::take(effective_timestamp);
+				reads += 1;
+				writes += 1;
+
+				let remaining_count = (MAX_MARKET_CHANGES_APPLIED - applied_count) as usize;
+				let pending_market_ids = if market_ids.len() > remaining_count {
+					market_ids.split_off(remaining_count)
+				} else {
+					Vec::new()
+				};
+
+				for market_id in market_ids {
+					applied_count += 1;
+					let scheduled_change = ScheduledMarketChangeMap::<T>::take(market_id);
+					reads += 1;
+					writes += 1;
+					let scheduled_change = match scheduled_change {
+						Some(scheduled_change) => scheduled_change,
+						None => continue,
+					};
+					// Reads of the market and its assets and write of the market
+					reads += 3;
+					writes += 1;
+
+					match Self::apply_market_change(market_id, &scheduled_change.market) {
+						Ok(_) => Self::deposit_event(Event::MarketChangeApplied { market_id }),
+						Err(_) => Self::deposit_event(Event::MarketChangeFailed { market_id }),
+					}
+				}
+
+				if !pending_market_ids.is_empty() {
+					MarketChangesByTimestamp::<T>::insert(effective_timestamp, pending_market_ids);
+					writes += 1;
+					break
+				}
+				completed_count += 1;
+			}
+
+			if completed_count > 0 {
+				timestamps.drain(..completed_count);
+				MarketChangeTimestamps::<T>::put(timestamps);
+				writes += 1;
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		fn on_runtime_upgrade() -> frame_support::weights::Weight {
+			super::migrations::migrations::migrate_to_v1::<T>()
+				.saturating_add(super::migrations::migrations::migrate_to_v2::<T>())
+		}
 	}
 
 	// Pallet callable functions
@@ -87,7 +236,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		// TODO(merkle-groot): To be removed in production
 		/// Replace all markets
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::replace_all_markets(markets.len() as u32))]
 		pub fn replace_all_markets(
 			origin: OriginFor<T>,
 			markets: Vec<ExtendedMarket>,
@@ -97,8 +246,11 @@ pub mod pallet {
 			}
 			ensure_signed(origin)?;
 
-			// Clear market map
+			// Clear market map and the changes scheduled for the old markets
 			let _ = MarketMap::<T>::clear(DELETION_LIMIT, None);
+			let _ = ScheduledMarketChangeMap::<T>::clear(DELETION_LIMIT, None);
+			let _ = MarketChangesByTimestamp::<T>::clear(DELETION_LIMIT, None);
+			MarketChangeTimestamps::<T>::kill();
 
 			let length: u64 = u64::try_from(markets.len()).unwrap();
 
@@ -128,6 +280,12 @@ pub mod pallet {
 						.contains(&current_market.currently_allowed_leverage),
 					Error::<T>::InvalidLeverage
 				);
+				if let Some(max_open_interest) = current_market.max_open_interest {
+					ensure!(
+						max_open_interest > FixedI128::from_inner(0),
+						Error::<T>::InvalidMaxOpenInterest
+					);
+				}
 
 				MarketMap::<T>::insert(current_market.id, extended_market.clone());
 
@@ -140,7 +298,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_market())]
 		pub fn add_market(origin: OriginFor<T>, extended_market: ExtendedMarket) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -154,7 +312,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_market())]
 		pub fn update_market(
 			origin: OriginFor<T>,
 			extended_market: ExtendedMarket,
@@ -170,14 +328,14 @@ pub mod pallet {
 				Error::<T>::InvalidMarket
 			);
 
-			// Add the market
-			Self::update_market_internal(extended_market)?;
+			// Update the market, changes of risk parameters wait for the notice period
+			Self::update_market_with_notice_internal(extended_market)?;
 
 			Ok(())
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::remove_market())]
 		pub fn remove_market(origin: OriginFor<T>, id: u128) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -188,7 +346,149 @@ pub mod pallet {
 			ensure!(MarketMap::<T>::contains_key(id), Error::<T>::InvalidMarket);
 
 			// Remove the market
-			Self::remove_market_internal(id);
+			Self::remove_market_internal(id)?;
+			Ok(())
+		}
+
+		/// Move a market to the next state of its lifecycle
+		#[pallet::weight(T::WeightInfo::set_market_state())]
+		pub fn set_market_state(
+			origin: OriginFor<T>,
+			market_id: u128,
+			state: MarketState,
+		) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			let mut extended_market =
+				MarketMap::<T>::get(market_id).ok_or(Error::<T>::InvalidMarket)?;
+			extended_market.market.state = state;
+
+			Self::update_market_internal(extended_market)?;
+
+			Ok(())
+		}
+
+		/// Set the notice period for changes of risk parameters
+		#[pallet::weight(T::WeightInfo::set_market_change_notice_period())]
+		pub fn set_market_change_notice_period(
+			origin: OriginFor<T>,
+			notice_period: u64,
+		) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			MarketChangeNoticePeriod::<T>::put(notice_period);
+
+			Self::deposit_event(Event::MarketChangeNoticePeriodSet { notice_period });
+
+			Ok(())
+		}
+
+		/// Cancel the scheduled change of risk parameters of a market
+		#[pallet::weight(T::WeightInfo::cancel_market_change())]
+		pub fn cancel_market_change(origin: OriginFor<T>, market_id: u128) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			Self::unschedule_market_change(market_id).ok_or(Error::<T>::MarketChangeNotFound)?;
+
+			Self::deposit_event(Event::MarketChangeCancelled { market_id });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn get_pending_market_changes() -> Vec<ScheduledMarketChange> {
+			MarketChangeTimestamps::<T>::get()
+				.into_iter()
+				.flat_map(|timestamp| MarketChangesByTimestamp::<T>::get(timestamp))
+				.filter_map(|market_id| ScheduledMarketChangeMap::<T>::get(market_id))
+				.collect()
+		}
+
+		pub fn get_markets_by_state(states: &[MarketState]) -> Vec<ExtendedMarket> {
+			Self::get_all_markets_by_state(states)
+				.into_iter()
+				.filter_map(|market_id| MarketMap::<T>::get(market_id))
+				.collect()
+		}
+
+		/// Removes a market from the market map if no position depends on it
+		fn take_removable_market(id: u128) -> Result<ExtendedMarket, DispatchError> {
+			let extended_market = MarketMap::<T>::get(id).ok_or(Error::<T>::InvalidMarket)?;
+
+			// Positions of the market would be orphaned by the removal
+			ensure!(
+				T::TradingPallet::get_open_interest(id) == FixedI128::from_inner(0),
+				Error::<T>::MarketHasOpenInterest
+			);
+			ensure!(
+				T::TradingPallet::get_no_of_delisted_market_positions(id) == 0,
+				Error::<T>::MarketHasOpenPositions
+			);
+
+			// Remove market from the market map along with its scheduled change
+			MarketMap::<T>::remove(id);
+			Self::unschedule_market_change(id);
+
+			// Decrease the market count
+			// Get the number of markets available
+			let length: u64 = MarketsCount::<T>::get();
+			MarketsCount::<T>::put(length - 1);
+
+			Ok(extended_market)
+		}
+
+		/// Queues a change of risk parameters under its effective timestamp,
+		/// replacing the change already scheduled for the market
+		fn schedule_market_change(scheduled_change: ScheduledMarketChange) {
+			let market_id = scheduled_change.market.id;
+			let effective_timestamp = scheduled_change.effective_timestamp;
+			Self::unschedule_market_change(market_id);
+
+			MarketChangesByTimestamp::<T>::append(effective_timestamp, market_id);
+			MarketChangeTimestamps::<T>::mutate(|timestamps| {
+				if let Err(index) = timestamps.binary_search(&effective_timestamp) {
+					timestamps.insert(index, effective_timestamp);
+				}
+			});
+			ScheduledMarketChangeMap::<T>::insert(market_id, scheduled_change);
+		}
+
+		/// Removes the change scheduled for the market along with its timestamp entry
+		fn unschedule_market_change(market_id: u128) -> Option<ScheduledMarketChange> {
+			let scheduled_change = ScheduledMarketChangeMap::<T>::take(market_id)?;
+			let effective_timestamp = scheduled_change.effective_timestamp;
+
+			let mut market_ids = MarketChangesByTimestamp::<T>::get(effective_timestamp);
+			market_ids.retain(|&id| id != market_id);
+			if market_ids.is_empty() {
+				MarketChangesByTimestamp::<T>::remove(effective_timestamp);
+				MarketChangeTimestamps::<T>::mutate(|timestamps| {
+					timestamps.retain(|&timestamp| timestamp != effective_timestamp)
+				});
+			} else {
+				MarketChangesByTimestamp::<T>::insert(effective_timestamp, market_ids);
+			}
+
+			Some(scheduled_change)
+		}
+
+		/// Applies the risk parameters of a scheduled change to the market,
+		/// its state and the rest of its details are left as they are now
+		fn apply_market_change(market_id: u128, market: &Market) -> DispatchResult {
+			let mut extended_market =
+				MarketMap::<T>::get(market_id).ok_or(Error::<T>::InvalidMarket)?;
+			extended_market.market.set_risk_parameters(market);
+			Self::validate_market_details(&extended_market.market)?;
+
+			MarketMap::<T>::insert(market_id, extended_market.clone());
+
+			// Emit the market updated event
+			Self::deposit_event(Event::MarketUpdated { market: extended_market });
+
 			Ok(())
 		}
 	}
@@ -222,11 +522,33 @@ pub mod pallet {
 			// Validate the market details
 			Self::validate_market_details(&extended_market.market)?;
 
+			// Validate the state transition
+			let market_id = extended_market.market.id;
+			let state = extended_market.market.state;
+			let previous_state = MarketMap::<T>::get(market_id)
+				.map_or(MarketState::Pending, |previous| previous.market.state);
+			ensure!(
+				previous_state.can_transition_to(state),
+				Error::<T>::InvalidMarketStateTransition
+			);
+			// A delisted market is settled only after all its positions are closed
+			if previous_state == MarketState::Delisting && state == MarketState::Settled {
+				ensure!(
+					T::TradingPallet::get_no_of_delisted_market_positions(market_id) == 0,
+					Error::<T>::MarketHasOpenPositions
+				);
+			}
+
 			// Replace the market in the market map
-			MarketMap::<T>::insert(extended_market.market.id, extended_market.clone());
+			MarketMap::<T>::insert(market_id, extended_market.clone());
+
+			// Positions of a delisted market are closed at the price set here
+			if state == MarketState::Delisting {
+				T::PricesPallet::set_mark_price_for_ads(market_id)?;
+			}
 
-			if extended_market.market.is_tradable == false {
-				T::PricesPallet::set_mark_price_for_ads(extended_market.market.id)?;
+			if previous_state != state {
+				Self::deposit_event(Event::MarketStateUpdated { market_id, previous_state, state });
 			}
 
 			// Emit the market updated event
@@ -235,20 +557,61 @@ pub mod pallet {
 			Ok(())
 		}
 
-		fn remove_market_internal(id: u128) {
-			// Get the market to be emitted in the event
-			let extended_market = MarketMap::<T>::get(id).unwrap();
+		fn update_market_with_notice_internal(extended_market: ExtendedMarket) -> DispatchResult {
+			let market_id = extended_market.market.id;
+			let current_market = MarketMap::<T>::get(market_id).ok_or(Error::<T>::InvalidMarket)?;
+			let notice_period = MarketChangeNoticePeriod::<T>::get();
 
-			// Remove market from the market map
-			MarketMap::<T>::remove(id);
+			if notice_period == 0 ||
+				extended_market.market.has_same_risk_parameters(&current_market.market)
+			{
+				return Self::update_market_internal(extended_market)
+			}
 
-			// Decrease the market count
-			// Get the number of markets available
-			let length: u64 = MarketsCount::<T>::get();
-			MarketsCount::<T>::put(length - 1);
+			// Validate the risk parameters before queueing them
+			Self::validate_market_details(&extended_market.market)?;
+
+			// Apply the rest of the update right away
+			let mut immediate_market = extended_market.clone();
+			immediate_market.market.set_risk_parameters(&current_market.market);
+			Self::update_market_internal(immediate_market)?;
+
+			// A newer change replaces the one already scheduled for the market
+			let effective_timestamp = T::TimeProvider::now().as_secs() + notice_period;
+			Self::schedule_market_change(ScheduledMarketChange {
+				market: extended_market.market.clone(),
+				effective_timestamp,
+			});
+
+			Self::deposit_event(Event::MarketChangeScheduled {
+				market: extended_market.market,
+				effective_timestamp,
+			});
+
+			Ok(())
+		}
+
+		fn remove_market_internal(id: u128) -> DispatchResult {
+			// Get the market to be emitted in the event
+			let extended_market = Self::take_removable_market(id)?;
 
 			// Emit the market removed event
 			Self::deposit_event(Event::MarketRemoved { market: extended_market });
+
+			Ok(())
+		}
+
+		fn archive_market_internal(id: u128) -> DispatchResult {
+			let mut extended_market = Self::take_removable_market(id)?;
+
+			// Keep the market in the archive so that its history stays queryable
+			extended_market.market.state = MarketState::Archived;
+			ArchivedMarketMap::<T>::insert(id, extended_market.clone());
+
+			// Emit the market archived event
+			Self::deposit_event(Event::MarketArchived { market: extended_market });
+
+			Ok(())
 		}
 
 		fn validate_market_details(market: &Market) -> DispatchResult {
@@ -264,6 +627,12 @@ pub mod pallet {
 					.contains(&market.currently_allowed_leverage),
 				Error::<T>::InvalidLeverage
 			);
+			if let Some(max_open_interest) = market.max_open_interest {
+				ensure!(
+					max_open_interest > FixedI128::from_inner(0),
+					Error::<T>::InvalidMaxOpenInterest
+				);
+			}
 
 			// Check Asset properties
 			// Validate asset and asset collateral
@@ -292,16 +661,24 @@ pub mod pallet {
 			markets
 		}
 
-		fn get_all_markets_by_state(is_tradable: bool, is_archived: bool) -> Vec<u128> {
+		fn get_all_markets_by_state(states: &[MarketState]) -> Vec<u128> {
 			let mut markets = Vec::<u128>::new();
 			for (key, value) in MarketMap::<T>::iter() {
-				if value.market.is_tradable == is_tradable &&
-					value.market.is_archived == is_archived
-				{
+				if states.contains(&value.market.state) {
 					markets.push(key);
 				}
 			}
 			markets
 		}
+
+		fn is_asset_used(asset_id: u128) -> bool {
+			// Archived markets keep referring to their assets for their history
+			let refers_to_asset = |extended_market: ExtendedMarket| {
+				extended_market.market.asset == asset_id ||
+					extended_market.market.asset_collateral == asset_id
+			};
+			MarketMap::<T>::iter_values().any(refers_to_asset) ||
+				ArchivedMarketMap::<T>::iter_values().any(refers_to_asset)
+		}
 	}
 }
@@ -0,0 +1,186 @@
+use super::*;
+
+pub mod migrations {
+	use super::*;
+	use codec::{Decode, Encode};
+	use frame_support::{
+		storage_alias,
+		traits::{ConstU32, Get, GetStorageVersion, StorageVersion},
+		weights::Weight,
+		BoundedVec, RuntimeDebug, Twox64Concat,
+	};
+	use pallet_support::types::{ExtendedMarket, Market, MarketState};
+	use scale_info::TypeInfo;
+	use sp_arithmetic::fixed_point::FixedI128;
+
+	// Market struct before the addition of max_open_interest
+	#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldMarket {
+		pub id: u128,
+		pub version: u16,
+		pub asset: u128,
+		pub asset_collateral: u128,
+		pub is_tradable: bool,
+		pub is_archived: bool,
+		pub ttl: u32,
+		pub tick_size: FixedI128,
+		pub tick_precision: u8,
+		pub step_size: FixedI128,
+		pub step_precision: u8,
+		pub minimum_order_size: FixedI128,
+		pub minimum_leverage: FixedI128,
+		pub maximum_leverage: FixedI128,
+		pub currently_allowed_leverage: FixedI128,
+		pub maintenance_margin_fraction: FixedI128,
+		pub initial_margin_fraction: FixedI128,
+		pub incremental_initial_margin_fraction: FixedI128,
+		pub incremental_position_size: FixedI128,
+		pub baseline_position_size: FixedI128,
+		pub maximum_position_size: FixedI128,
+	}
+
+	#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldExtendedMarket {
+		pub market: OldMarket,
+		pub metadata_url: BoundedVec<u8, ConstU32<256>>,
+	}
+
+	// Market struct before is_tradable and is_archived were replaced by the market state
+	#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct MarketV1 {
+		pub id: u128,
+		pub version: u16,
+		pub asset: u128,
+		pub asset_collateral: u128,
+		pub is_tradable: bool,
+		pub is_archived: bool,
+		pub ttl: u32,
+		pub tick_size: FixedI128,
+		pub tick_precision: u8,
+		pub step_size: FixedI128,
+		pub step_precision: u8,
+		pub minimum_order_size: FixedI128,
+		pub minimum_leverage: FixedI128,
+		pub maximum_leverage: FixedI128,
+		pub currently_allowed_leverage: FixedI128,
+		pub maintenance_margin_fraction: FixedI128,
+		pub initial_margin_fraction: FixedI128,
+		pub incremental_initial_margin_fraction: FixedI128,
+		pub incremental_position_size: FixedI128,
+		pub baseline_position_size: FixedI128,
+		pub maximum_position_size: FixedI128,
+		pub max_open_interest: Option<FixedI128>,
+	}
+
+	#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ExtendedMarketV1 {
+		pub market: MarketV1,
+		pub metadata_url: BoundedVec<u8, ConstU32<256>>,
+	}
+
+	// Market map as it was stored in version 1
+	pub mod v1 {
+		use super::*;
+
+		#[storage_alias]
+		pub type MarketMap<T: Config> = StorageMap<Pallet<T>, Twox64Concat, u128, ExtendedMarketV1>;
+	}
+
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+		if onchain_version < 1 {
+			let mut count: u64 = 0;
+
+			// Existing markets do not have an open interest cap
+			v1::MarketMap::<T>::translate::<OldExtendedMarket, _>(|_, old_extended_market| {
+				count += 1;
+				let old_market = old_extended_market.market;
+				Some(ExtendedMarketV1 {
+					market: MarketV1 {
+						id: old_market.id,
+						version: old_market.version,
+						asset: old_market.asset,
+						asset_collateral: old_market.asset_collateral,
+						is_tradable: old_market.is_tradable,
+						is_archived: old_market.is_archived,
+						ttl: old_market.ttl,
+						tick_size: old_market.tick_size,
+						tick_precision: old_market.tick_precision,
+						step_size: old_market.step_size,
+						step_precision: old_market.step_precision,
+						minimum_order_size: old_market.minimum_order_size,
+						minimum_leverage: old_market.minimum_leverage,
+						maximum_leverage: old_market.maximum_leverage,
+						currently_allowed_leverage: old_market.currently_allowed_leverage,
+						maintenance_margin_fraction: old_market.maintenance_margin_fraction,
+						initial_margin_fraction: old_market.initial_margin_fraction,
+						incremental_initial_margin_fraction: old_market
+							.incremental_initial_margin_fraction,
+						incremental_position_size: old_market.incremental_position_size,
+						baseline_position_size: old_market.baseline_position_size,
+						maximum_position_size: old_market.maximum_position_size,
+						max_open_interest: None,
+					},
+					metadata_url: old_extended_market.metadata_url,
+				})
+			});
+
+			// Update the storage version
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(count + 1, count + 1)
+		} else {
+			Weight::zero()
+		}
+	}
+
+	pub fn migrate_to_v2<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+		if onchain_version < 2 {
+			let mut count: u64 = 0;
+
+			MarketMap::<T>::translate::<ExtendedMarketV1, _>(|_, old_extended_market| {
+				count += 1;
+				let old_market = old_extended_market.market;
+				let state =
+					MarketState::from_legacy_flags(old_market.is_tradable, old_market.is_archived);
+				Some(ExtendedMarket {
+					market: Market {
+						id: old_market.id,
+						version: old_market.version,
+						asset: old_market.asset,
+						asset_collateral: old_market.asset_collateral,
+						state,
+						ttl: old_market.ttl,
+						tick_size: old_market.tick_size,
+						tick_precision: old_market.tick_precision,
+						step_size: old_market.step_size,
+						step_precision: old_market.step_precision,
+						minimum_order_size: old_market.minimum_order_size,
+						minimum_leverage: old_market.minimum_leverage,
+						maximum_leverage: old_market.maximum_leverage,
+						currently_allowed_leverage: old_market.currently_allowed_leverage,
+						maintenance_margin_fraction: old_market.maintenance_margin_fraction,
+						initial_margin_fraction: old_market.initial_margin_fraction,
+						incremental_initial_margin_fraction: old_market
+							.incremental_initial_margin_fraction,
+						incremental_position_size: old_market.incremental_position_size,
+						baseline_position_size: old_market.baseline_position_size,
+						maximum_position_size: old_market.maximum_position_size,
+						max_open_interest: old_market.max_open_interest,
+					},
+					metadata_url: old_extended_market.metadata_url,
+				})
+			});
+
+			// Update the storage version
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(count + 1, count + 1)
+		} else {
+			Weight::zero()
+		}
+	}
+}
@@ -62,12 +62,18 @@ impl frame_system::Config for Test {
 
 impl pallet_asset::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MarketPallet = MarketModule;
+	type TradingAccountPallet = TradingAccounts;
+	type WeightInfo = ();
 }
 
 impl markets::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type PricesPallet = Prices;
+	type TradingPallet = Trading;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_prices::Config for Test {
@@ -78,12 +84,15 @@ impl pallet_prices::Config for Test {
 	type TradingAccountPallet = TradingAccounts;
 	type TradingPallet = Trading;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_trading_fees::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type MarketPallet = MarketModule;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_trading_account::Config for Test {
@@ -112,6 +121,7 @@ impl pallet_trading::Config for Test {
 	type TradingFeesPallet = TradingFees;
 	type TimeProvider = Timestamp;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -1,11 +1,18 @@
-use crate::mock::*;
-use frame_support::assert_ok;
+use crate::{mock::*, pallet::MarketMap, Event, MAX_MARKET_CHANGES_APPLIED};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::Hooks,
+	storage::unhashed,
+	traits::{GetStorageVersion, StorageVersion},
+};
 use pallet_support::{
 	test_helpers::{
 		asset_helper::{eth, link, usdc},
 		market_helper::{eth_usdc, link_usdc},
 	},
-	types::{ExtendedMarket, MultiplePrices},
+	traits::{AssetInterface, MarketInterface},
+	types::{ExtendedMarket, MarketState, MultiplePrices, ScheduledMarketChange},
 };
 use sp_arithmetic::fixed_point::FixedI128;
 
@@ -308,7 +315,7 @@ fn test_add_market_with_invalid_current_leverage() {
 fn test_update_market() {
 	let (mut env, markets) = setup();
 	let eth_usdc_market = &markets[0];
-	let eth_usdc_market_updated = &markets[0].clone().set_is_tradable(false);
+	let eth_usdc_market_updated = &markets[0].clone().set_state(MarketState::Delisting);
 
 	env.execute_with(|| {
 		// Set the eth_usdc market
@@ -341,7 +348,7 @@ fn test_update_market() {
 			eth_usdc_market_updated.clone()
 		);
 
-		// Since is_tradable flag for ETH-USDC is set to false
+		// Since ETH-USDC is being delisted
 		// Check whether the mark price for that market is set
 		let eth_usdc_mark_price = Prices::mark_price_for_ads(eth_usdc_market_updated.market.id);
 		assert_eq!(eth_usdc_mark_price.unwrap(), FixedI128::from_inner(260000000000000000000));
@@ -396,3 +403,621 @@ fn test_remove_market_with_already_removed_market_id() {
 		));
 	});
 }
+
+#[test]
+#[should_panic(expected = "InvalidMaxOpenInterest")]
+fn test_add_market_with_zero_max_open_interest() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = markets[0].clone().set_max_open_interest(Some(FixedI128::from(0)));
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market
+		));
+	});
+}
+
+// Encodes the market with the is_tradable and is_archived flags used before the market state
+fn encode_market_v1(
+	extended_market: &ExtendedMarket,
+	is_tradable: bool,
+	is_archived: bool,
+) -> Vec<u8> {
+	// The state is encoded right after id, version, asset and asset_collateral
+	let state_offset = 16 + 2 + 16 + 16;
+	let mut encoding = extended_market.market.encode();
+	encoding.splice(state_offset..state_offset + 1, [is_tradable as u8, is_archived as u8]);
+	encoding
+}
+
+#[test]
+fn test_migration_to_v1() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		// Store the market as it was encoded before the addition of max_open_interest, whose
+		// None value is the last byte of the encoded market
+		let mut old_encoding = encode_market_v1(eth_usdc_market, true, false);
+		old_encoding.pop();
+		old_encoding.extend(eth_usdc_market.metadata_url.encode());
+		unhashed::put_raw(
+			&MarketMap::<Test>::hashed_key_for(eth_usdc_market.market.id),
+			&old_encoding,
+		);
+
+		// Trigger migration
+		MarketModule::on_runtime_upgrade();
+
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market.clone()
+		);
+		assert_eq!(MarketModule::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn test_migration_to_v2() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let link_usdc_market = &markets[1];
+
+	env.execute_with(|| {
+		StorageVersion::new(1).put::<MarketModule>();
+
+		// Store a tradable market and a market that was being delisted
+		let mut eth_usdc_encoding = encode_market_v1(eth_usdc_market, true, false);
+		eth_usdc_encoding.extend(eth_usdc_market.metadata_url.encode());
+		unhashed::put_raw(
+			&MarketMap::<Test>::hashed_key_for(eth_usdc_market.market.id),
+			&eth_usdc_encoding,
+		);
+		let mut link_usdc_encoding = encode_market_v1(link_usdc_market, false, false);
+		link_usdc_encoding.extend(link_usdc_market.metadata_url.encode());
+		unhashed::put_raw(
+			&MarketMap::<Test>::hashed_key_for(link_usdc_market.market.id),
+			&link_usdc_encoding,
+		);
+
+		// Trigger migration
+		MarketModule::on_runtime_upgrade();
+
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market.clone()
+		);
+		assert_eq!(
+			MarketModule::markets(link_usdc_market.market.id).unwrap(),
+			link_usdc_market.clone().set_state(MarketState::Delisting)
+		);
+		assert_eq!(MarketModule::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn test_set_market_state() {
+	let (mut env, markets) = setup();
+	let link_usdc_market = &markets[1];
+
+	env.execute_with(|| {
+		// link_usdc is listed in the pending state
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			link_usdc_market.clone()
+		));
+
+		// Open the market for trading and halt it
+		assert_ok!(MarketModule::set_market_state(
+			RuntimeOrigin::root(),
+			link_usdc_market.market.id,
+			MarketState::Active
+		));
+		assert_ok!(MarketModule::set_market_state(
+			RuntimeOrigin::root(),
+			link_usdc_market.market.id,
+			MarketState::Halted
+		));
+
+		assert_eq!(
+			MarketModule::markets(link_usdc_market.market.id).unwrap().market.state,
+			MarketState::Halted
+		);
+		System::assert_last_event(
+			Event::MarketStateUpdated {
+				market_id: link_usdc_market.market.id,
+				previous_state: MarketState::Active,
+				state: MarketState::Halted,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "InvalidMarketStateTransition")]
+fn test_set_market_state_with_invalid_transition() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		// An active market has to be delisted before it is settled
+		assert_ok!(MarketModule::set_market_state(
+			RuntimeOrigin::root(),
+			eth_usdc_market.market.id,
+			MarketState::Settled
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "BadOrigin")]
+fn test_set_market_state_with_non_root_origin() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		assert_ok!(MarketModule::set_market_state(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.market.id,
+			MarketState::Halted
+		));
+	});
+}
+
+#[test]
+fn test_update_market_with_notice_period() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let eth_usdc_market_updated = markets[0]
+		.clone()
+		.set_currently_allowed_leverage(5.into())
+		.set_state(MarketState::ReduceOnly);
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+
+		// Update the leverage and the state of the market
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market_updated.clone()
+		));
+
+		// State is updated right away while the leverage waits for the notice period
+		let market = MarketModule::markets(eth_usdc_market.market.id).unwrap().market;
+		assert_eq!(market.state, MarketState::ReduceOnly);
+		assert_eq!(
+			market.currently_allowed_leverage,
+			eth_usdc_market.market.currently_allowed_leverage
+		);
+		assert_eq!(
+			MarketModule::get_pending_market_changes(),
+			vec![ScheduledMarketChange {
+				market: eth_usdc_market_updated.market.clone(),
+				effective_timestamp: 1699940367 + 3600,
+			}]
+		);
+		System::assert_last_event(
+			Event::MarketChangeScheduled {
+				market: eth_usdc_market_updated.market.clone(),
+				effective_timestamp: 1699940367 + 3600,
+			}
+			.into(),
+		);
+
+		// Change is not applied before the effective timestamp
+		Timestamp::set_timestamp(1699940367000 + 3599000);
+		MarketModule::on_initialize(2);
+		let market = MarketModule::markets(eth_usdc_market.market.id).unwrap().market;
+		assert_eq!(
+			market.currently_allowed_leverage,
+			eth_usdc_market.market.currently_allowed_leverage
+		);
+
+		// Change is applied once the notice period has passed
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		MarketModule::on_initialize(3);
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market_updated
+		);
+		assert_eq!(MarketModule::get_pending_market_changes(), vec![]);
+		System::assert_last_event(
+			Event::MarketChangeApplied { market_id: eth_usdc_market.market.id }.into(),
+		);
+	});
+}
+
+#[test]
+fn test_update_market_without_notice_period() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let eth_usdc_market_updated = markets[0].clone().set_currently_allowed_leverage(5.into());
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		// Without a notice period the change is applied right away
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market_updated.clone()
+		));
+
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market_updated
+		);
+		assert_eq!(MarketModule::get_pending_market_changes(), vec![]);
+	});
+}
+
+#[test]
+fn test_cancel_market_change() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone().set_currently_allowed_leverage(5.into())
+		));
+
+		assert_ok!(MarketModule::cancel_market_change(
+			RuntimeOrigin::root(),
+			eth_usdc_market.market.id
+		));
+		System::assert_last_event(
+			Event::MarketChangeCancelled { market_id: eth_usdc_market.market.id }.into(),
+		);
+
+		// Cancelled change is never applied
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		MarketModule::on_initialize(2);
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market.clone()
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "MarketChangeNotFound")]
+fn test_cancel_market_change_without_scheduled_change() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		assert_ok!(MarketModule::cancel_market_change(
+			RuntimeOrigin::root(),
+			eth_usdc_market.market.id
+		));
+	});
+}
+
+#[test]
+fn test_scheduled_market_changes_applied_in_timestamp_order() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let link_usdc_market = &markets[1];
+	let eth_usdc_market_updated = markets[0].clone().set_currently_allowed_leverage(5.into());
+	let link_usdc_market_updated = markets[1].clone().set_currently_allowed_leverage(5.into());
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc_market.clone(), link_usdc_market.clone()]
+		));
+
+		// Change of ETH-USDC is scheduled first but takes effect after the one of LINK-USDC
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 7200));
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market_updated.clone()
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			link_usdc_market_updated.clone()
+		));
+		assert_eq!(
+			MarketModule::get_pending_market_changes(),
+			vec![
+				ScheduledMarketChange {
+					market: link_usdc_market_updated.market.clone(),
+					effective_timestamp: 1699940367 + 3600,
+				},
+				ScheduledMarketChange {
+					market: eth_usdc_market_updated.market.clone(),
+					effective_timestamp: 1699940367 + 7200,
+				},
+			]
+		);
+
+		// Only the change of LINK-USDC is due
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		MarketModule::on_initialize(2);
+		assert_eq!(
+			MarketModule::markets(link_usdc_market.market.id).unwrap(),
+			link_usdc_market_updated
+		);
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market.clone()
+		);
+		assert_eq!(MarketModule::market_change_timestamps(), vec![1699940367 + 7200]);
+
+		Timestamp::set_timestamp(1699940367000 + 7200000);
+		MarketModule::on_initialize(3);
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market_updated
+		);
+		assert_eq!(MarketModule::get_pending_market_changes(), vec![]);
+		assert_eq!(MarketModule::market_change_timestamps(), vec![]);
+	});
+}
+
+#[test]
+fn test_scheduled_market_changes_applied_over_blocks() {
+	let (mut env, _) = setup();
+	// One more market than the changes applied in a block
+	let markets: Vec<ExtendedMarket> = (0..=MAX_MARKET_CHANGES_APPLIED as u128)
+		.map(|index| eth_usdc().set_id(eth_usdc().market.id + index))
+		.collect();
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			markets.clone()
+		));
+
+		// Changes of all the markets take effect at the same time
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+		for market in markets.iter() {
+			assert_ok!(MarketModule::update_market(
+				RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+				market.clone().set_currently_allowed_leverage(5.into())
+			));
+		}
+
+		// Only the changes within the limit are applied in the first block
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		MarketModule::on_initialize(2);
+		let last_market = markets.last().unwrap();
+		assert_eq!(
+			MarketModule::get_pending_market_changes(),
+			vec![ScheduledMarketChange {
+				market: last_market.clone().set_currently_allowed_leverage(5.into()).market,
+				effective_timestamp: 1699940367 + 3600,
+			}]
+		);
+		assert_eq!(MarketModule::market_change_timestamps(), vec![1699940367 + 3600]);
+
+		// Rest of the changes are applied in the following block
+		MarketModule::on_initialize(3);
+		System::assert_last_event(
+			Event::MarketChangeApplied { market_id: last_market.market.id }.into(),
+		);
+		assert_eq!(
+			MarketModule::markets(last_market.market.id).unwrap(),
+			last_market.clone().set_currently_allowed_leverage(5.into())
+		);
+		assert_eq!(MarketModule::get_pending_market_changes(), vec![]);
+		assert_eq!(MarketModule::market_change_timestamps(), vec![]);
+	});
+}
+
+#[test]
+fn test_rescheduled_market_change_replaces_previous_one() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let eth_usdc_market_updated = markets[0].clone().set_currently_allowed_leverage(5.into());
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone().set_currently_allowed_leverage(4.into())
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 7200));
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market_updated.clone()
+		));
+
+		// Only the latest change is kept, under its own timestamp
+		assert_eq!(
+			MarketModule::get_pending_market_changes(),
+			vec![ScheduledMarketChange {
+				market: eth_usdc_market_updated.market.clone(),
+				effective_timestamp: 1699940367 + 7200,
+			}]
+		);
+		assert_eq!(MarketModule::market_change_timestamps(), vec![1699940367 + 7200]);
+
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		MarketModule::on_initialize(2);
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market.clone()
+		);
+	});
+}
+
+#[test]
+fn test_scheduled_market_change_keeps_mark_price_of_delisted_market() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let eth_usdc_market_updated = markets[0]
+		.clone()
+		.set_currently_allowed_leverage(5.into())
+		.set_state(MarketState::Delisting);
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![MultiplePrices {
+				market_id: eth_usdc_market.market.id,
+				index_price: FixedI128::from_inner(250000000000000000000),
+				mark_price: FixedI128::from_inner(260000000000000000000),
+			}],
+			1699940367000
+		));
+		assert_ok!(MarketModule::set_market_change_notice_period(RuntimeOrigin::root(), 3600));
+
+		// Market is delisted right away while the leverage waits for the notice period
+		assert_ok!(MarketModule::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market_updated.clone()
+		));
+		assert_eq!(
+			Prices::mark_price_for_ads(eth_usdc_market.market.id).unwrap(),
+			FixedI128::from_inner(260000000000000000000)
+		);
+
+		// Mark price moves before the change is applied
+		Timestamp::set_timestamp(1699940367000 + 3600000);
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![MultiplePrices {
+				market_id: eth_usdc_market.market.id,
+				index_price: FixedI128::from_inner(270000000000000000000),
+				mark_price: FixedI128::from_inner(280000000000000000000),
+			}],
+			1699940367000 + 3600000
+		));
+		MarketModule::on_initialize(2);
+
+		// Positions are still closed at the price set when the market was delisted
+		assert_eq!(
+			MarketModule::markets(eth_usdc_market.market.id).unwrap(),
+			eth_usdc_market_updated
+		);
+		assert_eq!(
+			Prices::mark_price_for_ads(eth_usdc_market.market.id).unwrap(),
+			FixedI128::from_inner(260000000000000000000)
+		);
+	});
+}
+
+#[test]
+fn test_get_markets_by_state() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+	let link_usdc_market = &markets[1];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc_market.clone(), link_usdc_market.clone()]
+		));
+
+		assert_eq!(
+			MarketModule::get_markets_by_state(&[MarketState::Active]),
+			vec![eth_usdc_market.clone()]
+		);
+		assert_eq!(
+			MarketModule::get_markets_by_state(&[MarketState::Pending]),
+			vec![link_usdc_market.clone()]
+		);
+		assert_eq!(MarketModule::get_markets_by_state(&[MarketState::Halted]), vec![]);
+	});
+}
+
+#[test]
+fn test_archive_market() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		assert_ok!(MarketModule::archive_market_internal(eth_usdc_market.market.id));
+
+		// The market is no longer available but stays queryable
+		let mut archived_market = eth_usdc_market.clone();
+		archived_market.market.state = MarketState::Archived;
+		assert_eq!(MarketModule::markets_count(), 0);
+		assert_eq!(MarketModule::markets(eth_usdc_market.market.id), None);
+		assert_eq!(
+			MarketModule::archived_markets(eth_usdc_market.market.id).unwrap(),
+			archived_market
+		);
+		System::assert_last_event(Event::MarketArchived { market: archived_market }.into());
+	});
+}
+
+#[test]
+fn test_remove_asset_used_by_market() {
+	let (mut env, markets) = setup();
+	let eth_usdc_market = &markets[0];
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::add_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			eth_usdc_market.clone()
+		));
+
+		// Both the asset and the collateral of the market are kept
+		assert_noop!(
+			Assets::remove_asset(
+				RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+				usdc().asset.id
+			),
+			pallet_asset::Error::<Test>::AssetUsedByMarket
+		);
+		assert_noop!(
+			Assets::archive_asset_internal(eth().asset.id),
+			pallet_asset::Error::<Test>::AssetUsedByMarket
+		);
+
+		// Archived markets keep their assets
+		assert_ok!(MarketModule::archive_market_internal(eth_usdc_market.market.id));
+		assert_noop!(
+			Assets::archive_asset_internal(eth().asset.id),
+			pallet_asset::Error::<Test>::AssetUsedByMarket
+		);
+
+		// Assets of no market can be removed
+		assert_ok!(Assets::remove_asset_internal(link().asset.id));
+	});
+}
@@ -0,0 +1,165 @@
+//! Weights for pallet_market
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_market.
+pub trait WeightInfo {
+	fn replace_all_markets(m: u32, ) -> Weight;
+	fn add_market() -> Weight;
+	fn update_market() -> Weight;
+	fn remove_market() -> Weight;
+	fn set_market_state() -> Weight;
+	fn set_market_change_notice_period() -> Weight;
+	fn cancel_market_change() -> Weight;
+}
+
+/// Weights for pallet_market using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Markets MarketMap (r:101 w:200)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Markets MarketsCount (r:0 w:1)
+	/// The range of component `m` is `[1, 100]`.
+	fn replace_all_markets(m: u32, ) -> Weight {
+		Weight::from_parts(98_000_000, 0)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(100_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(101_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Markets MarketsCount (r:1 w:1)
+	fn add_market() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Markets MarketChangeNoticePeriod (r:1 w:0)
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:2 w:2)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Prices CurrentPricesMap (r:1 w:0)
+	/// Storage: Prices MarkPriceForADS (r:0 w:1)
+	fn update_market() -> Weight {
+		Weight::from_parts(29_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Markets MarketsCount (r:1 w:1)
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:1 w:1)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	/// Storage: Trading OpenInterestMap (r:1 w:0)
+	/// Storage: Trading MarketToAccountMap (r:2 w:0)
+	fn remove_market() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Markets MarketMap (r:2 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Prices CurrentPricesMap (r:1 w:0)
+	/// Storage: Prices MarkPriceForADS (r:0 w:1)
+	fn set_market_state() -> Weight {
+		Weight::from_parts(31_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Markets MarketChangeNoticePeriod (r:0 w:1)
+	fn set_market_change_notice_period() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:1 w:1)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	fn cancel_market_change() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Markets MarketMap (r:101 w:200)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Markets MarketsCount (r:0 w:1)
+	/// The range of component `m` is `[1, 100]`.
+	fn replace_all_markets(m: u32, ) -> Weight {
+		Weight::from_parts(98_000_000, 0)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(100_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(101_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Markets MarketsCount (r:1 w:1)
+	fn add_market() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Markets MarketChangeNoticePeriod (r:1 w:0)
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:2 w:2)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Prices CurrentPricesMap (r:1 w:0)
+	/// Storage: Prices MarkPriceForADS (r:0 w:1)
+	fn update_market() -> Weight {
+		Weight::from_parts(29_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:1)
+	/// Storage: Markets MarketsCount (r:1 w:1)
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:1 w:1)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	/// Storage: Trading OpenInterestMap (r:1 w:0)
+	/// Storage: Trading MarketToAccountMap (r:2 w:0)
+	fn remove_market() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Markets MarketMap (r:2 w:1)
+	/// Storage: Assets AssetMap (r:2 w:0)
+	/// Storage: Prices CurrentPricesMap (r:1 w:0)
+	/// Storage: Prices MarkPriceForADS (r:0 w:1)
+	fn set_market_state() -> Weight {
+		Weight::from_parts(31_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Markets MarketChangeNoticePeriod (r:0 w:1)
+	fn set_market_change_notice_period() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets ScheduledMarketChangeMap (r:1 w:1)
+	/// Storage: Markets MarketChangesByTimestamp (r:1 w:1)
+	/// Storage: Markets MarketChangeTimestamps (r:1 w:1)
+	fn cancel_market_change() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
@@ -1,7 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::dispatch::Vec;
-use pallet_support::types::ABRDetails;
+use pallet_support::types::{
+	ABRDetails, Candle, CandleInterval, CurrentPriceInfo, HistoricalPricesPage,
+};
 use primitive_types::U256;
 use sp_arithmetic::fixed_point::FixedI128;
 
@@ -17,5 +19,10 @@ sp_api::decl_runtime_apis! {
 		fn get_previous_abr_values(market_id: U256, start_timestamp: u64, end_timestamp: u64) -> Vec<ABRDetails>;
 		fn get_intermediary_abr_value(market_id: U256) -> FixedI128;
 		fn get_remaining_prices_cleanup_calls() -> u64;
+		fn get_current_price(market_id: U256) -> Option<CurrentPriceInfo>;
+		fn get_all_current_prices() -> Vec<CurrentPriceInfo>;
+		fn get_historical_prices(market_id: U256, start_timestamp: u64, end_timestamp: u64, page_size: u32) -> HistoricalPricesPage;
+		fn get_candles(market_id: U256, interval: CandleInterval, start_timestamp: u64, end_timestamp: u64) -> Vec<Candle>;
+		fn get_twap(market_id: U256, start_timestamp: u64, end_timestamp: u64) -> Option<FixedI128>;
 	}
 }
@@ -5,7 +5,9 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorObject},
 };
 pub use pallet_prices_runtime_api::PricesApi as PricesRuntimeApi;
-use pallet_support::types::ABRDetails;
+use pallet_support::types::{
+	ABRDetails, Candle, CandleInterval, CurrentPriceInfo, HistoricalPricesPage,
+};
 use primitive_types::U256;
 use sp_api::ProvideRuntimeApi;
 use sp_arithmetic::fixed_point::FixedI128;
@@ -48,6 +50,45 @@ pub trait PricesApi<BlockHash> {
 
 	#[method(name = "prices_get_remaining_cleanup_calls")]
 	fn get_remaining_prices_cleanup_calls(&self, at: Option<BlockHash>) -> RpcResult<u64>;
+
+	#[method(name = "prices_get_current")]
+	fn get_current_price(
+		&self,
+		market_id: U256,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CurrentPriceInfo>>;
+
+	#[method(name = "prices_get_all_current")]
+	fn get_all_current_prices(&self, at: Option<BlockHash>) -> RpcResult<Vec<CurrentPriceInfo>>;
+
+	#[method(name = "prices_get_history")]
+	fn get_historical_prices(
+		&self,
+		market_id: U256,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		page_size: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<HistoricalPricesPage>;
+
+	#[method(name = "prices_get_candles")]
+	fn get_candles(
+		&self,
+		market_id: U256,
+		interval: CandleInterval,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Candle>>;
+
+	#[method(name = "prices_get_twap")]
+	fn get_twap(
+		&self,
+		market_id: U256,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<FixedI128>>;
 }
 
 /// A struct that implements the `PricesApi`.
@@ -144,6 +185,71 @@ where
 
 		api.get_remaining_prices_cleanup_calls(at).map_err(runtime_error_into_rpc_err)
 	}
+
+	fn get_current_price(
+		&self,
+		market_id: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<CurrentPriceInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_current_price(at, market_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_all_current_prices(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<CurrentPriceInfo>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_all_current_prices(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_historical_prices(
+		&self,
+		market_id: U256,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		page_size: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<HistoricalPricesPage> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_historical_prices(at, market_id, start_timestamp, end_timestamp, page_size)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_candles(
+		&self,
+		market_id: U256,
+		interval: CandleInterval,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Candle>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_candles(at, market_id, interval, start_timestamp, end_timestamp)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_twap(
+		&self,
+		market_id: U256,
+		start_timestamp: u64,
+		end_timestamp: u64,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<FixedI128>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_twap(at, market_id, start_timestamp, end_timestamp)
+			.map_err(runtime_error_into_rpc_err)
+	}
 }
 
 const RUNTIME_ERROR: i32 = 1;
@@ -0,0 +1,248 @@
+//! Benchmarking setup for pallet-prices
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{
+	AbrEpoch, AbrInterval, AbrMarketStatusMap, AbrState, BaseAbr, BollingerWidth, CandlesMap,
+	CandlesStartTimestampMap, CurrentPricesMap, EpochToTimestampMap, HistoricalPricesMap,
+	InitialisationTimestamp, MaxABRDefault, MaxABRPerMarket, NoOfBatchesForEpochMap,
+	PricesStartTimestamp, UsersPerBatch, ABR_INTERVAL_MIN, ABR_PRICE_INTERVAL,
+	CANDLES_CLEANUP_COUNT, CANDLES_CLEANUP_LIMIT, CLEANUP_COUNT, FOUR_WEEKS, ONE_YEAR,
+};
+#[allow(unused)]
+use crate::Pallet as Prices;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	test_helpers::{
+		asset_helper::{btc, usdc},
+		market_helper::btc_usdc,
+	},
+	traits::{AssetInterface, MarketInterface, TradingAccountInterface},
+	types::{
+		ABRState, Candle, CandleInterval, HistoricalPrice, MultiplePrices, TradingAccountMinimal,
+	},
+};
+use primitive_types::U256;
+use sp_arithmetic::fixed_point::FixedI128;
+use sp_runtime::traits::SaturatedConversion;
+
+// Initialisation timestamp of ABR in the benchmarks, in seconds
+const INITIALISATION_TIMESTAMP: u64 = 1699940000;
+
+fn set_timestamp<T: Config + pallet_timestamp::Config>(timestamp_in_seconds: u64) {
+	pallet_timestamp::Pallet::<T>::set_timestamp((timestamp_in_seconds * 1000).saturated_into());
+}
+
+// Adds `count` tradable markets with ids 1..=count
+fn setup_markets<T: Config>(count: u32) {
+	T::AssetPallet::add_asset_internal(usdc()).unwrap();
+	T::AssetPallet::add_asset_internal(btc()).unwrap();
+	for id in 1..=count {
+		T::MarketPallet::add_market_internal(btc_usdc().set_id(id as u128)).unwrap();
+	}
+}
+
+#[benchmarks(where T: pallet_timestamp::Config)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_initialisation_timestamp() {
+		#[extrinsic_call]
+		set_initialisation_timestamp(RawOrigin::Root, INITIALISATION_TIMESTAMP * 1000);
+
+		assert_eq!(InitialisationTimestamp::<T>::get(), INITIALISATION_TIMESTAMP);
+	}
+
+	#[benchmark]
+	fn set_default_max_abr() {
+		let max_abr_value = FixedI128::from_inner(1000000000000000);
+
+		#[extrinsic_call]
+		set_default_max_abr(RawOrigin::Root, max_abr_value);
+
+		assert_eq!(MaxABRDefault::<T>::get(), max_abr_value);
+	}
+
+	#[benchmark]
+	fn set_max_abr() {
+		setup_markets::<T>(1);
+		let market_id = btc_usdc().set_id(1).market.id;
+		let max_abr_value = FixedI128::from_inner(1000000000000000);
+
+		#[extrinsic_call]
+		set_max_abr(RawOrigin::Root, market_id, max_abr_value);
+
+		assert_eq!(MaxABRPerMarket::<T>::get(market_id), max_abr_value);
+	}
+
+	#[benchmark]
+	fn set_abr_interval() {
+		#[extrinsic_call]
+		set_abr_interval(RawOrigin::Root, ABR_INTERVAL_MIN);
+
+		assert_eq!(AbrInterval::<T>::get(), ABR_INTERVAL_MIN);
+	}
+
+	#[benchmark]
+	fn set_base_abr() {
+		let base_abr = FixedI128::from_inner(25000000000000);
+
+		#[extrinsic_call]
+		set_base_abr(RawOrigin::Root, base_abr);
+
+		assert_eq!(BaseAbr::<T>::get(), base_abr);
+	}
+
+	#[benchmark]
+	fn set_bollinger_width() {
+		let bollinger_width = FixedI128::from_inner(2000000000000000000);
+
+		#[extrinsic_call]
+		set_bollinger_width(RawOrigin::Root, bollinger_width);
+
+		assert_eq!(BollingerWidth::<T>::get(), bollinger_width);
+	}
+
+	#[benchmark]
+	fn set_no_of_users_per_batch() {
+		#[extrinsic_call]
+		set_no_of_users_per_batch(RawOrigin::Root, 10);
+
+		assert_eq!(UsersPerBatch::<T>::get(), 10);
+	}
+
+	// h is the ABR interval in hours
+	#[benchmark]
+	fn set_abr_value(h: Linear<1, 8>) {
+		setup_markets::<T>(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = btc_usdc().set_id(1).market.id;
+		let abr_interval = h as u64 * ABR_INTERVAL_MIN;
+
+		InitialisationTimestamp::<T>::put(INITIALISATION_TIMESTAMP);
+		AbrInterval::<T>::put(abr_interval);
+		UsersPerBatch::<T>::put(10);
+		BaseAbr::<T>::put(FixedI128::from_inner(25000000000000));
+		BollingerWidth::<T>::put(FixedI128::from_inner(2000000000000000000));
+		set_timestamp::<T>(INITIALISATION_TIMESTAMP + abr_interval);
+
+		// Prices are available for the first half of the interval, so that the call computes
+		// the ABR and also scans every second of the second half for missing prices
+		let mut timestamp = INITIALISATION_TIMESTAMP;
+		while timestamp < INITIALISATION_TIMESTAMP + abr_interval / 2 {
+			let offset = FixedI128::from((timestamp % 7) as i128);
+			HistoricalPricesMap::<T>::insert(
+				timestamp,
+				market_id,
+				HistoricalPrice {
+					index_price: FixedI128::from(100) + offset,
+					mark_price: FixedI128::from(101) + offset,
+				},
+			);
+			timestamp += ABR_PRICE_INTERVAL;
+		}
+
+		#[extrinsic_call]
+		set_abr_value(RawOrigin::Signed(caller), market_id);
+
+		assert!(AbrMarketStatusMap::<T>::get(1, market_id));
+	}
+
+	// u is the number of users per batch
+	#[benchmark]
+	fn make_abr_payments(u: Linear<1, 100>) {
+		setup_markets::<T>(1);
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Positions are owned by the trading pallet, which this pallet cannot depend on; the
+		// users of the batch only hold collateral
+		for index in 0..u {
+			T::TradingAccountPallet::deposit_internal(
+				TradingAccountMinimal {
+					account_address: U256::from(1000_u128 + index as u128),
+					index: 0,
+					pub_key: U256::from(2000_u128 + index as u128),
+				},
+				usdc().asset.id,
+				1000.into(),
+			);
+		}
+
+		UsersPerBatch::<T>::put(u as u64);
+		AbrState::<T>::put(ABRState::State2);
+		AbrEpoch::<T>::put(1);
+		EpochToTimestampMap::<T>::insert(1, INITIALISATION_TIMESTAMP);
+		NoOfBatchesForEpochMap::<T>::insert(1, 1);
+
+		#[extrinsic_call]
+		make_abr_payments(RawOrigin::Signed(caller));
+
+		assert_eq!(AbrState::<T>::get(), ABRState::State0);
+	}
+
+	// m is the number of markets
+	#[benchmark]
+	fn update_prices(m: Linear<1, 100>) {
+		setup_markets::<T>(m);
+		let caller: T::AccountId = whitelisted_caller();
+		set_timestamp::<T>(INITIALISATION_TIMESTAMP);
+		let prices: Vec<MultiplePrices> = (1..=m)
+			.map(|id| MultiplePrices {
+				market_id: id as u128,
+				index_price: 100.into(),
+				mark_price: 101.into(),
+			})
+			.collect();
+
+		#[extrinsic_call]
+		update_prices(RawOrigin::Signed(caller), prices, INITIALISATION_TIMESTAMP * 1000);
+
+		assert_eq!(CurrentPricesMap::<T>::get(m as u128).timestamp, INITIALISATION_TIMESTAMP);
+	}
+
+	// m is the number of markets with prices at a timestamp
+	#[benchmark]
+	fn perform_prices_cleanup(m: Linear<1, 100>) {
+		setup_markets::<T>(m);
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Every timestamp that a single call can clean up has a price for every market
+		let price = HistoricalPrice { index_price: 100.into(), mark_price: 101.into() };
+		PricesStartTimestamp::<T>::put(1);
+		for timestamp in 1..=CLEANUP_COUNT {
+			for id in 1..=m {
+				HistoricalPricesMap::<T>::insert(timestamp, id as u128, price);
+			}
+		}
+
+		// Every candle timestamp that a single call can clean up is full
+		for interval in [CandleInterval::OneMinute, CandleInterval::OneHour] {
+			CandlesStartTimestampMap::<T>::insert(interval, 0);
+			for index in 0..CANDLES_CLEANUP_COUNT {
+				let candle_timestamp = index * interval.duration();
+				for id in 0..CANDLES_CLEANUP_LIMIT {
+					CandlesMap::<T>::insert(
+						(interval, candle_timestamp),
+						id as u128,
+						Candle::new(candle_timestamp, candle_timestamp, &price),
+					);
+				}
+			}
+		}
+		set_timestamp::<T>(ONE_YEAR + (CANDLES_CLEANUP_COUNT + 1) * 3600);
+
+		#[extrinsic_call]
+		perform_prices_cleanup(RawOrigin::Signed(caller), m);
+
+		assert_eq!(PricesStartTimestamp::<T>::get(), Some(CLEANUP_COUNT + 1));
+		assert_eq!(
+			CandlesStartTimestampMap::<T>::get(CandleInterval::OneHour),
+			Some(CANDLES_CLEANUP_COUNT * 3600)
+		);
+	}
+
+	impl_benchmark_test_suite!(Prices, crate::mock::new_test_ext(), crate::mock::Test);
+}
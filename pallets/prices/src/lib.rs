@@ -8,6 +8,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use core::{cmp::max, option::Option};
@@ -28,8 +33,9 @@ pub mod pallet {
 			TradingAccountInterface, TradingInterface,
 		},
 		types::{
-			ABRDetails, ABRState, BalanceChangeReason, CurrentPrice, Direction, FundModifyType,
-			HistoricalPrice, LastOraclePrice, MultiplePrices, PositionExtended,
+			ABRDetails, ABRState, BalanceChangeReason, Candle, CandleInterval, CurrentPrice,
+			CurrentPriceInfo, Direction, FundModifyType, HistoricalPrice, HistoricalPriceInfo,
+			HistoricalPricesPage, LastOraclePrice, MarketState, MultiplePrices, PositionExtended,
 		},
 	};
 	use primitive_types::U256;
@@ -37,6 +43,8 @@ pub mod pallet {
 	use sp_core::crypto::KeyTypeId;
 	use sp_runtime::traits::SaturatedConversion;
 
+	use super::WeightInfo;
+
 	// ////////////
 	// Constants //
 	// ////////////
@@ -51,15 +59,31 @@ pub mod pallet {
 	const BASE_ABR_MAX: FixedI128 = FixedI128::from_inner(100000000000000); // 0.0001
 
 	// Minimum ABR interval
-	const ABR_INTERVAL_MIN: u64 = 3600;
+	pub(super) const ABR_INTERVAL_MIN: u64 = 3600;
 	// Price interval with which historical prices should be stored for ABR
-	const ABR_PRICE_INTERVAL: u64 = 60;
+	pub(super) const ABR_PRICE_INTERVAL: u64 = 60;
 	// To convert milliseconds to seconds
 	const MILLIS_PER_SECOND: u64 = 1000;
 	// Duration for which price data is available
-	static FOUR_WEEKS: u64 = 2419200;
+	pub(super) static FOUR_WEEKS: u64 = 2419200;
 	// Number of deletions for cleanup
-	static CLEANUP_COUNT: u64 = 120;
+	pub(super) static CLEANUP_COUNT: u64 = 120;
+	// Seconds into the future for which prices are accepted
+	const MAX_FUTURE_PRICE_INTERVAL: u64 = 10;
+	// Maximum number of historical prices returned in a single page
+	pub(super) const MAX_PRICES_PAGE_SIZE: u32 = 1000;
+	// Maximum number of seconds scanned for historical prices in a single page
+	pub(super) const MAX_PRICES_SCAN_INTERVAL: u64 = 3600;
+	// Duration for which one minute candles are available
+	pub(super) static EIGHT_WEEKS: u64 = 4838400;
+	// Duration for which one hour candles are available, one day candles are never cleaned up
+	pub(super) static ONE_YEAR: u64 = 31536000;
+	// Number of candle deletions per interval for cleanup
+	pub(super) static CANDLES_CLEANUP_COUNT: u64 = 60;
+	// Maximum number of candles of a candle timestamp deleted in a single cleanup
+	pub(super) static CANDLES_CLEANUP_LIMIT: u32 = 100;
+	// Maximum number of candles read to compute a TWAP
+	pub(super) const MAX_TWAP_CANDLES: u64 = 1440;
 	// Block interval at which offchain workers will be executed
 	const BLOCK_INTERVAL: u32 = 120;
 
@@ -107,6 +131,7 @@ pub mod pallet {
 		type TradingPallet: TradingInterface;
 		type TimeProvider: UnixTime;
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -134,6 +159,25 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn candle)]
+	// k1 - (interval, candle start timestamp), k2 - market_id, v - Candle
+	pub(super) type CandlesMap<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(CandleInterval, u64),
+		Blake2_128Concat,
+		u128,
+		Candle,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candles_start_timestamp)]
+	// k1 - interval, v - start timestamp of the oldest candle stored
+	pub(super) type CandlesStartTimestampMap<T: Config> =
+		StorageMap<_, Twox64Concat, CandleInterval, u64, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn prices_start_timestamp)]
 	// The beginning timestamp for which prices data is stored
@@ -340,7 +384,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// External function to be called for setting Initialisation timestamp
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_initialisation_timestamp())]
 		pub fn set_initialisation_timestamp(
 			origin: OriginFor<T>,
 			timestamp: u64,
@@ -364,7 +408,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting the default max abr
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_default_max_abr())]
 		pub fn set_default_max_abr(
 			origin: OriginFor<T>,
 			max_abr_value: FixedI128,
@@ -378,7 +422,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting max abr per market
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_max_abr())]
 		pub fn set_max_abr(
 			origin: OriginFor<T>,
 			market_id: u128,
@@ -391,7 +435,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting ABR interval
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_abr_interval())]
 		pub fn set_abr_interval(origin: OriginFor<T>, new_abr_interval: u64) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_root(origin)?;
@@ -407,7 +451,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting base ABR
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_base_abr())]
 		pub fn set_base_abr(origin: OriginFor<T>, new_base_abr: FixedI128) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_root(origin)?;
@@ -426,7 +470,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting bollinger width
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_bollinger_width())]
 		pub fn set_bollinger_width(
 			origin: OriginFor<T>,
 			new_bollinger_width: FixedI128,
@@ -455,7 +499,7 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting no.of users per batch
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_no_of_users_per_batch())]
 		pub fn set_no_of_users_per_batch(
 			origin: OriginFor<T>,
 			new_no_of_users_per_batch: u64,
@@ -476,7 +520,11 @@ pub mod pallet {
 		}
 
 		/// External function to be called for setting ABR value
-		#[pallet::weight(0)]
+		// Prices are scanned for the whole ABR interval, which is charged in started hours
+		#[pallet::weight(T::WeightInfo::set_abr_value(
+			((AbrInterval::<T>::get() + ABR_INTERVAL_MIN - 1) / ABR_INTERVAL_MIN)
+				.saturated_into::<u32>()
+		))]
 		pub fn set_abr_value(origin: OriginFor<T>, market_id: u128) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_signed(origin)?;
@@ -499,7 +547,7 @@ pub mod pallet {
 			ensure!(market.is_some(), Error::<T>::MarketNotFound);
 			let market = market.unwrap();
 
-			ensure!(market.is_tradable == true, Error::<T>::MarketNotTradable);
+			ensure!(market.state.is_abr_applicable(), Error::<T>::MarketNotTradable);
 
 			// Check if the market's abr is already set
 			let market_status = AbrMarketStatusMap::<T>::get(current_epoch, market_id);
@@ -554,7 +602,10 @@ pub mod pallet {
 		}
 
 		/// External function to be called for making ABR payments
-		#[pallet::weight(0)]
+		// A single call pays ABR for one batch of users
+		#[pallet::weight(T::WeightInfo::make_abr_payments(
+			UsersPerBatch::<T>::get().saturated_into::<u32>()
+		))]
 		pub fn make_abr_payments(origin: OriginFor<T>) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_signed(origin)?;
@@ -575,7 +626,7 @@ pub mod pallet {
 		}
 
 		/// update index and mark prices for several markets
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_prices(prices.len() as u32))]
 		pub fn update_prices(
 			origin: OriginFor<T>,
 			prices: Vec<MultiplePrices>,
@@ -590,7 +641,10 @@ pub mod pallet {
 			// Get the current timestamp and last timestamp for which prices were updated
 			let timestamp = Self::convert_to_seconds(timestamp);
 
-			ensure!(timestamp <= current_timestamp + 10, Error::<T>::FutureTimestampPriceUpdate);
+			ensure!(
+				timestamp <= current_timestamp + MAX_FUTURE_PRICE_INTERVAL,
+				Error::<T>::FutureTimestampPriceUpdate
+			);
 			// Modify start timestamp
 			let start_timestamp = PricesStartTimestamp::<T>::get();
 			if (start_timestamp.is_some() && timestamp < start_timestamp.unwrap()) ||
@@ -632,6 +686,9 @@ pub mod pallet {
 					curr_market.market_id,
 					historical_price,
 				);
+
+				// Aggregate the price into the candles of the market
+				Self::update_candles(curr_market.market_id, timestamp, &historical_price);
 			}
 
 			// Emit index/mark prices updated event
@@ -640,8 +697,10 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
-		pub fn perform_prices_cleanup(origin: OriginFor<T>) -> DispatchResult {
+		// Each cleaned up timestamp removes the prices of at most markets_count markets, which
+		// the caller passes as an upper bound on the no.of markets with prices
+		#[pallet::weight(T::WeightInfo::perform_prices_cleanup(*markets_count))]
+		pub fn perform_prices_cleanup(origin: OriginFor<T>, markets_count: u32) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_signed(origin)?;
 
@@ -651,16 +710,21 @@ pub mod pallet {
 			let timestamp_limit = current_timestamp - FOUR_WEEKS;
 			let mut cleanup_count = CLEANUP_COUNT;
 
+			// Candles are retained longer than the prices, so they have their own limits
+			Self::perform_candles_cleanup(current_timestamp);
+
 			for timestamp in start_timestamp..timestamp_limit {
 				if cleanup_count == 0 {
 					PricesStartTimestamp::<T>::put(timestamp);
 					return Ok(())
 				}
-				// we are passing None as 3rd argument as no.of prices stored for a particular
-				// timestamp is limited and it doesn't need another call to remove
-				let markets = T::MarketPallet::get_all_markets_by_state(true, false);
-				let _ =
-					HistoricalPricesMap::<T>::clear_prefix(timestamp, markets.len() as u32, None);
+				// If the timestamp has prices of more markets than markets_count, cleanup
+				// resumes from the same timestamp in the next call
+				let result = HistoricalPricesMap::<T>::clear_prefix(timestamp, markets_count, None);
+				if result.maybe_cursor.is_some() {
+					PricesStartTimestamp::<T>::put(timestamp);
+					return Ok(())
+				}
 				cleanup_count -= 1;
 			}
 			if start_timestamp < timestamp_limit {
@@ -720,6 +784,159 @@ pub mod pallet {
 			}
 		}
 
+		fn get_candles_retention(interval: CandleInterval) -> Option<u64> {
+			match interval {
+				CandleInterval::OneMinute => Some(EIGHT_WEEKS),
+				CandleInterval::OneHour => Some(ONE_YEAR),
+				CandleInterval::OneDay => None,
+			}
+		}
+
+		fn update_candles(market_id: u128, timestamp: u64, price: &HistoricalPrice) {
+			for interval in CandleInterval::ALL {
+				let candle_timestamp = interval.candle_start(timestamp);
+				CandlesMap::<T>::mutate((interval, candle_timestamp), market_id, |candle| {
+					match candle {
+						Some(candle) => candle.add_price(timestamp, price),
+						None => *candle = Some(Candle::new(candle_timestamp, timestamp, price)),
+					}
+				});
+
+				// Modify start timestamp of the candles
+				let start_timestamp = CandlesStartTimestampMap::<T>::get(interval);
+				if start_timestamp.map_or(true, |start| candle_timestamp < start) {
+					CandlesStartTimestampMap::<T>::insert(interval, candle_timestamp);
+				}
+			}
+		}
+
+		fn perform_candles_cleanup(current_timestamp: u64) {
+			for interval in CandleInterval::ALL {
+				let retention = match Self::get_candles_retention(interval) {
+					Some(retention) => retention,
+					None => continue,
+				};
+				let start_timestamp = match CandlesStartTimestampMap::<T>::get(interval) {
+					Some(start_timestamp) => start_timestamp,
+					None => continue,
+				};
+				let timestamp_limit =
+					interval.candle_start(current_timestamp.saturating_sub(retention));
+
+				let mut candle_timestamp = start_timestamp;
+				let mut cleanup_count = CANDLES_CLEANUP_COUNT;
+				while candle_timestamp < timestamp_limit && cleanup_count != 0 {
+					// If the timestamp has candles of more markets than the limit, cleanup
+					// resumes from the same timestamp in the next call
+					let result = CandlesMap::<T>::clear_prefix(
+						(interval, candle_timestamp),
+						CANDLES_CLEANUP_LIMIT,
+						None,
+					);
+					if result.maybe_cursor.is_some() {
+						break
+					}
+					candle_timestamp += interval.duration();
+					cleanup_count -= 1;
+				}
+				if candle_timestamp != start_timestamp {
+					CandlesStartTimestampMap::<T>::insert(interval, candle_timestamp);
+				}
+			}
+		}
+
+		pub fn get_candles(
+			market_id: u128,
+			interval: CandleInterval,
+			start_timestamp: u64,
+			end_timestamp: u64,
+		) -> Vec<Candle> {
+			let mut candles = Vec::<Candle>::new();
+
+			// Candles before the start timestamp have been cleaned up
+			let mut candle_timestamp = match CandlesStartTimestampMap::<T>::get(interval) {
+				Some(candles_start_timestamp) =>
+					max(interval.candle_start(start_timestamp), candles_start_timestamp),
+				None => return candles,
+			};
+			// Candles after the end timestamp can't have been stored yet
+			let current_timestamp: u64 = T::TimeProvider::now().as_secs();
+			let end_timestamp = end_timestamp.min(current_timestamp + MAX_FUTURE_PRICE_INTERVAL);
+
+			while candle_timestamp <= end_timestamp && candles.len() < MAX_PRICES_PAGE_SIZE as usize
+			{
+				if let Some(candle) = CandlesMap::<T>::get((interval, candle_timestamp), market_id)
+				{
+					candles.push(candle);
+				}
+				candle_timestamp += interval.duration();
+			}
+			candles
+		}
+
+		pub fn get_current_price_info(market_id: u128) -> Option<CurrentPriceInfo> {
+			let market = T::MarketPallet::get_market(market_id)?;
+			let price = CurrentPricesMap::<T>::get(market_id);
+
+			// Same staleness check as the one applied by get_price
+			let current_timestamp: u64 = T::TimeProvider::now().as_secs();
+			let ttl: u64 = market.ttl.into();
+			let is_stale = current_timestamp.saturating_sub(price.timestamp) > ttl;
+
+			Some(CurrentPriceInfo {
+				market_id: market_id.into(),
+				timestamp: price.timestamp,
+				index_price: price.index_price,
+				mark_price: price.mark_price,
+				is_stale,
+			})
+		}
+
+		pub fn get_all_current_prices() -> Vec<CurrentPriceInfo> {
+			T::MarketPallet::get_all_markets()
+				.into_iter()
+				.filter_map(Self::get_current_price_info)
+				.collect()
+		}
+
+		pub fn get_historical_prices(
+			market_id: u128,
+			start_timestamp: u64,
+			end_timestamp: u64,
+			page_size: u32,
+		) -> HistoricalPricesPage {
+			let mut page = HistoricalPricesPage::default();
+
+			// Prices before the start timestamp have been cleaned up
+			let mut timestamp = match PricesStartTimestamp::<T>::get() {
+				Some(prices_start_timestamp) => max(start_timestamp, prices_start_timestamp),
+				None => return page,
+			};
+			// Prices after the end timestamp can't have been stored yet
+			let current_timestamp: u64 = T::TimeProvider::now().as_secs();
+			let end_timestamp = end_timestamp.min(current_timestamp + MAX_FUTURE_PRICE_INTERVAL);
+			let page_size = page_size.clamp(1, MAX_PRICES_PAGE_SIZE) as usize;
+			// Sparse ranges are split into pages of a bounded number of seconds
+			let scan_end_timestamp =
+				end_timestamp.min(timestamp.saturating_add(MAX_PRICES_SCAN_INTERVAL - 1));
+
+			while timestamp <= scan_end_timestamp && page.prices.len() < page_size {
+				if let Ok(price) = HistoricalPricesMap::<T>::try_get(timestamp, market_id) {
+					page.prices.push(HistoricalPriceInfo {
+						timestamp,
+						index_price: price.index_price,
+						mark_price: price.mark_price,
+					});
+				}
+				timestamp += 1;
+			}
+
+			if timestamp <= end_timestamp {
+				page.next_timestamp = Some(timestamp);
+			}
+			page
+		}
+
 		fn get_last_abr_timestamp() -> u64 {
 			// Get current state and epoch
 			let current_state = AbrState::<T>::get();
@@ -752,7 +969,7 @@ pub mod pallet {
 
 		fn check_abr_markets_status(epoch: u64) {
 			// get all the markets available in the system
-			let markets = T::MarketPallet::get_all_markets_by_state(true, false);
+			let markets = T::MarketPallet::get_all_markets_by_state(&MarketState::ABR_STATES);
 
 			// Check the state of each market
 			for market_id in markets {
@@ -1263,7 +1480,7 @@ pub mod pallet {
 			ensure!(market.is_some(), Error::<T>::MarketNotFound);
 			let market = market.unwrap();
 
-			ensure!(market.is_tradable == true, Error::<T>::MarketNotTradable);
+			ensure!(market.state.is_abr_applicable(), Error::<T>::MarketNotTradable);
 
 			// Set the given abr value
 			MaxABRPerMarket::<T>::insert(market_id, max_abr_value);
@@ -1291,6 +1508,50 @@ pub mod pallet {
 			Self::get_price(market_id, price.timestamp, price.index_price)
 		}
 
+		fn get_twap(
+			market_id: u128,
+			start_timestamp: u64,
+			end_timestamp: u64,
+		) -> Option<FixedI128> {
+			if start_timestamp > end_timestamp {
+				return None
+			}
+
+			// Use the finest candles that are still retained for the whole range
+			// and that cover it within the read limit
+			let current_timestamp: u64 = T::TimeProvider::now().as_secs();
+			let interval = CandleInterval::ALL.into_iter().find(|interval| {
+				let is_retained = Self::get_candles_retention(*interval)
+					.map_or(true, |retention| {
+						start_timestamp >= current_timestamp.saturating_sub(retention)
+					});
+				is_retained &&
+					(end_timestamp - start_timestamp) / interval.duration() < MAX_TWAP_CANDLES
+			})?;
+
+			let mut candle_timestamp = interval.candle_start(start_timestamp);
+			let mut last_close: Option<FixedI128> = None;
+			let mut sum = FixedI128::zero();
+			let mut count: u64 = 0;
+			while candle_timestamp <= end_timestamp {
+				if let Some(candle) = CandlesMap::<T>::get((interval, candle_timestamp), market_id)
+				{
+					last_close = Some(candle.mark_price.close);
+				}
+				// The last price holds for the candles without an update
+				if let Some(close) = last_close {
+					sum = sum + close;
+					count += 1;
+				}
+				candle_timestamp += interval.duration();
+			}
+
+			if count == 0 {
+				return None
+			}
+			Some(sum / FixedI128::from(count as i128))
+		}
+
 		fn get_mark_price_for_ads(market_id: u128) -> Option<FixedI128> {
 			MarkPriceForADS::<T>::get(market_id)
 		}
@@ -1314,7 +1575,7 @@ pub mod pallet {
 		fn get_remaining_markets() -> Vec<u128> {
 			let current_epoch = AbrEpoch::<T>::get();
 
-			let markets = T::MarketPallet::get_all_markets_by_state(true, false);
+			let markets = T::MarketPallet::get_all_markets_by_state(&MarketState::ABR_STATES);
 			let mut remaining_markets = Vec::<u128>::new();
 
 			// According to ABR state, return remaining markets
@@ -1410,9 +1671,9 @@ pub mod pallet {
 				return FixedI128::zero()
 			}
 
-			// Check whether market is tradable
+			// Check whether ABR applies to the market
 			let market = market.unwrap();
-			if market.is_tradable == false {
+			if !market.state.is_abr_applicable() {
 				return FixedI128::zero()
 			}
 
@@ -1511,8 +1772,10 @@ pub mod pallet {
 				// Call perform prices clean up only when there are prices to clean up
 				let cleanup_calls = Self::get_remaining_prices_cleanup_calls();
 				if cleanup_calls != 0 {
-					let results =
-						signer.send_signed_transaction(|_account| Call::perform_prices_cleanup {});
+					let markets_count = T::MarketPallet::get_all_markets().len() as u32;
+					let results = signer.send_signed_transaction(|_account| {
+						Call::perform_prices_cleanup { markets_count }
+					});
 					for (acc, res) in &results {
 						match res {
 							Ok(()) => log::info!("[{:?}]: Submit transaction success.", acc.id),
@@ -63,12 +63,18 @@ impl frame_system::Config for Test {
 
 impl pallet_asset::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MarketPallet = MarketModule;
+	type TradingAccountPallet = TradingAccounts;
+	type WeightInfo = ();
 }
 
 impl pallet_market::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = AssetModule;
 	type PricesPallet = PricesModule;
+	type TradingPallet = Trading;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_risk_management::Config for Test {
@@ -82,6 +88,8 @@ impl pallet_trading_fees::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = AssetModule;
 	type MarketPallet = MarketModule;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -101,6 +109,7 @@ impl pallet_trading::Config for Test {
 	type TradingFeesPallet = TradingFees;
 	type TimeProvider = Timestamp;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_trading_account::Config for Test {
@@ -120,6 +129,7 @@ impl pallet_prices::Config for Test {
 	type TradingAccountPallet = TradingAccounts;
 	type TradingPallet = Trading;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,4 +1,4 @@
-use crate::{mock::*, Event};
+use crate::{mock::*, Event, MAX_PRICES_PAGE_SIZE, MAX_PRICES_SCAN_INTERVAL};
 use frame_support::assert_ok;
 use pallet_support::{
 	test_helpers::{
@@ -6,7 +6,11 @@ use pallet_support::{
 		asset_helper::{btc, eth, link, usdc},
 		market_helper::{btc_usdc, eth_usdc, link_usdc},
 	},
-	types::{ABRState, Direction, MultiplePrices, Order, OrderType},
+	traits::PricesInterface,
+	types::{
+		ABRState, Candle, CandleInterval, CurrentPriceInfo, Direction, HistoricalPriceInfo,
+		MultiplePrices, Order, OrderType, OHLC,
+	},
 };
 use primitive_types::U256;
 use sp_arithmetic::{fixed_point::FixedI128, traits::One};
@@ -147,6 +151,341 @@ fn test_update_prices() {
 	});
 }
 
+#[test]
+fn test_get_current_price_info() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market1 = eth_usdc();
+	let market2 = link_usdc();
+
+	env.execute_with(|| {
+		Timestamp::set_timestamp(1702359600000);
+
+		let markets = vec![eth_usdc(), link_usdc()];
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			markets
+		));
+
+		let prices = vec![
+			MultiplePrices {
+				market_id: market1.market.id,
+				index_price: 102.into(),
+				mark_price: 100.into(),
+			},
+			MultiplePrices {
+				market_id: market2.market.id,
+				index_price: 199.into(),
+				mark_price: 200.into(),
+			},
+		];
+		assert_ok!(PricesModule::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			prices,
+			1702359600000
+		));
+
+		assert_eq!(
+			PricesModule::get_current_price_info(market1.market.id),
+			Some(CurrentPriceInfo {
+				market_id: market1.market.id.into(),
+				timestamp: 1702359600,
+				index_price: 102.into(),
+				mark_price: 100.into(),
+				is_stale: false,
+			})
+		);
+		assert_eq!(PricesModule::get_current_price_info(12345), None);
+
+		// ttl of link_usdc is 360 seconds and ttl of eth_usdc is 3600 seconds
+		Timestamp::set_timestamp(1702360000000);
+		let current_prices = PricesModule::get_all_current_prices();
+		assert_eq!(current_prices.len(), 2);
+		for current_price in current_prices {
+			let is_link_usdc = current_price.market_id == U256::from(market2.market.id);
+			assert_eq!(current_price.is_stale, is_link_usdc);
+		}
+
+		// The margin engine reads stale prices as zero
+		assert_eq!(PricesModule::get_mark_price(market2.market.id), FixedI128::zero());
+		assert_eq!(PricesModule::get_mark_price(market1.market.id), 100.into());
+	});
+}
+
+#[test]
+fn test_get_historical_prices() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market1 = eth_usdc();
+
+	env.execute_with(|| {
+		let markets = vec![eth_usdc(), link_usdc()];
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			markets
+		));
+
+		// Store a price every 20 seconds
+		let start_timestamp: u64 = 1702359600;
+		for index in 0..3_u64 {
+			let timestamp = start_timestamp + 20 * index;
+			Timestamp::set_timestamp(timestamp * 1000);
+			assert_ok!(PricesModule::update_prices(
+				RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+				vec![MultiplePrices {
+					market_id: market1.market.id,
+					index_price: (100 + index as u32).into(),
+					mark_price: (200 + index as u32).into(),
+				}],
+				timestamp * 1000
+			));
+		}
+
+		// First page is full, the second page has the remaining price
+		let page =
+			PricesModule::get_historical_prices(market1.market.id, 0, start_timestamp + 40, 2);
+		assert_eq!(
+			page.prices,
+			vec![
+				HistoricalPriceInfo {
+					timestamp: start_timestamp,
+					index_price: 100.into(),
+					mark_price: 200.into(),
+				},
+				HistoricalPriceInfo {
+					timestamp: start_timestamp + 20,
+					index_price: 101.into(),
+					mark_price: 201.into(),
+				},
+			]
+		);
+		assert_eq!(page.next_timestamp, Some(start_timestamp + 21));
+
+		let page = PricesModule::get_historical_prices(
+			market1.market.id,
+			page.next_timestamp.unwrap(),
+			start_timestamp + 40,
+			2,
+		);
+		assert_eq!(
+			page.prices,
+			vec![HistoricalPriceInfo {
+				timestamp: start_timestamp + 40,
+				index_price: 102.into(),
+				mark_price: 202.into(),
+			}]
+		);
+		assert_eq!(page.next_timestamp, None);
+	});
+}
+
+#[test]
+fn test_get_historical_prices_of_sparse_range() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market1 = eth_usdc();
+
+	env.execute_with(|| {
+		let markets = vec![eth_usdc(), link_usdc()];
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			markets
+		));
+
+		// Prices are further apart than the seconds scanned for a page
+		let start_timestamp: u64 = 1702359600;
+		let last_timestamp = start_timestamp + MAX_PRICES_SCAN_INTERVAL + 5;
+		for timestamp in [start_timestamp, last_timestamp] {
+			Timestamp::set_timestamp(timestamp * 1000);
+			update_mark_price(market1.market.id, timestamp, 100.into());
+		}
+
+		// Scanning stops before the page is full
+		let page = PricesModule::get_historical_prices(
+			market1.market.id,
+			start_timestamp,
+			last_timestamp,
+			MAX_PRICES_PAGE_SIZE,
+		);
+		assert_eq!(page.prices.len(), 1);
+		assert_eq!(page.prices[0].timestamp, start_timestamp);
+		assert_eq!(page.next_timestamp, Some(start_timestamp + MAX_PRICES_SCAN_INTERVAL));
+
+		// An empty page still moves the range forward
+		let page = PricesModule::get_historical_prices(
+			market1.market.id,
+			start_timestamp + 1,
+			last_timestamp - 1,
+			MAX_PRICES_PAGE_SIZE,
+		);
+		assert_eq!(page.prices, vec![]);
+		assert_eq!(page.next_timestamp, Some(start_timestamp + 1 + MAX_PRICES_SCAN_INTERVAL));
+
+		// A page holds at least one price
+		let page = PricesModule::get_historical_prices(
+			market1.market.id,
+			start_timestamp + MAX_PRICES_SCAN_INTERVAL,
+			last_timestamp,
+			0,
+		);
+		assert_eq!(page.prices.len(), 1);
+		assert_eq!(page.prices[0].timestamp, last_timestamp);
+		assert_eq!(page.next_timestamp, None);
+	});
+}
+
+fn update_mark_price(market_id: u128, timestamp: u64, mark_price: FixedI128) {
+	assert_ok!(PricesModule::update_prices(
+		RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+		vec![MultiplePrices { market_id, index_price: mark_price, mark_price }],
+		timestamp * 1000
+	));
+}
+
+#[test]
+fn test_candles_aggregation() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market_id = eth_usdc().market.id;
+	let timestamp: u64 = 1702359600;
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc()]
+		));
+
+		Timestamp::set_timestamp(timestamp * 1000);
+		update_mark_price(market_id, timestamp, 100.into());
+		Timestamp::set_timestamp((timestamp + 30) * 1000);
+		update_mark_price(market_id, timestamp + 30, 120.into());
+		// Prices can arrive out of order
+		update_mark_price(market_id, timestamp + 10, 90.into());
+
+		let expected_ohlc =
+			OHLC { open: 100.into(), high: 120.into(), low: 90.into(), close: 120.into() };
+		for interval in CandleInterval::ALL {
+			let candle_timestamp = interval.candle_start(timestamp);
+			let candle = PricesModule::candle((interval, candle_timestamp), market_id).unwrap();
+			assert_eq!(
+				candle,
+				Candle {
+					timestamp: candle_timestamp,
+					open_timestamp: timestamp,
+					close_timestamp: timestamp + 30,
+					index_price: expected_ohlc,
+					mark_price: expected_ohlc,
+				}
+			);
+		}
+
+		// A new minute starts a new one minute candle
+		Timestamp::set_timestamp((timestamp + 60) * 1000);
+		update_mark_price(market_id, timestamp + 60, 110.into());
+		assert_eq!(
+			PricesModule::get_candles(
+				market_id,
+				CandleInterval::OneMinute,
+				timestamp,
+				timestamp + 60
+			)
+			.len(),
+			2
+		);
+		assert_eq!(
+			PricesModule::get_candles(market_id, CandleInterval::OneDay, timestamp, timestamp + 60)
+				.len(),
+			1
+		);
+	});
+}
+
+#[test]
+fn test_twap() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market_id = eth_usdc().market.id;
+	let timestamp: u64 = 1702359600;
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc()]
+		));
+
+		// No prices available yet
+		assert_eq!(PricesModule::get_twap(market_id, timestamp, timestamp + 179), None);
+
+		// A spike within the first minute doesn't affect its close
+		Timestamp::set_timestamp((timestamp + 50) * 1000);
+		update_mark_price(market_id, timestamp, 100.into());
+		update_mark_price(market_id, timestamp + 30, 1000.into());
+		update_mark_price(market_id, timestamp + 50, 100.into());
+		Timestamp::set_timestamp((timestamp + 120) * 1000);
+		update_mark_price(market_id, timestamp + 120, 130.into());
+		Timestamp::set_timestamp((timestamp + 180) * 1000);
+
+		// The price of the first minute holds in the second one
+		assert_eq!(PricesModule::get_twap(market_id, timestamp, timestamp + 179), Some(110.into()));
+		assert_eq!(PricesModule::get_twap(market_id, timestamp + 180, timestamp), None);
+	});
+}
+
+#[test]
+fn test_candles_cleanup() {
+	// Get a test environment
+	let mut env = setup();
+
+	// test variables
+	let market_id = eth_usdc().market.id;
+	let timestamp: u64 = 1702359600;
+
+	env.execute_with(|| {
+		assert_ok!(MarketModule::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc()]
+		));
+
+		Timestamp::set_timestamp(timestamp * 1000);
+		update_mark_price(market_id, timestamp, 100.into());
+
+		// One minute candles are retained for eight weeks
+		Timestamp::set_timestamp((timestamp + 4838400 + 60) * 1000);
+		assert_ok!(PricesModule::perform_prices_cleanup(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			10
+		));
+
+		let candle_of = |interval: CandleInterval| {
+			PricesModule::candle((interval, interval.candle_start(timestamp)), market_id)
+		};
+		assert_eq!(candle_of(CandleInterval::OneMinute), None);
+		assert!(candle_of(CandleInterval::OneHour).is_some());
+		assert!(candle_of(CandleInterval::OneDay).is_some());
+		assert_eq!(
+			PricesModule::candles_start_timestamp(CandleInterval::OneMinute),
+			Some(timestamp + 60)
+		);
+
+		// A later candle doesn't move the start timestamp
+		update_mark_price(market_id, timestamp + 4838400 + 60, 101.into());
+		assert_eq!(
+			PricesModule::candles_start_timestamp(CandleInterval::OneMinute),
+			Some(timestamp + 60)
+		);
+	});
+}
+
 #[test]
 #[should_panic(expected = "FutureTimestampPriceUpdate")]
 fn test_update_prices_with_future_timestamp() {
@@ -410,10 +749,19 @@ fn test_historical_prices_cleanup_after_timelimit() {
 		// Increment the blocktimestamp by 4 weeks
 		Timestamp::set_timestamp(1704779800000);
 
+		// Cleanup stops at a timestamp that has prices of more markets than markets_count
+		assert_ok!(PricesModule::perform_prices_cleanup(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			1
+		));
+		let start_timestamp = PricesModule::prices_start_timestamp().unwrap();
+		assert_eq!(start_timestamp, 1702359400);
+
 		// Perform cleanup of historical price data
-		assert_ok!(PricesModule::perform_prices_cleanup(RuntimeOrigin::signed(
-			sp_core::sr25519::Public::from_raw([1u8; 32])
-		)));
+		assert_ok!(PricesModule::perform_prices_cleanup(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			10
+		));
 
 		// Read historical prices after cleanup, every price should show as zero
 		let historical_price = PricesModule::historical_price(1702359500, market1.market.id);
@@ -536,9 +884,10 @@ fn test_historical_prices_cleanup_before_timelimit() {
 		Timestamp::set_timestamp(1703569200000);
 
 		// Perform cleanup of historical price data
-		assert_ok!(PricesModule::perform_prices_cleanup(RuntimeOrigin::signed(
-			sp_core::sr25519::Public::from_raw([1u8; 32])
-		)));
+		assert_ok!(PricesModule::perform_prices_cleanup(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			10
+		));
 
 		// Prices remian intact becuase we called cleanup before the timelimit
 		let historical_price = PricesModule::historical_price(1702359601, market1.market.id);
@@ -0,0 +1,255 @@
+//! Weights for pallet_prices
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_prices.
+pub trait WeightInfo {
+	fn set_initialisation_timestamp() -> Weight;
+	fn set_default_max_abr() -> Weight;
+	fn set_max_abr() -> Weight;
+	fn set_abr_interval() -> Weight;
+	fn set_base_abr() -> Weight;
+	fn set_bollinger_width() -> Weight;
+	fn set_no_of_users_per_batch() -> Weight;
+	fn set_abr_value(h: u32, ) -> Weight;
+	fn make_abr_payments(u: u32, ) -> Weight;
+	fn update_prices(m: u32, ) -> Weight;
+	fn perform_prices_cleanup(m: u32, ) -> Weight;
+}
+
+/// Weights for pallet_prices using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Prices InitialisationTimestamp (r:1 w:1)
+	fn set_initialisation_timestamp() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices MaxABRDefault (r:0 w:1)
+	fn set_default_max_abr() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Prices MaxABRPerMarket (r:0 w:1)
+	fn set_max_abr() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices AbrInterval (r:0 w:1)
+	fn set_abr_interval() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices BaseAbr (r:0 w:1)
+	fn set_base_abr() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices BollingerWidth (r:0 w:1)
+	fn set_bollinger_width() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices UsersPerBatch (r:0 w:1)
+	fn set_no_of_users_per_batch() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices AbrEpoch (r:1 w:1)
+	/// Storage: Prices AbrState (r:1 w:1)
+	/// Storage: Prices AbrInterval (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices InitialisationTimestamp (r:1 w:0)
+	/// Storage: Prices UsersPerBatch (r:1 w:0)
+	/// Storage: TradingAccount AccountsCount (r:1 w:0)
+	/// Storage: Prices EpochToTimestampMap (r:1 w:1)
+	/// Storage: Prices NoOfBatchesForEpochMap (r:0 w:1)
+	/// Storage: Markets MarketMap (r:2 w:0)
+	/// Storage: Prices AbrMarketStatusMap (r:2 w:1)
+	/// Storage: Prices HistoricalPricesMap (r:1830 w:0)
+	/// Storage: Prices BaseAbr (r:1 w:0)
+	/// Storage: Prices BollingerWidth (r:1 w:0)
+	/// Storage: Prices MaxABRPerMarket (r:1 w:0)
+	/// Storage: Prices MaxABRDefault (r:1 w:0)
+	/// Storage: Prices EpochMarketToAbrValueMap (r:0 w:1)
+	/// Storage: Prices EpochMarketToLastPriceMap (r:0 w:1)
+	/// The range of component `h` is `[1, 8]`.
+	fn set_abr_value(h: u32, ) -> Weight {
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(Weight::from_parts(112_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().reads((1830_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Prices AbrState (r:1 w:1)
+	/// Storage: Prices AbrEpoch (r:1 w:1)
+	/// Storage: Prices EpochToTimestampMap (r:1 w:0)
+	/// Storage: Prices UsersPerBatch (r:1 w:0)
+	/// Storage: Prices BatchesFetchedForEpochMap (r:1 w:1)
+	/// Storage: Prices NoOfBatchesForEpochMap (r:1 w:0)
+	/// Storage: TradingAccount AccountsListMap (r:100 w:0)
+	/// Storage: TradingAccount AccountCollateralsMap (r:100 w:0)
+	/// Storage: Trading PositionsMap (r:100 w:0)
+	/// The range of component `u` is `[1, 100]`.
+	fn make_abr_payments(u: u32, ) -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(u.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(u.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices PricesStartTimestamp (r:1 w:1)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: Prices CurrentPricesMap (r:100 w:100)
+	/// Storage: Prices HistoricalPricesMap (r:0 w:100)
+	/// Storage: Prices CandlesMap (r:300 w:300)
+	/// Storage: Prices CandlesStartTimestampMap (r:3 w:3)
+	/// The range of component `m` is `[1, 100]`.
+	fn update_prices(m: u32, ) -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(m.into())))
+	}
+	/// Storage: Prices PricesStartTimestamp (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices HistoricalPricesMap (r:0 w:12000)
+	/// Storage: Prices CandlesStartTimestampMap (r:2 w:2)
+	/// Storage: Prices CandlesMap (r:0 w:12000)
+	/// The range of component `m` is `[1, 100]`.
+	fn perform_prices_cleanup(m: u32, ) -> Weight {
+		Weight::from_parts(48_075_000_000, 0)
+			.saturating_add(Weight::from_parts(480_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(12_003_u64))
+			.saturating_add(T::DbWeight::get().writes((120_u64).saturating_mul(m.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Prices InitialisationTimestamp (r:1 w:1)
+	fn set_initialisation_timestamp() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices MaxABRDefault (r:0 w:1)
+	fn set_default_max_abr() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Prices MaxABRPerMarket (r:0 w:1)
+	fn set_max_abr() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices AbrInterval (r:0 w:1)
+	fn set_abr_interval() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices BaseAbr (r:0 w:1)
+	fn set_base_abr() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices BollingerWidth (r:0 w:1)
+	fn set_bollinger_width() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices UsersPerBatch (r:0 w:1)
+	fn set_no_of_users_per_batch() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Prices AbrEpoch (r:1 w:1)
+	/// Storage: Prices AbrState (r:1 w:1)
+	/// Storage: Prices AbrInterval (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices InitialisationTimestamp (r:1 w:0)
+	/// Storage: Prices UsersPerBatch (r:1 w:0)
+	/// Storage: TradingAccount AccountsCount (r:1 w:0)
+	/// Storage: Prices EpochToTimestampMap (r:1 w:1)
+	/// Storage: Prices NoOfBatchesForEpochMap (r:0 w:1)
+	/// Storage: Markets MarketMap (r:2 w:0)
+	/// Storage: Prices AbrMarketStatusMap (r:2 w:1)
+	/// Storage: Prices HistoricalPricesMap (r:1830 w:0)
+	/// Storage: Prices BaseAbr (r:1 w:0)
+	/// Storage: Prices BollingerWidth (r:1 w:0)
+	/// Storage: Prices MaxABRPerMarket (r:1 w:0)
+	/// Storage: Prices MaxABRDefault (r:1 w:0)
+	/// Storage: Prices EpochMarketToAbrValueMap (r:0 w:1)
+	/// Storage: Prices EpochMarketToLastPriceMap (r:0 w:1)
+	/// The range of component `h` is `[1, 8]`.
+	fn set_abr_value(h: u32, ) -> Weight {
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(Weight::from_parts(112_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().reads((1830_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Prices AbrState (r:1 w:1)
+	/// Storage: Prices AbrEpoch (r:1 w:1)
+	/// Storage: Prices EpochToTimestampMap (r:1 w:0)
+	/// Storage: Prices UsersPerBatch (r:1 w:0)
+	/// Storage: Prices BatchesFetchedForEpochMap (r:1 w:1)
+	/// Storage: Prices NoOfBatchesForEpochMap (r:1 w:0)
+	/// Storage: TradingAccount AccountsListMap (r:100 w:0)
+	/// Storage: TradingAccount AccountCollateralsMap (r:100 w:0)
+	/// Storage: Trading PositionsMap (r:100 w:0)
+	/// The range of component `u` is `[1, 100]`.
+	fn make_abr_payments(u: u32, ) -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(u.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(u.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices PricesStartTimestamp (r:1 w:1)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: Prices CurrentPricesMap (r:100 w:100)
+	/// Storage: Prices HistoricalPricesMap (r:0 w:100)
+	/// Storage: Prices CandlesMap (r:300 w:300)
+	/// Storage: Prices CandlesStartTimestampMap (r:3 w:3)
+	/// The range of component `m` is `[1, 100]`.
+	fn update_prices(m: u32, ) -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(m.into())))
+	}
+	/// Storage: Prices PricesStartTimestamp (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Prices HistoricalPricesMap (r:0 w:12000)
+	/// Storage: Prices CandlesStartTimestampMap (r:2 w:2)
+	/// Storage: Prices CandlesMap (r:0 w:12000)
+	/// The range of component `m` is `[1, 100]`.
+	fn perform_prices_cleanup(m: u32, ) -> Weight {
+		Weight::from_parts(48_075_000_000, 0)
+			.saturating_add(Weight::from_parts(480_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_003_u64))
+			.saturating_add(RocksDbWeight::get().writes((120_u64).saturating_mul(m.into())))
+	}
+}
@@ -16,13 +16,13 @@ pub mod pallet {
 			FixedI128Ext, MarketInterface, PricesInterface, RiskManagementInterface,
 			TradingAccountInterface, TradingInterface,
 		},
-		types::{Direction, ForceClosureFlag, Order, OrderType, Position, Side},
+		types::{Direction, ForceClosureFlag, Order, Position, Side},
 	};
 	use primitive_types::U256;
 	use sp_arithmetic::{traits::Zero, FixedI128, FixedPointNumber};
 
-	static _TWO_FI128: FixedI128 = FixedI128::from_inner(2000000000000000000);
-	static _TWO_POINT_FIVE_FI128: FixedI128 = FixedI128::from_inner(2500000000000000000);
+	static TWO_FI128: FixedI128 = FixedI128::from_inner(2000000000000000000);
+	static TWO_POINT_FIVE_FI128: FixedI128 = FixedI128::from_inner(2500000000000000000);
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -36,7 +36,7 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
-		fn _is_account_deleveragable(
+		fn is_account_deleveragable(
 			account_id: U256,
 			collateral_id: u128,
 			market_id: u128,
@@ -52,13 +52,21 @@ pub mod pallet {
 				if mark_price == FixedI128::zero() {
 					return (true, FixedI128::zero())
 				}
-				let long_position =
+				let mut long_position =
 					T::TradingPallet::get_position(account_id, market, Direction::Long);
-				let short_position =
+				let mut short_position =
 					T::TradingPallet::get_position(account_id, market, Direction::Short);
 
+				// Isolated positions are not part of the cross margin of the account
+				if T::TradingPallet::is_isolated_position(account_id, market, Direction::Long) {
+					long_position.size = FixedI128::zero();
+				}
+				if T::TradingPallet::is_isolated_position(account_id, market, Direction::Short) {
+					short_position.size = FixedI128::zero();
+				}
+
 				if long_position.size != FixedI128::zero() {
-					Self::_calculate_tav_and_tmr(
+					Self::calculate_tav_and_tmr(
 						&mut total_account_value,
 						&mut total_maintenance_margin,
 						long_position,
@@ -66,7 +74,7 @@ pub mod pallet {
 					)
 				}
 				if short_position.size != FixedI128::zero() {
-					Self::_calculate_tav_and_tmr(
+					Self::calculate_tav_and_tmr(
 						&mut total_account_value,
 						&mut total_maintenance_margin,
 						short_position,
@@ -95,7 +103,7 @@ pub mod pallet {
 					position.avg_execution_price - mark_price
 				};
 
-				if (price_diff >= FixedI128::zero()) || position.leverage <= _TWO_FI128 {
+				if (price_diff >= FixedI128::zero()) || position.leverage <= TWO_FI128 {
 					return (true, FixedI128::zero())
 				}
 
@@ -114,7 +122,7 @@ pub mod pallet {
 				let leverage_after_deleveraging = remaining_position_value / position.margin_amount;
 
 				if leverage_after_deleveraging <= 2.into() {
-					let new_size = (_TWO_FI128 * position.margin_amount) / mark_price;
+					let new_size = (TWO_FI128 * position.margin_amount) / mark_price;
 					let amount_to_be_sold = position.size - new_size;
 					let amount_to_be_sold =
 						amount_to_be_sold.round_to_precision(market.step_precision.into());
@@ -127,19 +135,19 @@ pub mod pallet {
 			}
 		}
 
-		fn _calculate_tav_and_tmr(
+		fn calculate_tav_and_tmr(
 			total_account_value: &mut FixedI128,
 			total_maintenance_margin: &mut FixedI128,
 			position: Position,
 			price: FixedI128,
 		) {
 			let (position_value, maintenance_margin) =
-				Self::_is_position_deleveragable(position, price);
+				Self::is_position_deleveragable(position, price);
 			*total_account_value = *total_account_value + position_value;
 			*total_maintenance_margin = *total_maintenance_margin + maintenance_margin;
 		}
 
-		fn _is_position_deleveragable(
+		fn is_position_deleveragable(
 			position: Position,
 			price: FixedI128,
 		) -> (FixedI128, FixedI128) {
@@ -161,7 +169,7 @@ pub mod pallet {
 			// Sell the position such that resulting leverage is 2.5
 			// amount_to_sell = initial_size - ((2.5 * margin_amount)/current_asset_price)
 			if pnl.is_negative() {
-				let new_size = (_TWO_POINT_FIVE_FI128 * position.margin_amount) / price;
+				let new_size = (TWO_POINT_FIVE_FI128 * position.margin_amount) / price;
 				let new_size = new_size.round_to_precision(market.step_precision.into());
 				let value_to_sell = (position.size - new_size) * price;
 				// Calculate the new borrowed amount if the total position size is
@@ -195,13 +203,33 @@ pub mod pallet {
 			let leveraged_position_value = execution_price * size;
 			let maintenance_requirement = req_margin_fraction * leveraged_position_value;
 
-			let (liq_result, _, available_margin, _, _, _) =
-				T::TradingAccountPallet::get_margin_info(
+			let (liq_result, available_margin) = if order.is_isolated {
+				// Margin of an isolated position is taken from the available margin of the
+				// account, but its liquidation depends only on the position itself
+				let (_, _, available_margin, _, _, _) = T::TradingAccountPallet::get_margin_info(
 					order.account_id,
 					market.asset_collateral,
+					FixedI128::zero(),
+					margin_amount,
+				);
+				let (liq_result, _, _) = T::TradingAccountPallet::get_isolated_margin_info(
+					order.account_id,
+					order.market_id,
+					order.direction,
 					maintenance_requirement,
 					margin_amount,
 				);
+				(liq_result, available_margin)
+			} else {
+				let (liq_result, _, available_margin, _, _, _) =
+					T::TradingAccountPallet::get_margin_info(
+						order.account_id,
+						market.asset_collateral,
+						maintenance_requirement,
+						margin_amount,
+					);
+				(liq_result, available_margin)
+			};
 
 			let mut is_error: bool = false;
 			if liq_result == true {
@@ -209,7 +237,7 @@ pub mod pallet {
 			} else {
 				if (order.direction == Direction::Short) &&
 					(order.side == Side::Buy) &&
-					(order.order_type == OrderType::Limit)
+					order.order_type.is_limit()
 				{
 					let price_diff = oracle_price - execution_price;
 					let pnl = price_diff * size;
@@ -224,9 +252,26 @@ pub mod pallet {
 		fn check_for_force_closure(
 			account_id: U256,
 			collateral_id: u128,
-			_market_id: u128,
-			_direction: Direction,
+			market_id: u128,
+			direction: Direction,
 		) {
+			// An isolated position is force closed only if its own margin is not sufficient
+			if T::TradingPallet::is_isolated_position(account_id, market_id, direction) {
+				let (liq_result, _, _) = T::TradingAccountPallet::get_isolated_margin_info(
+					account_id,
+					market_id,
+					direction,
+					FixedI128::zero(),
+					FixedI128::zero(),
+				);
+				if liq_result {
+					T::TradingPallet::set_flag_for_isolated_force_order(
+						account_id, market_id, direction,
+					);
+				}
+				return
+			}
+
 			let (liq_result, _, _, _, _, _) = T::TradingAccountPallet::get_margin_info(
 				account_id,
 				collateral_id,
@@ -235,15 +280,35 @@ pub mod pallet {
 			);
 
 			if liq_result == true {
-				// DELEVERAGE REMOVED
-				// Removed call to is_account_deleveragable
-				// Removed if condition of the previous call to set deleveragable flag
-				T::TradingPallet::set_flags_for_force_orders(
-					account_id,
-					collateral_id,
-					ForceClosureFlag::Liquidate,
-					FixedI128::zero(),
-				);
+				// An account that is already being liquidated is not moved back to deleveraging
+				let (is_deleveragable, amount_to_be_sold) =
+					match T::TradingPallet::get_force_closure_flags(account_id, collateral_id) {
+						Some(ForceClosureFlag::Liquidate) => (false, FixedI128::zero()),
+						_ => Self::is_account_deleveragable(
+							account_id,
+							collateral_id,
+							market_id,
+							direction,
+						),
+					};
+
+				// Account is deleveraged only if selling a part of the position is enough to
+				// bring it above the maintenance requirement, else it is liquidated
+				if is_deleveragable && amount_to_be_sold > FixedI128::zero() {
+					T::TradingPallet::set_flags_for_force_orders(
+						account_id,
+						collateral_id,
+						ForceClosureFlag::Deleverage,
+						amount_to_be_sold,
+					);
+				} else {
+					T::TradingPallet::set_flags_for_force_orders(
+						account_id,
+						collateral_id,
+						ForceClosureFlag::Liquidate,
+						FixedI128::zero(),
+					);
+				}
 			}
 		}
 	}
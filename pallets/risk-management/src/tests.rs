@@ -11,8 +11,8 @@ use pallet_support::{
 	},
 	traits::TradingAccountInterface,
 	types::{
-		BalanceUpdate, BaseFee, BaseFeeAggregate, Direction, FundModifyType, MultiplePrices, Order,
-		OrderType, Position, Side,
+		BalanceUpdate, BaseFee, BaseFeeAggregate, Direction, ForceClosureFlag, FundModifyType,
+		MultiplePrices, Order, OrderType, Position, Side,
 	},
 };
 use pallet_trading::Event as TradingEvent;
@@ -781,12 +781,12 @@ fn test_liquidation_multiple_positions() {
 
 		Timestamp::set_timestamp(1699949278000);
 
-		// Decrease the price of BTCUSDC
+		// Decrease the price of BTCUSDC, such that the account cannot be deleveraged
 		let mut index_prices: Vec<MultiplePrices> = Vec::new();
 		let index_price1 = MultiplePrices {
 			market_id: btc_usdc().market.id,
-			index_price: 8000.into(),
-			mark_price: 8000.into(),
+			index_price: 7500.into(),
+			mark_price: 7500.into(),
 		};
 		index_prices.push(index_price1);
 		assert_ok!(Prices::update_prices(
@@ -811,14 +811,14 @@ fn test_liquidation_multiple_positions() {
 
 		let charlie_order = Order::new(204.into(), charlie_id)
 			.set_size(9.into())
-			.set_price(8000.into())
+			.set_price(7500.into())
 			.set_leverage(8.into())
 			.set_timestamp(1699949278000)
 			.sign_order(get_private_key(charlie().pub_key));
 
 		let alice_forced_order = Order::new(203.into(), alice_id)
 			.set_size(9.into())
-			.set_price(8000.into())
+			.set_price(7500.into())
 			.set_order_type(OrderType::Forced)
 			.set_direction(Direction::Long)
 			.set_side(Side::Sell)
@@ -834,7 +834,7 @@ fn test_liquidation_multiple_positions() {
 			// market
 			market_id,
 			// price
-			8000.into(),
+			7500.into(),
 			// orders
 			vec![charlie_order, alice_forced_order],
 			// batch_timestamp
@@ -902,7 +902,7 @@ fn test_liquidation_multiple_positions() {
 				account_id: alice_id,
 				order_id: U256::from(203),
 				market_id: btc_usdc().market.id,
-				amount: FixedI128::from_inner(5062500000000000000000),
+				amount: FixedI128::from_inner(562500000000000000000),
 				block_number: 1,
 			}
 			.into(),
@@ -1440,7 +1440,8 @@ fn test_liquidation_underwater_negative_balance() {
 		let flag = Trading::force_closure_flag(alice_id, btc_usdc().market.asset_collateral);
 		assert_eq!(flag.is_none(), true);
 
-		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), new_user_balance + pnl);
+		// Loss is covered by the insurance fund, balance of the user is left as is
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), new_user_balance);
 
 		assert_has_events(vec![
 			Event::InsuranceFundChangeV2 {
@@ -1621,7 +1622,8 @@ fn test_liquidation_underwater_non_negative_balance() {
 		let flag = Trading::force_closure_flag(alice_id, btc_usdc().market.asset_collateral);
 		assert_eq!(flag.is_none(), true);
 
-		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), user_balance + pnl);
+		// Loss above the balance is covered by the insurance fund
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), FixedI128::zero());
 
 		assert_has_events(vec![
 			Event::InsuranceFundChangeV2 {
@@ -1643,6 +1645,161 @@ fn test_liquidation_underwater_non_negative_balance() {
 	});
 }
 
+#[test]
+fn test_liquidation_loss_socialisation() {
+	let mut env = setup();
+	let market_id: u128 = btc_usdc().market.id;
+	let btc_insurance_fund = U256::from(2_u8);
+	let collateral_id = usdc().asset.id;
+
+	env.execute_with(|| {
+		// Set insurance fund for BTC
+		assert_ok!(TradingAccounts::update_fee_split_details(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			market_id,
+			btc_insurance_fund,
+			FixedI128::from_float(0.1)
+		));
+
+		// Insurance fund can cover only a part of the loss
+		assert_ok!(TradingAccounts::update_insurance_fund_balance(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_insurance_fund,
+			collateral_id,
+			FixedI128::from_u32(1),
+		));
+		assert_ok!(Trading::set_loss_socialisation(RuntimeOrigin::root(), true));
+
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_size(5.into())
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(alice().pub_key));
+
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_size(5.into())
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(1_u8),
+			// size
+			5.into(),
+			// market
+			market_id,
+			// price
+			10000.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		// Decrease the price of the asset
+		let mut index_prices: Vec<MultiplePrices> = Vec::new();
+		let index_price1 = MultiplePrices {
+			market_id,
+			index_price: FixedI128::from_float(7999.7),
+			mark_price: FixedI128::from_float(7999.7),
+		};
+		index_prices.push(index_price1);
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			index_prices,
+			1699940278000
+		));
+
+		// Place Forced order for liquidation
+		let charlie_order = Order::new(204.into(), charlie_id)
+			.set_size(5.into())
+			.set_price(FixedI128::from_float(7999.7))
+			.set_leverage(5.into())
+			.sign_order(get_private_key(charlie().pub_key));
+
+		let alice_forced_order = Order::new(203.into(), alice_id)
+			.set_size(5.into())
+			.set_price(FixedI128::from_float(7999.7))
+			.set_order_type(OrderType::Forced)
+			.set_direction(Direction::Long)
+			.set_side(Side::Sell)
+			.sign_order_liquidator(get_private_key(eduard().pub_key), eduard().pub_key);
+
+		let bob_balance_before = TradingAccounts::balances(bob_id, collateral_id);
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(2_u8),
+			// size
+			5.into(),
+			// market
+			market_id,
+			// price
+			FixedI128::from_float(7999.7),
+			// orders
+			vec![charlie_order, alice_forced_order],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		// Here,
+		// Margin: 10000 USDC
+		// Pnl: -10001.5
+		// Balance: 10000
+		// Insurance fund: 1
+		// Case: Loss of 1.5 above the balance, 1 is covered by the insurance fund and 0.5 is
+		// socialised to bob's short position
+		let residual_loss = FixedI128::from_inner(500000000000000000);
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(btc_insurance_fund, collateral_id),
+			FixedI128::zero()
+		);
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), FixedI128::zero());
+		assert_eq!(
+			TradingAccounts::balances(bob_id, collateral_id),
+			bob_balance_before - residual_loss
+		);
+
+		assert_has_events(vec![
+			Event::InsuranceFundChangeV2 {
+				market_id,
+				amount: FixedI128::from_u32(1),
+				modify_type: FundModifyType::Decrease,
+				block_number: 1,
+			}
+			.into(),
+			TradingEvent::BankruptcyCovered {
+				account_id: alice_id,
+				order_id: U256::from(203),
+				market_id,
+				amount: FixedI128::from_u32(1),
+			}
+			.into(),
+			TradingEvent::LossSocialised {
+				account_id: alice_id,
+				order_id: U256::from(203),
+				market_id,
+				amount: residual_loss,
+			}
+			.into(),
+		]);
+	});
+}
+
 #[test]
 fn test_liquidation_underwater_sufficient_balance() {
 	let mut env = setup();
@@ -1796,3 +1953,358 @@ fn test_liquidation_underwater_sufficient_balance() {
 		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), new_user_balance + pnl);
 	});
 }
+
+#[test]
+fn test_liquidation_isolated_position() {
+	let mut env = setup();
+	let default_insurance_fund = U256::from(1_u8);
+	let collateral_id = usdc().asset.id;
+
+	env.execute_with(|| {
+		// Set balance of default insurance fund
+		assert_ok!(TradingAccounts::update_insurance_fund_balance(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			default_insurance_fund,
+			collateral_id,
+			FixedI128::from_u32(1000000),
+		));
+
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Alice opens an isolated position with a margin of 4000
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_size(2.into())
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.set_is_isolated(true)
+			.sign_order(get_private_key(alice().pub_key));
+
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_size(2.into())
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(1_u8),
+			// size
+			2.into(),
+			// market
+			market_id,
+			// price
+			10000.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940278000,
+		));
+		assert!(Trading::isolated_position(alice_id, (market_id, Direction::Long)));
+
+		// Decrease the price of the asset
+		let mut index_prices: Vec<MultiplePrices> = Vec::new();
+		let index_price1 =
+			MultiplePrices { market_id, index_price: 7000.into(), mark_price: 7000.into() };
+		index_prices.push(index_price1);
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			index_prices,
+			1699940278000
+		));
+
+		// Loss of the isolated position does not make the account liquidatable
+		let (is_liquidation, _, _, _, _, _) = TradingAccounts::get_margin_info(
+			alice_id,
+			collateral_id,
+			FixedI128::zero(),
+			FixedI128::zero(),
+		);
+		assert!(!is_liquidation);
+		let (is_liquidation, _, _) = TradingAccounts::get_isolated_margin_info(
+			alice_id,
+			market_id,
+			Direction::Long,
+			FixedI128::zero(),
+			FixedI128::zero(),
+		);
+		assert!(is_liquidation);
+
+		// Place Forced order for liquidation
+		let charlie_order = Order::new(204.into(), charlie_id)
+			.set_size(2.into())
+			.set_price(7000.into())
+			.set_leverage(5.into())
+			.sign_order(get_private_key(charlie().pub_key));
+
+		let alice_forced_order = Order::new(203.into(), alice_id)
+			.set_size(2.into())
+			.set_price(7000.into())
+			.set_order_type(OrderType::Forced)
+			.set_direction(Direction::Long)
+			.set_side(Side::Sell)
+			.sign_order_liquidator(get_private_key(eduard().pub_key), eduard().pub_key);
+
+		let insurance_fund_balance_before =
+			TradingAccounts::insurance_fund_balance(default_insurance_fund, collateral_id);
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(2_u8),
+			// size
+			2.into(),
+			// market
+			market_id,
+			// price
+			7000.into(),
+			// orders
+			vec![charlie_order, alice_forced_order],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		// Loss beyond the margin of the position is covered by the insurance fund
+		let insurance_fund_balance_after =
+			TradingAccounts::insurance_fund_balance(default_insurance_fund, collateral_id);
+		assert_eq!(
+			insurance_fund_balance_after,
+			insurance_fund_balance_before - FixedI128::from_u32(2000)
+		);
+
+		// Alice loses only the margin of the isolated position
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), 6000.into());
+		assert_eq!(TradingAccounts::locked_margin(alice_id, collateral_id), 0.into());
+
+		let alice_position = Trading::positions(alice_id, (market_id, Direction::Long));
+		assert_eq!(alice_position.size, FixedI128::zero());
+		assert!(!Trading::isolated_position(alice_id, (market_id, Direction::Long)));
+		assert!(!Trading::isolated_force_closure_flag(alice_id, (market_id, Direction::Long)));
+		assert!(Trading::force_closure_flag(alice_id, collateral_id).is_none());
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError540")]
+fn test_invalid_forced_order_isolated_position() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Alice opens an isolated position with a margin of 4000
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_size(2.into())
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.set_is_isolated(true)
+			.sign_order(get_private_key(alice().pub_key));
+
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_size(2.into())
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(1_u8),
+			// size
+			2.into(),
+			// market
+			market_id,
+			// price
+			10000.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		// Decrease the price of the asset, the position still has enough margin
+		let mut index_prices: Vec<MultiplePrices> = Vec::new();
+		let index_price1 =
+			MultiplePrices { market_id, index_price: 9500.into(), mark_price: 9500.into() };
+		index_prices.push(index_price1);
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			index_prices,
+			1699940278000
+		));
+
+		// Place Forced order for liquidation
+		let charlie_order = Order::new(204.into(), charlie_id)
+			.set_size(2.into())
+			.set_price(9500.into())
+			.set_leverage(5.into())
+			.sign_order(get_private_key(charlie().pub_key));
+
+		let alice_forced_order = Order::new(203.into(), alice_id)
+			.set_size(2.into())
+			.set_price(9500.into())
+			.set_order_type(OrderType::Forced)
+			.set_direction(Direction::Long)
+			.set_side(Side::Sell)
+			.sign_order_liquidator(get_private_key(eduard().pub_key), eduard().pub_key);
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(2_u8),
+			// size
+			2.into(),
+			// market
+			market_id,
+			// price
+			9500.into(),
+			// orders
+			vec![charlie_order, alice_forced_order],
+			// batch_timestamp
+			1699940278000,
+		));
+	});
+}
+
+#[test]
+// Account which can get above the maintenance requirement by selling a part of its position is
+// deleveraged instead of being liquidated
+fn test_deleverage_position() {
+	let mut env = setup();
+	let default_insurance_fund = U256::from(1_u8);
+	let collateral_id = usdc().asset.id;
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_size(5.into())
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(alice().pub_key));
+
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_size(5.into())
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.set_leverage(5.into())
+			.set_price(10000.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(1_u8),
+			// size
+			5.into(),
+			// market
+			market_id,
+			// price
+			10000.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		// Decrease the price of the asset
+		// Total margin of 2500 is below the maintenance requirement of 3187.5, but it is above
+		// the requirement of 2175 for the position reduced to 2.9
+		let mut index_prices: Vec<MultiplePrices> = Vec::new();
+		let index_price1 =
+			MultiplePrices { market_id, index_price: 8500.into(), mark_price: 8500.into() };
+		index_prices.push(index_price1);
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			index_prices,
+			1699940278000
+		));
+
+		// Place Forced order for deleveraging
+		let charlie_order = Order::new(204.into(), charlie_id)
+			.set_size(5.into())
+			.set_price(8500.into())
+			.sign_order(get_private_key(charlie().pub_key));
+
+		let alice_forced_order = Order::new(203.into(), alice_id)
+			.set_size(5.into())
+			.set_price(8500.into())
+			.set_order_type(OrderType::Forced)
+			.set_direction(Direction::Long)
+			.set_side(Side::Sell)
+			.sign_order_liquidator(get_private_key(eduard().pub_key), eduard().pub_key);
+
+		let insurance_fund_balance_before =
+			TradingAccounts::insurance_fund_balance(default_insurance_fund, collateral_id);
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch id
+			U256::from(2_u8),
+			// size
+			5.into(),
+			// market
+			market_id,
+			// price
+			8500.into(),
+			// orders
+			vec![charlie_order, alice_forced_order],
+			// batch_timestamp
+			1699940278000,
+		));
+
+		System::assert_has_event(
+			TradingEvent::ForceClosureFlagsChanged {
+				account_id: alice_id,
+				collateral_id,
+				force_closure_flag: ForceClosureFlag::Deleverage.into(),
+			}
+			.into(),
+		);
+
+		// Only the deleveragable amount of 0.3 is sold, margin of the position is unchanged
+		// and only its borrowed amount is reduced
+		let alice_position = Trading::positions(alice_id, (market_id, alice_order.direction));
+		assert_eq!(alice_position.size, FixedI128::from_inner(4700000000000000000));
+		assert_eq!(alice_position.margin_amount, 10000.into());
+		assert_eq!(alice_position.borrowed_amount, 37450.into());
+
+		let charlie_position = Trading::positions(charlie_id, (market_id, Direction::Long));
+		assert_eq!(charlie_position.size, FixedI128::from_inner(300000000000000000));
+
+		// Deleveraging does not realize any loss or touch the insurance fund
+		assert_eq!(TradingAccounts::get_balance(alice_id, collateral_id), 10000.into());
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(default_insurance_fund, collateral_id),
+			insurance_fund_balance_before
+		);
+
+		// Flags are reset once the deleveragable amount is sold
+		assert_eq!(Trading::deleveragable_amount(alice_id, collateral_id), FixedI128::zero());
+		let flag = Trading::force_closure_flag(alice_id, collateral_id);
+		assert_eq!(flag.is_none(), true);
+	});
+}
@@ -20,12 +20,16 @@ pub mod test_helpers;
 // helper fns to be used by other pallets
 pub mod helpers {
 	use super::{FieldElement, FromByteSliceError};
-	use crate::traits::U256Ext;
-	use core::f64;
+	use crate::{traits::U256Ext, types::Market};
+	use core::{cmp::min, f64};
 	use frame_support::dispatch::Vec;
 	use libm::log;
 	use primitive_types::U256;
-	use sp_arithmetic::{fixed_point::FixedI128, traits::One};
+	use sp_arithmetic::{
+		fixed_point::FixedI128,
+		traits::{One, Zero},
+		FixedPointNumber,
+	};
 	pub use starknet_core::crypto::compute_hash_on_elements;
 
 	// Unix timestamp for 1st Nov 12:00 AM UTC
@@ -133,6 +137,41 @@ pub mod helpers {
 		return (day_cur - day_prev) as usize;
 	}
 
+	// Function to check whether a market has size tiers configured
+	// An initial margin fraction of 0 or of 1 and above does not allow any leverage, so such a
+	// market falls back to its currently allowed leverage
+	pub fn has_size_tiers(market: &Market) -> bool {
+		market.initial_margin_fraction > FixedI128::zero() &&
+			market.initial_margin_fraction < FixedI128::one()
+	}
+
+	// Function to calculate the initial margin fraction required for a position size
+	// Every increment of incremental_position_size (or part of it) beyond the baseline position
+	// size raises the fraction by incremental_initial_margin_fraction
+	pub fn get_initial_margin_fraction(market: &Market, position_size: FixedI128) -> FixedI128 {
+		if market.incremental_position_size <= FixedI128::zero() ||
+			position_size <= market.baseline_position_size
+		{
+			return market.initial_margin_fraction
+		}
+
+		let increments = ((position_size - market.baseline_position_size) /
+			market.incremental_position_size)
+			.ceil();
+		market.initial_margin_fraction + increments * market.incremental_initial_margin_fraction
+	}
+
+	// Function to calculate the maximum leverage allowed for a position size
+	// It is the inverse of the initial margin fraction, capped by the currently allowed leverage
+	pub fn get_max_leverage(market: &Market, position_size: FixedI128) -> FixedI128 {
+		if !has_size_tiers(market) {
+			return market.currently_allowed_leverage
+		}
+
+		let initial_margin_fraction = get_initial_margin_fraction(market, position_size);
+		min(market.currently_allowed_leverage, FixedI128::one() / initial_margin_fraction)
+	}
+
 	// Function to calculate 30day volume from volume vector
 	pub fn calc_30day_volume(volume_array: &Vec<FixedI128>) -> FixedI128 {
 		let mut total_volume: FixedI128 = FixedI128::from_inner(0);
@@ -1,4 +1,4 @@
-use crate::types::{ExtendedMarket, Market};
+use crate::types::{ExtendedMarket, Market, MarketState};
 use sp_arithmetic::fixed_point::FixedI128;
 use sp_runtime::BoundedVec;
 
@@ -10,9 +10,9 @@ impl ExtendedMarket {
 		extended_market
 	}
 
-	pub fn set_is_tradable(self: ExtendedMarket, is_tradable: bool) -> ExtendedMarket {
+	pub fn set_state(self: ExtendedMarket, state: MarketState) -> ExtendedMarket {
 		let mut extended_market = self;
-		extended_market.market.is_tradable = is_tradable;
+		extended_market.market.state = state;
 
 		extended_market
 	}
@@ -80,6 +80,57 @@ impl ExtendedMarket {
 
 		extended_market
 	}
+
+	pub fn set_max_open_interest(
+		self: ExtendedMarket,
+		max_open_interest: Option<FixedI128>,
+	) -> ExtendedMarket {
+		let mut extended_market = self;
+		extended_market.market.max_open_interest = max_open_interest;
+
+		extended_market
+	}
+
+	pub fn set_initial_margin_fraction(
+		self: ExtendedMarket,
+		initial_margin_fraction: FixedI128,
+	) -> ExtendedMarket {
+		let mut extended_market = self;
+		extended_market.market.initial_margin_fraction = initial_margin_fraction;
+
+		extended_market
+	}
+
+	pub fn set_incremental_initial_margin_fraction(
+		self: ExtendedMarket,
+		incremental_initial_margin_fraction: FixedI128,
+	) -> ExtendedMarket {
+		let mut extended_market = self;
+		extended_market.market.incremental_initial_margin_fraction =
+			incremental_initial_margin_fraction;
+
+		extended_market
+	}
+
+	pub fn set_baseline_position_size(
+		self: ExtendedMarket,
+		baseline_position_size: FixedI128,
+	) -> ExtendedMarket {
+		let mut extended_market = self;
+		extended_market.market.baseline_position_size = baseline_position_size;
+
+		extended_market
+	}
+
+	pub fn set_incremental_position_size(
+		self: ExtendedMarket,
+		incremental_position_size: FixedI128,
+	) -> ExtendedMarket {
+		let mut extended_market = self;
+		extended_market.market.incremental_position_size = incremental_position_size;
+
+		extended_market
+	}
 }
 
 pub fn eth_usdc() -> ExtendedMarket {
@@ -96,8 +147,7 @@ pub fn eth_usdc() -> ExtendedMarket {
 			version: 1,
 			asset: 4543560,
 			asset_collateral: 1431520323,
-			is_tradable: true,
-			is_archived: false,
+			state: MarketState::Active,
 			ttl: 3600,
 			tick_size: 1.into(),
 			tick_precision: 1,
@@ -113,6 +163,7 @@ pub fn eth_usdc() -> ExtendedMarket {
 			incremental_position_size: 100.into(),
 			baseline_position_size: 1000.into(),
 			maximum_position_size: 10000.into(),
+			max_open_interest: None,
 		},
 		metadata_url: metadata_url.clone(),
 	}
@@ -132,8 +183,7 @@ pub fn link_usdc() -> ExtendedMarket {
 			version: 1,
 			asset: 1279872587,
 			asset_collateral: 1431520323,
-			is_tradable: false,
-			is_archived: false,
+			state: MarketState::Pending,
 			ttl: 360,
 			tick_size: 1.into(),
 			tick_precision: 1,
@@ -149,6 +199,7 @@ pub fn link_usdc() -> ExtendedMarket {
 			incremental_position_size: 1.into(),
 			baseline_position_size: 1.into(),
 			maximum_position_size: 1.into(),
+			max_open_interest: None,
 		},
 		metadata_url: metadata_url.clone(),
 	}
@@ -168,8 +219,7 @@ pub fn btc_usdc() -> ExtendedMarket {
 			version: 1,
 			asset: 4346947,
 			asset_collateral: 1431520323,
-			is_tradable: true,
-			is_archived: false,
+			state: MarketState::Active,
 			ttl: 3600,
 			tick_size: 1.into(),
 			tick_precision: 1,
@@ -185,6 +235,7 @@ pub fn btc_usdc() -> ExtendedMarket {
 			incremental_position_size: 1.into(),
 			baseline_position_size: 1.into(),
 			maximum_position_size: 250.into(),
+			max_open_interest: None,
 		},
 		metadata_url: metadata_url.clone(),
 	}
@@ -1,10 +1,13 @@
 use crate::{
 	traits::{FieldElementExt, Hashable},
-	types::{BaseFee, Direction, HashType, Order, OrderType, Side, SignatureInfo, TimeInForce},
+	types::{
+		BaseFee, CancelAllRequest, CancelRequest, Direction, HashType, Order, OrderType, Side,
+		SignatureInfo, TimeInForce,
+	},
 };
 use frame_support::dispatch::Vec;
 use primitive_types::U256;
-use sp_arithmetic::fixed_point::FixedI128;
+use sp_arithmetic::{fixed_point::FixedI128, traits::Zero};
 use starknet_crypto::{sign, FieldElement};
 
 use super::btc_usdc;
@@ -59,6 +62,10 @@ impl Order {
 				sig_s: U256::zero(),
 			},
 			timestamp: 1699940278000,
+			trigger_price: FixedI128::zero(),
+			reduce_only: false,
+			is_isolated: false,
+			expiry: 0,
 		}
 	}
 
@@ -114,6 +121,22 @@ impl Order {
 		Order { timestamp, ..self }
 	}
 
+	pub fn set_trigger_price(self: Order, trigger_price: FixedI128) -> Order {
+		Order { trigger_price, ..self }
+	}
+
+	pub fn set_reduce_only(self: Order, reduce_only: bool) -> Order {
+		Order { reduce_only, ..self }
+	}
+
+	pub fn set_is_isolated(self: Order, is_isolated: bool) -> Order {
+		Order { is_isolated, ..self }
+	}
+
+	pub fn set_expiry(self: Order, expiry: u64) -> Order {
+		Order { expiry, ..self }
+	}
+
 	pub fn sign_order(self: Order, private_key: FieldElement) -> Order {
 		let order_hash = self.hash(&self.signature_info.hash_type).unwrap();
 		let signature = sign(&private_key, &order_hash, &FieldElement::ONE).unwrap();
@@ -139,3 +162,51 @@ impl Order {
 		Order { signature_info, ..self }
 	}
 }
+
+impl CancelRequest {
+	pub fn new(account_id: U256, order_id: U256) -> CancelRequest {
+		CancelRequest {
+			account_id,
+			order_id,
+			timestamp: 1699940278000,
+			sig_r: U256::zero(),
+			sig_s: U256::zero(),
+			hash_type: HashType::Pedersen,
+		}
+	}
+
+	pub fn sign_request(self: CancelRequest, private_key: FieldElement) -> CancelRequest {
+		let request_hash = self.hash(&self.hash_type).unwrap();
+		let signature = sign(&private_key, &request_hash, &FieldElement::ONE).unwrap();
+
+		let sig_r = signature.r.to_u256();
+		let sig_s = signature.s.to_u256();
+		CancelRequest { sig_r, sig_s, ..self }
+	}
+}
+
+impl CancelAllRequest {
+	pub fn new(account_id: U256, market_id: Option<u128>) -> CancelAllRequest {
+		CancelAllRequest {
+			account_id,
+			market_id,
+			timestamp: 1699940278000,
+			sig_r: U256::zero(),
+			sig_s: U256::zero(),
+			hash_type: HashType::Pedersen,
+		}
+	}
+
+	pub fn set_timestamp(self: CancelAllRequest, timestamp: u64) -> CancelAllRequest {
+		CancelAllRequest { timestamp, ..self }
+	}
+
+	pub fn sign_request(self: CancelAllRequest, private_key: FieldElement) -> CancelAllRequest {
+		let request_hash = self.hash(&self.hash_type).unwrap();
+		let signature = sign(&private_key, &request_hash, &FieldElement::ONE).unwrap();
+
+		let sig_r = signature.r.to_u256();
+		let sig_s = signature.s.to_u256();
+		CancelAllRequest { sig_r, sig_s, ..self }
+	}
+}
@@ -1,8 +1,12 @@
 use crate::{
 	ecdsa_verify,
-	helpers::{calc_30day_volume, compute_hash_on_elements, get_day_diff, shift_and_recompute},
-	traits::{FixedI128Ext, Hashable, U256Ext},
-	types::{HashType, Order, Side},
+	helpers::{
+		calc_30day_volume, compute_hash_on_elements, get_day_diff, get_initial_margin_fraction,
+		get_max_leverage, shift_and_recompute,
+	},
+	test_helpers::market_helper::btc_usdc,
+	traits::{FeltSerializedArrayExt, FixedI128Ext, Hashable, U256Ext},
+	types::{HashType, MarketUpdatedV2, Order, OrderType, Side, TimeInForce},
 	Signature,
 };
 use codec::alloc::vec;
@@ -172,6 +176,205 @@ fn test_order_signature() {
 	assert_eq!(verification, true);
 }
 
+#[test]
+fn test_conditional_order_hash() {
+	let order = Order::new(U256::from(201), U256::from(0))
+		.set_order_type(OrderType::StopMarket)
+		.set_trigger_price(95.into());
+	let order_hash = order.hash(&HashType::Pedersen).unwrap();
+
+	// Trigger price is appended to the elements of the order
+	let elements: Vec<FieldElement> = vec![
+		FieldElement::from(0_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(201_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(1_u8),
+		string_to_felt("STOP_MARKET").unwrap(),
+		string_to_felt("LONG").unwrap(),
+		string_to_felt("BUY").unwrap(),
+		FieldElement::from(100000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(100000000000000000_u128),
+		FieldElement::from(0_u8),
+		string_to_felt("GTC").unwrap(),
+		FieldElement::from(1699940278000_u64),
+		FieldElement::from(95000000000000000000_u128),
+	];
+	assert_eq!(order_hash, compute_hash_on_elements(&elements));
+
+	// Changing the trigger price changes the hash of the order
+	let updated_order_hash = order.set_trigger_price(90.into()).hash(&HashType::Pedersen).unwrap();
+	assert_ne!(order_hash, updated_order_hash);
+
+	// Trigger price is not part of the hash of other orders
+	let limit_order_hash = Order::new(U256::from(201), U256::from(0))
+		.set_trigger_price(95.into())
+		.hash(&HashType::Pedersen)
+		.unwrap();
+	assert_eq!(
+		limit_order_hash,
+		Order::new(U256::from(201), U256::from(0)).hash(&HashType::Pedersen).unwrap()
+	);
+}
+
+#[test]
+fn test_reduce_only_order_hash() {
+	let order = Order::new(U256::from(201), U256::from(0))
+		.set_side(Side::Sell)
+		.set_reduce_only(true);
+	let order_hash = order.hash(&HashType::Pedersen).unwrap();
+
+	// Reduce only flag is appended to the elements of the order
+	let elements: Vec<FieldElement> = vec![
+		FieldElement::from(0_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(201_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(1_u8),
+		string_to_felt("LIMIT").unwrap(),
+		string_to_felt("LONG").unwrap(),
+		string_to_felt("SELL").unwrap(),
+		FieldElement::from(100000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(100000000000000000_u128),
+		FieldElement::from(0_u8),
+		string_to_felt("GTC").unwrap(),
+		FieldElement::from(1699940278000_u64),
+		FieldElement::from(1_u8),
+	];
+	assert_eq!(order_hash, compute_hash_on_elements(&elements));
+
+	// Clearing the flag changes the hash of the order
+	let updated_order_hash =
+		order.clone().set_reduce_only(false).hash(&HashType::Pedersen).unwrap();
+	assert_ne!(order_hash, updated_order_hash);
+
+	// Isolated flag is encoded differently from the reduce only flag
+	let isolated_order_hash = order
+		.clone()
+		.set_reduce_only(false)
+		.set_is_isolated(true)
+		.hash(&HashType::Pedersen)
+		.unwrap();
+	assert_ne!(order_hash, isolated_order_hash);
+	assert_ne!(updated_order_hash, isolated_order_hash);
+}
+
+#[test]
+fn test_gtt_order_hash() {
+	let order = Order::new(U256::from(201), U256::from(0))
+		.set_time_in_force(TimeInForce::GTT)
+		.set_expiry(1699943878000);
+	let order_hash = order.hash(&HashType::Pedersen).unwrap();
+
+	// Expiry is appended to the elements of the order
+	let elements: Vec<FieldElement> = vec![
+		FieldElement::from(0_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(201_u8),
+		FieldElement::from(0_u8),
+		FieldElement::from(1_u8),
+		string_to_felt("LIMIT").unwrap(),
+		string_to_felt("LONG").unwrap(),
+		string_to_felt("BUY").unwrap(),
+		FieldElement::from(100000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(1000000000000000000_u128),
+		FieldElement::from(100000000000000000_u128),
+		FieldElement::from(0_u8),
+		string_to_felt("GTT").unwrap(),
+		FieldElement::from(1699940278000_u64),
+		FieldElement::from(1699943878000_u64),
+	];
+	assert_eq!(order_hash, compute_hash_on_elements(&elements));
+
+	// Changing the expiry changes the hash of the order
+	let updated_order_hash =
+		order.clone().set_expiry(1699947478000).hash(&HashType::Pedersen).unwrap();
+	assert_ne!(order_hash, updated_order_hash);
+
+	// Expiry is ignored for other orders
+	let gtc_order = order.clone().set_time_in_force(TimeInForce::GTC);
+	assert_eq!(
+		gtc_order.hash(&HashType::Pedersen).unwrap(),
+		gtc_order.clone().set_expiry(0).hash(&HashType::Pedersen).unwrap()
+	);
+}
+
+#[test]
+fn test_market_updated_open_interest_cap_serialization() {
+	let mut market = btc_usdc().market;
+	let event = MarketUpdatedV2 {
+		event_index: 1,
+		id: market.id,
+		market: market.clone(),
+		metadata_url: vec![5_u8, 6_u8].try_into().unwrap(),
+		fee_split_details: (U256::from(1), FixedI128::from_inner(0)),
+		block_number: 1337,
+	};
+
+	// Moving the first byte of the metadata url into the cap changes the elements
+	market.max_open_interest = Some(FixedI128::from_inner(5));
+	let moved_event =
+		MarketUpdatedV2 { market, metadata_url: vec![6_u8].try_into().unwrap(), ..event.clone() };
+
+	let mut elements: Vec<FieldElement> = Vec::new();
+	elements.try_append_market_updated_v2_event(&event).unwrap();
+	let mut moved_elements: Vec<FieldElement> = Vec::new();
+	moved_elements.try_append_market_updated_v2_event(&moved_event).unwrap();
+	assert_ne!(elements, moved_elements);
+}
+
+#[test]
+fn test_initial_margin_fraction_tiers() {
+	// Market without size tiers is allowed the currently allowed leverage for any size
+	let market = btc_usdc().market;
+	assert_eq!(get_max_leverage(&market, 250.into()), 8.into());
+
+	// Baseline position size is 100 and every 50 beyond it adds 0.02 to the fraction
+	let market = btc_usdc()
+		.set_initial_margin_fraction(FixedI128::from_inner(100000000000000000))
+		.set_incremental_initial_margin_fraction(FixedI128::from_inner(20000000000000000))
+		.set_baseline_position_size(100.into())
+		.set_incremental_position_size(50.into())
+		.market;
+
+	// Positions up to the baseline size require the initial margin fraction
+	assert_eq!(
+		get_initial_margin_fraction(&market, 50.into()),
+		FixedI128::from_inner(100000000000000000)
+	);
+	assert_eq!(
+		get_initial_margin_fraction(&market, 100.into()),
+		FixedI128::from_inner(100000000000000000)
+	);
+	assert_eq!(get_max_leverage(&market, 100.into()), 8.into());
+
+	// A part of an increment counts as a whole increment
+	assert_eq!(
+		get_initial_margin_fraction(&market, 101.into()),
+		FixedI128::from_inner(120000000000000000)
+	);
+	assert_eq!(
+		get_initial_margin_fraction(&market, 150.into()),
+		FixedI128::from_inner(120000000000000000)
+	);
+	assert_eq!(
+		get_initial_margin_fraction(&market, 151.into()),
+		FixedI128::from_inner(140000000000000000)
+	);
+
+	// Maximum leverage is the inverse of the fraction once it is below the allowed leverage
+	assert_eq!(
+		get_initial_margin_fraction(&market, 250.into()),
+		FixedI128::from_inner(160000000000000000)
+	);
+	assert_eq!(get_max_leverage(&market, 250.into()), FixedI128::from_inner(6250000000000000000));
+}
+
 #[test]
 fn test_round_to_precision_1() {
 	// 4.99, 1
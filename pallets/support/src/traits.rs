@@ -1,11 +1,12 @@
 use crate::types::{
-	ABRDetails, AccountInfo, Asset, AssetAddress, AssetRemoved, AssetUpdated, BalanceChangeReason,
-	BaseFeeAggregate, Direction, ExtendedAsset, ExtendedMarket, FeeRates, FeeShareDetails,
-	ForceClosureFlag, FundModifyType, HashType, InsuranceFundDeposited, MarginInfo, Market,
-	MarketRemoved, MarketUpdated, MarketUpdatedV2, MasterAccountLevelChanged, Order, OrderSide,
-	Position, PositionExtended, QuorumSet, ReferralDetails, ReferralDetailsAdded, Setting,
-	SettingsAdded, Side, SignerAdded, SignerRemoved, TradingAccount, TradingAccountMinimal,
-	UniversalEvent, UserDeposit, VolumeType,
+	ABRDetails, AccountInfo, AccruedFees, Asset, AssetAddress, AssetRemoved, AssetUpdated,
+	BalanceChangeReason, BaseFeeAggregate, Direction, ExtendedAsset, ExtendedMarket, FeeOverride,
+	FeeOverrideUpdated, FeeRates, FeeRecipients, FeeShareDetails, ForceClosureFlag, FundModifyType,
+	HashType, InsuranceFundDeposited, MarginInfo, Market, MarketRemoved, MarketState,
+	MarketUpdated, MarketUpdatedV2, MarketUpdatedV3, MasterAccountLevelChanged, Order, OrderSide,
+	OrderState, Position, PositionExtended, QuorumSet, ReferralDetails, ReferralDetailsAdded,
+	Setting, SettingsAdded, Side, SignerAdded, SignerRemoved, TradingAccount,
+	TradingAccountMinimal, UniversalEvent, UserDeposit, VolumeType,
 };
 use frame_support::dispatch::Vec;
 use primitive_types::U256;
@@ -48,9 +49,17 @@ pub trait TradingAccountInterface {
 		new_position_maintanence_requirement: FixedI128,
 		new_position_margin: FixedI128,
 	) -> (bool, FixedI128, FixedI128, FixedI128, FixedI128, FixedI128);
+	fn get_isolated_margin_info(
+		account_id: U256,
+		market_id: u128,
+		direction: Direction,
+		new_position_maintanence_requirement: FixedI128,
+		new_position_margin: FixedI128,
+	) -> (bool, FixedI128, FixedI128);
 	fn get_account_list(start_index: u128, end_index: u128) -> Vec<U256>;
 	fn add_deferred_balance(account_id: U256, collateral_id: u128) -> DispatchResult;
 	fn get_accounts_count() -> u128;
+	fn has_balances(asset_id: u128) -> bool;
 	fn get_collaterals_of_user(account_id: U256) -> Vec<u128>;
 	fn get_amount_to_withdraw(account_id: U256, collateral_id: u128) -> FixedI128;
 	fn update_and_get_cumulative_volume(
@@ -98,6 +107,7 @@ pub trait TradingAccountInterface {
 		market_id: u128,
 		amount: FixedI128,
 		fee_share_amount: FixedI128,
+		rebate_amount: FixedI128,
 	);
 	fn handle_insurance_fund_update(
 		collateral_id: u128,
@@ -110,12 +120,28 @@ pub trait TradingAccountInterface {
 		insurance_fund: U256,
 		fee_split: FixedI128,
 	);
+	fn update_fee_recipients_internal(market_id: u128, fee_recipients: FeeRecipients) -> bool;
 	fn get_fee_split_details(market_id: u128) -> (U256, FixedI128);
+	fn get_insurance_fund_balance(insurance_fund: U256, collateral_id: u128) -> FixedI128;
 	fn update_insurance_fund_balance_internal(
 		insurance_fund: U256,
 		collateral_id: u128,
 		amount: FixedI128,
 	);
+	fn transfer_collateral(
+		account_id: U256,
+		collateral_id: u128,
+		amount: FixedI128,
+		reason: BalanceChangeReason,
+	);
+	fn deposit_to_insurance_fund(
+		insurance_fund: U256,
+		collateral_id: u128,
+		amount: FixedI128,
+		reason: BalanceChangeReason,
+	);
+	fn get_unallocated_revenue(collateral_id: u128) -> FixedI128;
+	fn update_unallocated_revenue(collateral_id: u128, amount: FixedI128);
 }
 
 pub trait TradingInterface {
@@ -128,6 +154,8 @@ pub trait TradingInterface {
 		force_closure_flag: ForceClosureFlag,
 		amount_to_be_sold: FixedI128,
 	);
+	fn is_isolated_position(account_id: U256, market_id: u128, direction: Direction) -> bool;
+	fn set_flag_for_isolated_force_order(account_id: U256, market_id: u128, direction: Direction);
 	fn get_deleveragable_amount(account_id: U256, collateral_id: u128) -> FixedI128;
 	fn get_account_margin_info(account_id: U256, collateral_id: u128) -> MarginInfo;
 	fn get_account_info(account_id: U256, collateral_id: u128) -> AccountInfo;
@@ -136,8 +164,12 @@ pub trait TradingInterface {
 	fn get_fee(account_id: U256, market_id: u128) -> (FeeRates, u64);
 	fn get_withdrawable_amount(account_id: U256, collateral_id: u128) -> FixedI128;
 	fn get_remaining_trading_cleanup_calls() -> u64;
+	fn get_order_state(order_id: U256) -> OrderState;
+	fn get_batch_status(batch_id: U256) -> bool;
+	fn get_accrued_fees(collateral_id: u128) -> AccruedFees;
 	fn get_fee_rate(
 		account_id: U256,
+		market_id: u128,
 		base_fees: &BaseFeeAggregate,
 		side: Side,
 		order_side: OrderSide,
@@ -150,12 +182,14 @@ pub trait TradingInterface {
 		volume: FixedI128,
 	) -> FeeRates;
 	fn get_no_of_delisted_market_positions(market_id: u128) -> u32;
+	fn get_open_interest(market_id: u128) -> FixedI128;
 }
 
 pub trait AssetInterface {
 	fn update_asset_internal(asset: ExtendedAsset) -> DispatchResult;
 	fn add_asset_internal(asset: ExtendedAsset) -> DispatchResult;
-	fn remove_asset_internal(id: u128);
+	fn remove_asset_internal(id: u128) -> DispatchResult;
+	fn archive_asset_internal(id: u128) -> DispatchResult;
 	fn get_default_collateral() -> u128;
 	fn get_asset(id: u128) -> Option<Asset>;
 }
@@ -180,10 +214,13 @@ pub trait MarketInterface {
 	fn get_market(id: u128) -> Option<Market>;
 	fn add_market_internal(extended_market: ExtendedMarket) -> DispatchResult;
 	fn update_market_internal(extended_market: ExtendedMarket) -> DispatchResult;
-	fn remove_market_internal(id: u128);
+	fn update_market_with_notice_internal(extended_market: ExtendedMarket) -> DispatchResult;
+	fn remove_market_internal(id: u128) -> DispatchResult;
+	fn archive_market_internal(id: u128) -> DispatchResult;
 	fn validate_market_details(market: &Market) -> DispatchResult;
 	fn get_all_markets() -> Vec<u128>;
-	fn get_all_markets_by_state(is_tradable: bool, is_archived: bool) -> Vec<u128>;
+	fn get_all_markets_by_state(states: &[MarketState]) -> Vec<u128>;
+	fn is_asset_used(asset_id: u128) -> bool;
 }
 
 pub trait PricesInterface {
@@ -208,6 +245,7 @@ pub trait PricesInterface {
 	fn get_remaining_prices_cleanup_calls() -> u64;
 	fn set_mark_price_for_ads(market_id: u128) -> DispatchResult;
 	fn get_mark_price_for_ads(market_id: u128) -> Option<FixedI128>;
+	fn get_twap(market_id: u128, start_timestamp: u64, end_timestamp: u64) -> Option<FixedI128>;
 }
 
 pub trait FixedI128Ext {
@@ -237,6 +275,13 @@ pub trait TradingFeesInterface {
 	) -> DispatchResult;
 	fn get_fee_share(account_level: u8, collateral_id: u128, volume: FixedI128) -> FixedI128;
 	fn get_all_fee_shares(collateral_id: u128) -> Vec<Vec<FeeShareDetails>>;
+	fn set_fee_override_internal(
+		account_id: U256,
+		market_id: u128,
+		fee_override: FeeOverride,
+	) -> DispatchResult;
+	fn remove_fee_override_internal(account_id: U256, market_id: u128);
+	fn get_fee_override(account_id: U256, market_id: u128) -> Option<FeeOverride>;
 }
 
 // This trait needs to be implemented by every type that can be hashed (pedersen or poseidon) and
@@ -263,6 +308,7 @@ pub trait FeltSerializedArrayExt {
 	fn try_append_fixedi128(&mut self, fixed_value: FixedI128) -> Result<(), FromByteSliceError>;
 	fn try_append_asset(&mut self, asset: &Asset) -> Result<(), FromByteSliceError>;
 	fn try_append_market(&mut self, market: &Market) -> Result<(), FromByteSliceError>;
+	fn try_append_market_with_state(&mut self, market: &Market) -> Result<(), FromByteSliceError>;
 	fn try_append_trading_account(
 		&mut self,
 		trading_account: &TradingAccountMinimal,
@@ -319,6 +365,14 @@ pub trait FeltSerializedArrayExt {
 		&mut self,
 		insurance_fund_deposited: &InsuranceFundDeposited,
 	) -> Result<(), FromByteSliceError>;
+	fn try_append_fee_override_updated(
+		&mut self,
+		fee_override_updated: &FeeOverrideUpdated,
+	) -> Result<(), FromByteSliceError>;
+	fn try_append_market_updated_v3_event(
+		&mut self,
+		market_updated_v3_event: &MarketUpdatedV3,
+	) -> Result<(), FromByteSliceError>;
 	fn try_append_universal_event_array(
 		&mut self,
 		universal_event_array: &Vec<UniversalEvent>,
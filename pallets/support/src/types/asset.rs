@@ -1,6 +1,8 @@
-use codec::{Decode, Encode};
+use codec::{alloc::string::String, Decode, Encode};
+use frame_support::dispatch::Vec;
 use primitive_types::U256;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
 
 #[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
@@ -25,3 +27,50 @@ pub struct ExtendedAsset {
 	pub asset_addresses: BoundedVec<AssetAddress, ConstU32<256>>,
 	pub metadata_url: BoundedVec<u8, ConstU32<256>>,
 }
+
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct AssetAddressInfo {
+	pub chain: U256,
+	pub address: U256,
+}
+
+// Asset details returned by the RPC, ids are U256 instead of u128
+// so that ZKXNode can handle them correctly
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct AssetInfo {
+	pub id: U256,
+	pub version: u16,
+	pub short_name: U256,
+	pub is_tradable: bool,
+	pub is_collateral: bool,
+	pub decimals: u8,
+	pub asset_addresses: Vec<AssetAddressInfo>,
+	pub metadata_url: String,
+}
+
+impl From<ExtendedAsset> for AssetInfo {
+	fn from(extended_asset: ExtendedAsset) -> AssetInfo {
+		let asset = extended_asset.asset;
+		AssetInfo {
+			id: U256::from(asset.id),
+			version: asset.version,
+			short_name: asset.short_name,
+			is_tradable: asset.is_tradable,
+			is_collateral: asset.is_collateral,
+			decimals: asset.decimals,
+			asset_addresses: extended_asset
+				.asset_addresses
+				.iter()
+				.map(|asset_address| AssetAddressInfo {
+					chain: U256::from(asset_address.chain),
+					address: asset_address.address,
+				})
+				.collect(),
+			metadata_url: String::from_utf8_lossy(&extended_asset.metadata_url).into_owned(),
+		}
+	}
+}
@@ -1,17 +1,56 @@
-use codec::{Decode, Encode};
+use codec::{alloc::string::String, Decode, Encode};
+use primitive_types::U256;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_arithmetic::fixed_point::FixedI128;
-use sp_runtime::traits::ConstU32;
-use sp_runtime::{BoundedVec, RuntimeDebug};
+use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
 
-#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+// Lifecycle of a market
+// Pending -> Active | PostOnly | Archived
+// Active | PostOnly | ReduceOnly | Halted -> each other | Delisting
+// Delisting -> Settled -> Archived
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Default,
+	Deserialize,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	Serialize,
+	TypeInfo,
+)]
+pub enum MarketState {
+	/// Listed, but not open for trading yet
+	#[default]
+	Pending,
+	/// Orders are matched normally
+	Active,
+	/// Orders can be placed in the order book, but they are not matched
+	PostOnly,
+	/// Only orders reducing a position are matched
+	ReduceOnly,
+	/// Trading is suspended, positions are kept as they are
+	Halted,
+	/// Open positions are being closed at the delisting price
+	Delisting,
+	/// All the positions are closed
+	Settled,
+	/// Removed from the market map, kept only for its history
+	Archived,
+}
+
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
 pub struct Market {
 	pub id: u128,
 	pub version: u16,
 	pub asset: u128,
 	pub asset_collateral: u128,
-	pub is_tradable: bool,
-	pub is_archived: bool,
+	pub state: MarketState,
 	pub ttl: u32,
 	pub tick_size: FixedI128,
 	pub tick_precision: u8,
@@ -27,6 +66,8 @@ pub struct Market {
 	pub incremental_position_size: FixedI128,
 	pub baseline_position_size: FixedI128,
 	pub maximum_position_size: FixedI128,
+	// Cap on the open interest of the market, None if there is no cap
+	pub max_open_interest: Option<FixedI128>,
 }
 
 #[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
@@ -34,3 +75,178 @@ pub struct ExtendedMarket {
 	pub market: Market,
 	pub metadata_url: BoundedVec<u8, ConstU32<256>>,
 }
+
+// Market details returned by the RPC, ids are U256 instead of u128
+// so that ZKXNode can handle them correctly
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct MarketInfo {
+	pub id: U256,
+	pub version: u16,
+	pub asset: U256,
+	pub asset_collateral: U256,
+	pub state: MarketState,
+	pub ttl: u32,
+	pub tick_size: FixedI128,
+	pub tick_precision: u8,
+	pub step_size: FixedI128,
+	pub step_precision: u8,
+	pub minimum_order_size: FixedI128,
+	pub minimum_leverage: FixedI128,
+	pub maximum_leverage: FixedI128,
+	pub currently_allowed_leverage: FixedI128,
+	pub maintenance_margin_fraction: FixedI128,
+	pub initial_margin_fraction: FixedI128,
+	pub incremental_initial_margin_fraction: FixedI128,
+	pub incremental_position_size: FixedI128,
+	pub baseline_position_size: FixedI128,
+	pub maximum_position_size: FixedI128,
+	pub max_open_interest: Option<FixedI128>,
+	pub metadata_url: String,
+}
+
+// Risk parameters of a market waiting for their notice period to pass
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct ScheduledMarketChange {
+	pub market: Market,
+	// Timestamp in seconds from which the change is applied
+	pub effective_timestamp: u64,
+}
+
+impl MarketState {
+	// States in which positions are live and ABR is applied
+	pub const ABR_STATES: [MarketState; 3] =
+		[MarketState::Active, MarketState::PostOnly, MarketState::ReduceOnly];
+
+	// Whether orders can be matched in the market
+	pub fn is_tradable(&self) -> bool {
+		matches!(self, MarketState::Active | MarketState::ReduceOnly)
+	}
+
+	pub fn is_abr_applicable(&self) -> bool {
+		Self::ABR_STATES.contains(self)
+	}
+
+	// State carried by the is_tradable and is_archived flags of markets and events
+	// from before the market state, markets that are neither were being delisted
+	pub fn from_legacy_flags(is_tradable: bool, is_archived: bool) -> MarketState {
+		if is_archived {
+			MarketState::Archived
+		} else if is_tradable {
+			MarketState::Active
+		} else {
+			MarketState::Delisting
+		}
+	}
+
+	// Whether the state is the one derived from its own legacy flags, other states
+	// can't be told apart in the legacy encoding
+	pub fn is_legacy(&self) -> bool {
+		*self == Self::from_legacy_flags(self.is_tradable(), *self == MarketState::Archived)
+	}
+
+	pub fn can_transition_to(&self, next_state: MarketState) -> bool {
+		use MarketState::*;
+		if *self == next_state {
+			return true
+		}
+
+		match (self, next_state) {
+			(Pending, Active | PostOnly | Archived) => true,
+			(Active | PostOnly | ReduceOnly | Halted, Active | PostOnly | ReduceOnly | Halted) =>
+				true,
+			(Active | PostOnly | ReduceOnly | Halted, Delisting) => true,
+			(Delisting, Settled) => true,
+			(Settled, Archived) => true,
+			_ => false,
+		}
+	}
+}
+
+impl From<MarketState> for u8 {
+	fn from(value: MarketState) -> u8 {
+		match value {
+			MarketState::Pending => 0_u8,
+			MarketState::Active => 1_u8,
+			MarketState::PostOnly => 2_u8,
+			MarketState::ReduceOnly => 3_u8,
+			MarketState::Halted => 4_u8,
+			MarketState::Delisting => 5_u8,
+			MarketState::Settled => 6_u8,
+			MarketState::Archived => 7_u8,
+		}
+	}
+}
+
+impl Market {
+	// Whether the parameters used for margin and order validation are the same in both markets
+	pub fn has_same_risk_parameters(&self, other: &Market) -> bool {
+		self.tick_size == other.tick_size &&
+			self.tick_precision == other.tick_precision &&
+			self.step_size == other.step_size &&
+			self.step_precision == other.step_precision &&
+			self.minimum_order_size == other.minimum_order_size &&
+			self.minimum_leverage == other.minimum_leverage &&
+			self.maximum_leverage == other.maximum_leverage &&
+			self.currently_allowed_leverage == other.currently_allowed_leverage &&
+			self.maintenance_margin_fraction == other.maintenance_margin_fraction &&
+			self.initial_margin_fraction == other.initial_margin_fraction &&
+			self.incremental_initial_margin_fraction == other.incremental_initial_margin_fraction &&
+			self.incremental_position_size == other.incremental_position_size &&
+			self.baseline_position_size == other.baseline_position_size &&
+			self.maximum_position_size == other.maximum_position_size &&
+			self.max_open_interest == other.max_open_interest
+	}
+
+	// Copies the parameters used for margin and order validation from the other market
+	pub fn set_risk_parameters(&mut self, other: &Market) {
+		self.tick_size = other.tick_size;
+		self.tick_precision = other.tick_precision;
+		self.step_size = other.step_size;
+		self.step_precision = other.step_precision;
+		self.minimum_order_size = other.minimum_order_size;
+		self.minimum_leverage = other.minimum_leverage;
+		self.maximum_leverage = other.maximum_leverage;
+		self.currently_allowed_leverage = other.currently_allowed_leverage;
+		self.maintenance_margin_fraction = other.maintenance_margin_fraction;
+		self.initial_margin_fraction = other.initial_margin_fraction;
+		self.incremental_initial_margin_fraction = other.incremental_initial_margin_fraction;
+		self.incremental_position_size = other.incremental_position_size;
+		self.baseline_position_size = other.baseline_position_size;
+		self.maximum_position_size = other.maximum_position_size;
+		self.max_open_interest = other.max_open_interest;
+	}
+}
+
+impl From<ExtendedMarket> for MarketInfo {
+	fn from(extended_market: ExtendedMarket) -> MarketInfo {
+		let market = extended_market.market;
+		MarketInfo {
+			id: U256::from(market.id),
+			version: market.version,
+			asset: U256::from(market.asset),
+			asset_collateral: U256::from(market.asset_collateral),
+			state: market.state,
+			ttl: market.ttl,
+			tick_size: market.tick_size,
+			tick_precision: market.tick_precision,
+			step_size: market.step_size,
+			step_precision: market.step_precision,
+			minimum_order_size: market.minimum_order_size,
+			minimum_leverage: market.minimum_leverage,
+			maximum_leverage: market.maximum_leverage,
+			currently_allowed_leverage: market.currently_allowed_leverage,
+			maintenance_margin_fraction: market.maintenance_margin_fraction,
+			initial_margin_fraction: market.initial_margin_fraction,
+			incremental_initial_margin_fraction: market.incremental_initial_margin_fraction,
+			incremental_position_size: market.incremental_position_size,
+			baseline_position_size: market.baseline_position_size,
+			maximum_position_size: market.maximum_position_size,
+			max_open_interest: market.max_open_interest,
+			metadata_url: String::from_utf8_lossy(&extended_market.metadata_url).into_owned(),
+		}
+	}
+}
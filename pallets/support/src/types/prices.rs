@@ -1,5 +1,8 @@
 use codec::{Decode, Encode};
+use frame_support::dispatch::Vec;
+use primitive_types::U256;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_arithmetic::fixed_point::FixedI128;
 use sp_runtime::RuntimeDebug;
 
@@ -28,3 +31,124 @@ pub struct MultiplePrices {
 	pub index_price: FixedI128,
 	pub mark_price: FixedI128,
 }
+
+// Current prices of a market returned by the RPC, market id is U256 instead of u128
+// so that ZKXNode can handle it correctly
+#[derive(
+	Clone, Copy, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct CurrentPriceInfo {
+	pub market_id: U256,
+	pub timestamp: u64,
+	pub index_price: FixedI128,
+	pub mark_price: FixedI128,
+	// Prices older than the ttl of the market are read as zero by the margin engine
+	pub is_stale: bool,
+}
+
+#[derive(
+	Clone, Copy, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct HistoricalPriceInfo {
+	pub timestamp: u64,
+	pub index_price: FixedI128,
+	pub mark_price: FixedI128,
+}
+
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct HistoricalPricesPage {
+	pub prices: Vec<HistoricalPriceInfo>,
+	// Timestamp from which the next page starts, None if the range is exhausted
+	pub next_timestamp: Option<u64>,
+}
+
+// Intervals of the candles aggregated from the historical prices
+#[derive(
+	Clone, Copy, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub enum CandleInterval {
+	#[default]
+	OneMinute,
+	OneHour,
+	OneDay,
+}
+
+impl CandleInterval {
+	pub const ALL: [CandleInterval; 3] =
+		[CandleInterval::OneMinute, CandleInterval::OneHour, CandleInterval::OneDay];
+
+	// Length of the interval in seconds
+	pub fn duration(&self) -> u64 {
+		match self {
+			CandleInterval::OneMinute => 60,
+			CandleInterval::OneHour => 3600,
+			CandleInterval::OneDay => 86400,
+		}
+	}
+
+	// Timestamp at which the candle containing the given timestamp starts
+	pub fn candle_start(&self, timestamp: u64) -> u64 {
+		timestamp - timestamp % self.duration()
+	}
+}
+
+#[derive(
+	Clone, Copy, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct OHLC {
+	pub open: FixedI128,
+	pub high: FixedI128,
+	pub low: FixedI128,
+	pub close: FixedI128,
+}
+
+impl OHLC {
+	pub fn new(price: FixedI128) -> OHLC {
+		OHLC { open: price, high: price, low: price, close: price }
+	}
+}
+
+#[derive(
+	Clone, Copy, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct Candle {
+	// Start of the interval covered by the candle
+	pub timestamp: u64,
+	// Timestamps of the first and last prices aggregated in the candle
+	pub open_timestamp: u64,
+	pub close_timestamp: u64,
+	pub index_price: OHLC,
+	pub mark_price: OHLC,
+}
+
+impl Candle {
+	pub fn new(timestamp: u64, price_timestamp: u64, price: &HistoricalPrice) -> Candle {
+		Candle {
+			timestamp,
+			open_timestamp: price_timestamp,
+			close_timestamp: price_timestamp,
+			index_price: OHLC::new(price.index_price),
+			mark_price: OHLC::new(price.mark_price),
+		}
+	}
+
+	// Prices can be inserted out of order, so open and close follow the price timestamps
+	pub fn add_price(&mut self, price_timestamp: u64, price: &HistoricalPrice) {
+		for (ohlc, value) in
+			[(&mut self.index_price, price.index_price), (&mut self.mark_price, price.mark_price)]
+		{
+			ohlc.high = ohlc.high.max(value);
+			ohlc.low = ohlc.low.min(value);
+			if price_timestamp < self.open_timestamp {
+				ohlc.open = value;
+			}
+			if price_timestamp >= self.close_timestamp {
+				ohlc.close = value;
+			}
+		}
+		self.open_timestamp = self.open_timestamp.min(price_timestamp);
+		self.close_timestamp = self.close_timestamp.max(price_timestamp);
+	}
+}
@@ -2,8 +2,8 @@ use crate::{
 	traits::{FeltSerializedArrayExt, U256Ext},
 	types::{
 		common::convert_to_u128_pair, Asset, AssetRemoved, AssetUpdated, Market, MarketRemoved,
-		MarketUpdated, MarketUpdatedV2, ReferralDetailsAdded, Setting, SettingsAdded, SignerAdded,
-		SignerRemoved, TradingAccountMinimal, UniversalEvent, UserDeposit,
+		MarketState, MarketUpdated, MarketUpdatedV2, ReferralDetailsAdded, Setting, SettingsAdded,
+		SignerAdded, SignerRemoved, TradingAccountMinimal, UniversalEvent, UserDeposit,
 	},
 };
 use frame_support::dispatch::Vec;
@@ -12,7 +12,10 @@ use sp_arithmetic::fixed_point::FixedI128;
 use sp_runtime::{traits::ConstU32, BoundedVec};
 use starknet_ff::{FieldElement, FromByteSliceError};
 
-use super::{AssetAddress, InsuranceFundDeposited, MasterAccountLevelChanged, QuorumSet};
+use super::{
+	AssetAddress, FeeOverrideUpdated, InsuranceFundDeposited, MarketUpdatedV3,
+	MasterAccountLevelChanged, QuorumSet,
+};
 
 impl FeltSerializedArrayExt for Vec<FieldElement> {
 	fn append_bounded_vec_u8(&mut self, vec: &BoundedVec<u8, ConstU32<256>>) {
@@ -89,25 +92,18 @@ impl FeltSerializedArrayExt for Vec<FieldElement> {
 		self.push(FieldElement::from(market.id));
 		self.push(FieldElement::from(market.asset));
 		self.push(FieldElement::from(market.asset_collateral));
-		self.append_bool(market.is_tradable);
-		self.append_bool(market.is_archived);
-		self.push(FieldElement::from(market.ttl));
-		self.try_append_fixedi128(market.tick_size)?;
-		self.push(FieldElement::from(market.tick_precision));
-		self.try_append_fixedi128(market.step_size)?;
-		self.push(FieldElement::from(market.step_precision));
-		self.try_append_fixedi128(market.minimum_order_size)?;
-		self.try_append_fixedi128(market.minimum_leverage)?;
-		self.try_append_fixedi128(market.maximum_leverage)?;
-		self.try_append_fixedi128(market.currently_allowed_leverage)?;
-		self.try_append_fixedi128(market.maintenance_margin_fraction)?;
-		self.try_append_fixedi128(market.initial_margin_fraction)?;
-		self.try_append_fixedi128(market.incremental_initial_margin_fraction)?;
-		self.try_append_fixedi128(market.incremental_position_size)?;
-		self.try_append_fixedi128(market.baseline_position_size)?;
-		self.try_append_fixedi128(market.maximum_position_size)?;
+		// Events before MarketUpdatedV3 carry the state as the is_tradable and is_archived flags
+		self.append_bool(market.state.is_tradable());
+		self.append_bool(market.state == MarketState::Archived);
+		try_append_market_parameters(self, market)
+	}
 
-		Ok(())
+	fn try_append_market_with_state(&mut self, market: &Market) -> Result<(), FromByteSliceError> {
+		self.push(FieldElement::from(market.id));
+		self.push(FieldElement::from(market.asset));
+		self.push(FieldElement::from(market.asset_collateral));
+		self.push(FieldElement::from(u8::from(market.state)));
+		try_append_market_parameters(self, market)
 	}
 
 	fn try_append_trading_account(
@@ -316,6 +312,45 @@ impl FeltSerializedArrayExt for Vec<FieldElement> {
 		Ok(())
 	}
 
+	fn try_append_fee_override_updated(
+		&mut self,
+		fee_override_updated: &FeeOverrideUpdated,
+	) -> Result<(), FromByteSliceError> {
+		// enum prefix
+		self.push(FieldElement::from(13_u8));
+		self.push(FieldElement::from(fee_override_updated.event_index));
+		self.try_append_u256(fee_override_updated.account_id)?;
+		self.push(FieldElement::from(fee_override_updated.market_id));
+		self.try_append_fixedi128(fee_override_updated.maker_fee)?;
+		self.try_append_fixedi128(fee_override_updated.taker_fee)?;
+		self.push(FieldElement::from(fee_override_updated.expires_at));
+		self.push(FieldElement::from(fee_override_updated.block_number));
+
+		Ok(())
+	}
+
+	fn try_append_market_updated_v3_event(
+		&mut self,
+		market_updated_v3_event: &MarketUpdatedV3,
+	) -> Result<(), FromByteSliceError> {
+		// enum prefix
+		self.push(FieldElement::from(14_u8));
+		self.push(FieldElement::from(market_updated_v3_event.event_index));
+		self.push(FieldElement::from(market_updated_v3_event.id));
+		self.try_append_market_with_state(&market_updated_v3_event.market)?;
+		self.append_bounded_vec_u8(&market_updated_v3_event.metadata_url);
+		self.try_append_u256(market_updated_v3_event.fee_split_details.0)?;
+		self.try_append_fixedi128(market_updated_v3_event.fee_split_details.1)?;
+		self.push(FieldElement::from(market_updated_v3_event.fee_recipients.len() as u32));
+		for (recipient, fraction) in market_updated_v3_event.fee_recipients.iter() {
+			self.try_append_u256(*recipient)?;
+			self.try_append_fixedi128(*fraction)?;
+		}
+		self.push(FieldElement::from(market_updated_v3_event.block_number));
+
+		Ok(())
+	}
+
 	fn try_append_universal_event_array(
 		&mut self,
 		universal_event_array: &Vec<UniversalEvent>,
@@ -360,9 +395,45 @@ impl FeltSerializedArrayExt for Vec<FieldElement> {
 				},
 				UniversalEvent::InsuranceFundDeposited(insurance_fund_deposited) =>
 					self.try_append_insurance_fund_deposited(insurance_fund_deposited)?,
+				UniversalEvent::FeeOverrideUpdated(fee_override_updated) =>
+					self.try_append_fee_override_updated(fee_override_updated)?,
+				UniversalEvent::MarketUpdatedV3(market_updated_v3) =>
+					self.try_append_market_updated_v3_event(market_updated_v3)?,
 			}
 		}
 
 		Ok(())
 	}
 }
+
+fn try_append_market_parameters(
+	array: &mut Vec<FieldElement>,
+	market: &Market,
+) -> Result<(), FromByteSliceError> {
+	array.push(FieldElement::from(market.ttl));
+	array.try_append_fixedi128(market.tick_size)?;
+	array.push(FieldElement::from(market.tick_precision));
+	array.try_append_fixedi128(market.step_size)?;
+	array.push(FieldElement::from(market.step_precision));
+	array.try_append_fixedi128(market.minimum_order_size)?;
+	array.try_append_fixedi128(market.minimum_leverage)?;
+	array.try_append_fixedi128(market.maximum_leverage)?;
+	array.try_append_fixedi128(market.currently_allowed_leverage)?;
+	array.try_append_fixedi128(market.maintenance_margin_fraction)?;
+	array.try_append_fixedi128(market.initial_margin_fraction)?;
+	array.try_append_fixedi128(market.incremental_initial_margin_fraction)?;
+	array.try_append_fixedi128(market.incremental_position_size)?;
+	array.try_append_fixedi128(market.baseline_position_size)?;
+	array.try_append_fixedi128(market.maximum_position_size)?;
+	// Open interest cap is prefixed with whether it is set, so that it can't be confused with
+	// the metadata url that follows it
+	match market.max_open_interest {
+		Some(max_open_interest) => {
+			array.push(FieldElement::ONE);
+			array.try_append_fixedi128(max_open_interest)?;
+		},
+		None => array.push(FieldElement::ZERO),
+	}
+
+	Ok(())
+}
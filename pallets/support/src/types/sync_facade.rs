@@ -1,4 +1,4 @@
-use crate::types::{Asset, AssetAddress, Market, TradingAccountMinimal};
+use crate::types::{Asset, AssetAddress, FeeRecipients, Market, TradingAccountMinimal};
 use codec::{Decode, Encode};
 use primitive_types::U256;
 use scale_info::TypeInfo;
@@ -33,6 +33,8 @@ pub enum UniversalEvent {
 	MasterAccountLevelChanged(MasterAccountLevelChanged),
 	MarketUpdatedV2(MarketUpdatedV2),
 	InsuranceFundDeposited(InsuranceFundDeposited),
+	FeeOverrideUpdated(FeeOverrideUpdated),
+	MarketUpdatedV3(MarketUpdatedV3),
 }
 
 #[derive(Clone, Copy, Decode, Default, Encode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -134,6 +136,19 @@ pub struct InsuranceFundDeposited {
 	pub block_number: u64,
 }
 
+// market_id 0 applies the fee override to every market of the account
+// expires_at 0 removes the fee override
+#[derive(Clone, Decode, Encode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FeeOverrideUpdated {
+	pub event_index: u32,
+	pub account_id: U256,
+	pub market_id: u128,
+	pub maker_fee: FixedI128,
+	pub taker_fee: FixedI128,
+	pub expires_at: u64,
+	pub block_number: u64,
+}
+
 #[derive(Clone, Copy, Debug, Decode, Encode, Eq, Hash, PartialEq, TypeInfo)]
 pub enum SettingsType {
 	FeeSettings(FeeSettingsType),
@@ -173,3 +188,15 @@ pub struct MarketUpdatedV2 {
 	pub fee_split_details: (U256, FixedI128),
 	pub block_number: u64,
 }
+
+// An empty fee_recipients clears the fee split policy of the market
+#[derive(Clone, Decode, Encode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MarketUpdatedV3 {
+	pub event_index: u32,
+	pub id: u128,
+	pub market: Market,
+	pub metadata_url: BoundedVec<u8, ConstU32<256>>,
+	pub fee_split_details: (U256, FixedI128),
+	pub fee_recipients: FeeRecipients,
+	pub block_number: u64,
+}
@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use sp_arithmetic::fixed_point::FixedI128;
 use sp_runtime::RuntimeDebug;
 use starknet_crypto::poseidon_hash_many;
-use starknet_ff::FieldElement;
+use starknet_ff::{FieldElement, FromByteSliceError};
 
 #[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct SignatureInfo {
@@ -38,6 +38,35 @@ pub struct Order {
 	pub time_in_force: TimeInForce,
 	pub signature_info: SignatureInfo,
 	pub timestamp: u64,
+	// Mark price at which a conditional order becomes executable, 0 for other orders
+	pub trigger_price: FixedI128,
+	// Order can only decrease an existing position
+	pub reduce_only: bool,
+	// Position of the order has its own margin, separate from the other positions of the account
+	pub is_isolated: bool,
+	// Timestamp (in ms) after which a GTT order can no longer be executed, 0 for other orders
+	pub expiry: u64,
+}
+
+#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CancelRequest {
+	pub account_id: U256,
+	pub order_id: U256,
+	pub timestamp: u64,
+	pub sig_r: U256,
+	pub sig_s: U256,
+	pub hash_type: HashType,
+}
+
+#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CancelAllRequest {
+	pub account_id: U256,
+	// None cancels the orders of the account in all markets
+	pub market_id: Option<u128>,
+	pub timestamp: u64,
+	pub sig_r: U256,
+	pub sig_s: U256,
+	pub hash_type: HashType,
 }
 
 #[derive(Clone, Copy, Decode, Default, Encode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -79,6 +108,10 @@ pub enum OrderType {
 	Market,
 	Forced,
 	ADS,
+	StopMarket,
+	StopLimit,
+	TakeProfitMarket,
+	TakeProfitLimit,
 }
 
 #[derive(Clone, Copy, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
@@ -87,6 +120,7 @@ pub enum TimeInForce {
 	GTC,
 	IOC,
 	FOK,
+	GTT,
 }
 
 #[derive(Clone, Copy, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo)]
@@ -99,6 +133,9 @@ pub enum BalanceChangeReason {
 	Withdrawal,
 	WithdrawalFee,
 	ABR,
+	LossSocialisation,
+	FeeSweep,
+	MakerRebate,
 }
 
 #[derive(
@@ -194,6 +231,35 @@ pub struct AccountInfo {
 	pub unused_balance: FixedI128,
 }
 
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct OrderState {
+	// Portion of the order that has been executed
+	pub executed_size: FixedI128,
+	pub is_cancelled: bool,
+	// Hash of the order stored on its first execution, 0 if the order was never executed
+	pub order_hash: U256,
+}
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum FeeSweepDestination {
+	// Id of the treasury trading account
+	TradingAccount(U256),
+	// Address of the insurance fund
+	InsuranceFund(U256),
+}
+
+#[derive(
+	Clone, Encode, Decode, Default, Deserialize, PartialEq, RuntimeDebug, Serialize, TypeInfo,
+)]
+pub struct AccruedFees {
+	pub trading_fee: FixedI128,
+	pub liquidation_fee: FixedI128,
+	// Share of the trading fee not credited to any fee recipient, this is what can be swept
+	pub unallocated_revenue: FixedI128,
+}
+
 // Impls
 impl From<Direction> for u8 {
 	fn from(value: Direction) -> u8 {
@@ -220,6 +286,10 @@ impl From<OrderType> for u8 {
 			OrderType::Market => 1_u8,
 			OrderType::Forced => 2_u8,
 			OrderType::ADS => 3_u8,
+			OrderType::StopMarket => 4_u8,
+			OrderType::StopLimit => 5_u8,
+			OrderType::TakeProfitMarket => 6_u8,
+			OrderType::TakeProfitLimit => 7_u8,
 		}
 	}
 }
@@ -230,6 +300,7 @@ impl From<TimeInForce> for u8 {
 			TimeInForce::GTC => 0_u8,
 			TimeInForce::IOC => 1_u8,
 			TimeInForce::FOK => 2_u8,
+			TimeInForce::GTT => 3_u8,
 		}
 	}
 }
@@ -259,6 +330,10 @@ impl From<OrderType> for &str {
 			OrderType::Limit => "LIMIT",
 			OrderType::Forced => "FORCED",
 			OrderType::ADS => "ADS",
+			OrderType::StopMarket => "STOP_MARKET",
+			OrderType::StopLimit => "STOP_LIMIT",
+			OrderType::TakeProfitMarket => "TAKE_PROFIT_MARKET",
+			OrderType::TakeProfitLimit => "TAKE_PROFIT_LIMIT",
 		}
 	}
 }
@@ -269,6 +344,7 @@ impl From<TimeInForce> for &str {
 			TimeInForce::GTC => "GTC",
 			TimeInForce::FOK => "FOK",
 			TimeInForce::IOC => "IOC",
+			TimeInForce::GTT => "GTT",
 		}
 	}
 }
@@ -282,6 +358,9 @@ impl From<BalanceChangeReason> for u8 {
 			BalanceChangeReason::Withdrawal => 5_u8,
 			BalanceChangeReason::WithdrawalFee => 6_u8,
 			BalanceChangeReason::ABR => 7_u8,
+			BalanceChangeReason::LossSocialisation => 8_u8,
+			BalanceChangeReason::FeeSweep => 9_u8,
+			BalanceChangeReason::MakerRebate => 10_u8,
 		}
 	}
 }
@@ -304,6 +383,35 @@ impl From<ForceClosureFlag> for u8 {
 	}
 }
 
+impl OrderType {
+	// Conditional orders can be executed only after the mark price crosses their trigger price
+	pub fn is_conditional(&self) -> bool {
+		match self {
+			OrderType::StopMarket |
+			OrderType::StopLimit |
+			OrderType::TakeProfitMarket |
+			OrderType::TakeProfitLimit => true,
+			_ => false,
+		}
+	}
+
+	// Orders that are matched against their limit price once executable
+	pub fn is_limit(&self) -> bool {
+		match self {
+			OrderType::Limit | OrderType::StopLimit | OrderType::TakeProfitLimit => true,
+			_ => false,
+		}
+	}
+
+	// Orders that are matched against the oracle price within their slippage once executable
+	pub fn is_market(&self) -> bool {
+		match self {
+			OrderType::Market | OrderType::StopMarket | OrderType::TakeProfitMarket => true,
+			_ => false,
+		}
+	}
+}
+
 impl PositionExtended {
 	pub fn new(
 		position: Position,
@@ -414,9 +522,81 @@ impl Hashable for Order {
 
 		elements.push(FieldElement::from(self.timestamp));
 
+		// Trigger price is part of the hash only for conditional orders, so that the hash of
+		// other orders is unchanged
+		if self.order_type.is_conditional() {
+			let u256_representation = &self.trigger_price.to_u256();
+			elements.push(
+				u256_representation
+					.try_to_felt()
+					.map_err(|_err| GeneralConversionError::U256ToFieldElementError)?,
+			);
+		}
+
+		// Reduce only and isolated flags are part of the hash only when one of them is set, so
+		// that the hash of other orders is unchanged
+		let flags = (self.reduce_only as u8) | ((self.is_isolated as u8) << 1);
+		if flags != 0 {
+			elements.push(FieldElement::from(flags));
+		}
+
+		// Expiry is part of the hash only for GTT orders
+		if self.time_in_force == TimeInForce::GTT {
+			elements.push(FieldElement::from(self.expiry));
+		}
+
 		match &hash_type {
 			HashType::Pedersen => Ok(compute_hash_on_elements(&elements)),
 			HashType::Poseidon => Ok(poseidon_hash_many(&elements)),
 		}
 	}
 }
+
+impl Hashable for CancelRequest {
+	// No error apart from error during conversion from U256 to FieldElement should happen
+	// Hence associated type is defined to be exactly that error i.e.
+	// starknet_ff::FromByteSliceError
+	type ConversionError = FromByteSliceError;
+
+	fn hash(&self, hash_type: &HashType) -> Result<FieldElement, Self::ConversionError> {
+		let (account_id_low, account_id_high) = convert_to_u128_pair(self.account_id)?;
+		let (order_id_low, order_id_high) = convert_to_u128_pair(self.order_id)?;
+		let mut elements = Vec::<FieldElement>::new();
+		elements.push(account_id_low);
+		elements.push(account_id_high);
+		elements.push(order_id_low);
+		elements.push(order_id_high);
+		elements.push(FieldElement::from(self.timestamp));
+
+		let result = match hash_type {
+			HashType::Pedersen => compute_hash_on_elements(&elements),
+			HashType::Poseidon => poseidon_hash_many(&elements),
+		};
+
+		Ok(result)
+	}
+}
+
+impl Hashable for CancelAllRequest {
+	// No error apart from error during conversion from U256 to FieldElement should happen
+	// Hence associated type is defined to be exactly that error i.e.
+	// starknet_ff::FromByteSliceError
+	type ConversionError = FromByteSliceError;
+
+	fn hash(&self, hash_type: &HashType) -> Result<FieldElement, Self::ConversionError> {
+		let (account_id_low, account_id_high) = convert_to_u128_pair(self.account_id)?;
+		let mut elements = Vec::<FieldElement>::new();
+		elements.push(account_id_low);
+		elements.push(account_id_high);
+		// 0 is used to represent all markets
+		elements.push(FieldElement::from(self.market_id.unwrap_or(0)));
+		elements.push(FieldElement::from(self.timestamp));
+
+		let result = match hash_type {
+			HashType::Pedersen => compute_hash_on_elements(&elements),
+			HashType::Poseidon => poseidon_hash_many(&elements),
+		};
+
+		Ok(result)
+	}
+}
@@ -8,10 +8,16 @@ use frame_support::{dispatch::Vec, pallet_prelude::MaxEncodedLen};
 use primitive_types::U256;
 use scale_info::TypeInfo;
 use sp_arithmetic::fixed_point::FixedI128;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
 use starknet_crypto::poseidon_hash_many;
 use starknet_ff::{FieldElement, FromByteSliceError};
 
+// Maximum no.of recipients in the fee split policy of a market
+pub const MAX_FEE_RECIPIENTS: u32 = 8;
+
+// (recipient, fraction of the fee) pairs in the fee split policy of a market
+pub type FeeRecipients = BoundedVec<(U256, FixedI128), ConstU32<MAX_FEE_RECIPIENTS>>;
+
 #[derive(
 	Encode, Decode, Default, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug,
 )]
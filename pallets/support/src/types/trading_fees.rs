@@ -29,6 +29,24 @@ pub struct FeeRates {
 	pub taker_sell: FixedI128,
 }
 
+// Fee tiers that replace the regular ones between the start (inclusive) and end timestamps
+#[derive(Clone, Decode, Default, Encode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FeeSchedule {
+	pub base_fee_aggregate: BaseFeeAggregate,
+	// Timestamps in seconds
+	pub start_timestamp: u64,
+	pub end_timestamp: u64,
+}
+
+// Fee rates negotiated with an account, they take precedence over the volume based tiers
+#[derive(Clone, Copy, Decode, Default, Encode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FeeOverride {
+	pub maker_fee: FixedI128,
+	pub taker_fee: FixedI128,
+	// Timestamp in seconds after which the override is ignored
+	pub expires_at: u64,
+}
+
 #[derive(Clone, Copy, Decode, Default, Encode, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct FeeShareDetails {
 	pub volume: FixedI128,
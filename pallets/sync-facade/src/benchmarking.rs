@@ -0,0 +1,146 @@
+//! Benchmarking setup for pallet-sync-facade
+//!
+//! Signer management calls are only enabled with the `dev` feature, so their benchmarks are
+//! skipped unless the runtime is built with `--features runtime-benchmarks,dev`.
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{
+	IsBatchProcessed, IsSignerWhitelisted, Signers, SignersQuorum, IS_DEV_ENABLED,
+};
+#[allow(unused)]
+use crate::Pallet as SyncFacade;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	ecdsa_sign,
+	helpers::compute_hash_on_elements,
+	test_helpers::asset_helper::usdc,
+	traits::{AssetInterface, FeltSerializedArrayExt, FieldElementExt},
+	types::{SyncSignature, TradingAccountMinimal, UniversalEvent, UserDeposit},
+	FieldElement,
+};
+use primitive_types::U256;
+use starknet_crypto::get_public_key;
+
+// Whitelists `count` signers and returns their private keys
+fn add_signers<T: Config>(count: u32) -> Vec<FieldElement> {
+	let private_keys: Vec<FieldElement> =
+		(0..count).map(|index| FieldElement::from(12345_u128 + index as u128)).collect();
+	for private_key in private_keys.iter() {
+		let pub_key = get_public_key(private_key).to_u256();
+		Signers::<T>::append(pub_key);
+		IsSignerWhitelisted::<T>::insert(pub_key, true);
+	}
+	SignersQuorum::<T>::put(count as u8);
+
+	private_keys
+}
+
+// Creates a batch of deposits, each of which creates a new trading account
+fn create_deposits(count: u32) -> Vec<UniversalEvent> {
+	(0..count)
+		.map(|index| {
+			UniversalEvent::UserDeposit(UserDeposit {
+				event_index: index,
+				trading_account: TradingAccountMinimal {
+					account_address: U256::from(1000_u128 + index as u128),
+					index: 0,
+					pub_key: U256::from(2000_u128 + index as u128),
+				},
+				collateral_id: usdc().asset.id,
+				nonce: U256::from(index),
+				amount: 1000.into(),
+				block_number: 1,
+			})
+		})
+		.collect()
+}
+
+fn compute_batch_hash(events_batch: &Vec<UniversalEvent>) -> FieldElement {
+	let mut flattened_array: Vec<FieldElement> = Vec::new();
+	flattened_array.try_append_universal_event_array(events_batch).unwrap();
+
+	compute_hash_on_elements(&flattened_array)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn add_signer() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let pub_key = U256::from(12345_u16);
+
+		#[extrinsic_call]
+		add_signer(RawOrigin::Signed(caller), pub_key);
+
+		assert!(IsSignerWhitelisted::<T>::get(pub_key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_signers_quorum() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		add_signers::<T>(3);
+
+		#[extrinsic_call]
+		set_signers_quorum(RawOrigin::Signed(caller), 2);
+
+		assert_eq!(SignersQuorum::<T>::get(), 2);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_signer() -> Result<(), BenchmarkError> {
+		if !IS_DEV_ENABLED {
+			return Err(BenchmarkError::Skip)
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let private_keys = add_signers::<T>(2);
+		SignersQuorum::<T>::put(1);
+		let pub_key = get_public_key(&private_keys[0]).to_u256();
+
+		#[extrinsic_call]
+		remove_signer(RawOrigin::Signed(caller), pub_key);
+
+		assert!(!IsSignerWhitelisted::<T>::get(pub_key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn synchronize_events(e: Linear<1, 100>, s: Linear<1, 10>) {
+		let caller: T::AccountId = whitelisted_caller();
+		T::AssetPallet::add_asset_internal(usdc()).unwrap();
+		let private_keys = add_signers::<T>(s);
+
+		let events_batch = create_deposits(e);
+		let batch_hash = compute_batch_hash(&events_batch);
+		let signatures: Vec<SyncSignature> = private_keys
+			.iter()
+			.map(|private_key| {
+				let signature = ecdsa_sign(private_key, &batch_hash).unwrap();
+				SyncSignature {
+					signer_pub_key: get_public_key(private_key).to_u256(),
+					r: signature.r.to_u256(),
+					s: signature.s.to_u256(),
+				}
+			})
+			.collect();
+
+		#[extrinsic_call]
+		synchronize_events(RawOrigin::Signed(caller), events_batch, signatures);
+
+		assert!(IsBatchProcessed::<T>::get(batch_hash.to_u256()));
+	}
+
+	impl_benchmark_test_suite!(SyncFacade, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -8,6 +8,11 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use frame_support::{
@@ -23,7 +28,7 @@ pub mod pallet {
 			PricesInterface, TradingAccountInterface, TradingFeesInterface, U256Ext,
 		},
 		types::{
-			ABRSettingsType, BaseFee, BaseFeeAggregate, ExtendedAsset, ExtendedMarket,
+			ABRSettingsType, BaseFee, BaseFeeAggregate, ExtendedAsset, ExtendedMarket, FeeOverride,
 			FeeSettingsType, FeeShareDetails, FeeShareSettingsType, ReferralDetails, Setting,
 			SettingsType, SyncSignature, UniversalEvent,
 		},
@@ -36,6 +41,8 @@ pub mod pallet {
 		traits::{One, Zero},
 	};
 
+	use super::WeightInfo;
+
 	#[cfg(not(feature = "dev"))]
 	pub const IS_DEV_ENABLED: bool = false;
 
@@ -53,6 +60,7 @@ pub mod pallet {
 		type MarketPallet: MarketInterface;
 		type TradingFeesPallet: TradingFeesInterface;
 		type PricesPallet: PricesInterface;
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -133,6 +141,10 @@ pub mod pallet {
 		MarketRemovedError { id: u128 },
 		/// An invalid request to remove non-existent asset
 		AssetRemovedError { id: u128 },
+		/// A request to remove a market refused as positions depend on it
+		MarketRemovalRefused { id: u128, error: DispatchError },
+		/// A request to remove an asset refused as markets or balances depend on it
+		AssetRemovalRefused { id: u128, error: DispatchError },
 		/// An invalid request to add a duplicate signer
 		SignerAddedError { pub_key: U256 },
 		/// An invalid request to remove non-existent signer
@@ -173,10 +185,16 @@ pub mod pallet {
 		DeprecatedEvent { event_index: u32, block_number: u64 },
 		/// An invalid fee split data associated with update market event
 		InvalidFeeSplitData { event_index: u32, block_number: u64 },
+		/// An invalid fee split policy associated with update market event
+		InvalidFeeRecipientsData { event_index: u32, block_number: u64 },
 		/// An invalid insurance fund event data
 		InvalidInsuranceData { event_index: u32, block_number: u64 },
 		/// Invalid fee discount value
 		InvalidFeeDiscount { event_index: u32, block_number: u64 },
+		/// An invalid fee override event data
+		InvalidFeeOverrideData { event_index: u32, block_number: u64 },
+		/// A market state that can't be expressed by the flags of a legacy market event
+		InvalidLegacyMarketState { event_index: u32, block_number: u64 },
 	}
 
 	#[pallet::error]
@@ -222,7 +240,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_signer())]
 		pub fn add_signer(origin: OriginFor<T>, pub_key: U256) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -243,7 +261,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_signers_quorum())]
 		pub fn set_signers_quorum(origin: OriginFor<T>, new_quorum: u8) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -261,7 +279,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To be removed in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::remove_signer())]
 		pub fn remove_signer(origin: OriginFor<T>, pub_key: U256) -> DispatchResult {
 			if !IS_DEV_ENABLED {
 				return Err(Error::<T>::DevOnlyCall.into());
@@ -287,7 +305,10 @@ pub mod pallet {
 		}
 
 		/// External function to be called by Synchronizer network to sync events from L2
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::synchronize_events(
+			events_batch.len() as u32,
+			signatures.len() as u32
+		))]
 		pub fn synchronize_events(
 			origin: OriginFor<T>,
 			events_batch: Vec<UniversalEvent>,
@@ -845,9 +866,16 @@ pub mod pallet {
 					UniversalEvent::MarketRemoved(market_removed) => {
 						// Check if the Market exists
 						match T::MarketPallet::get_market(market_removed.id) {
-							// If yes, remove it
+							// If yes, archive it unless positions still depend on it
 							Some(_) => {
-								T::MarketPallet::remove_market_internal(market_removed.id);
+								if let Err(error) =
+									T::MarketPallet::archive_market_internal(market_removed.id)
+								{
+									Self::deposit_event(Event::MarketRemovalRefused {
+										id: market_removed.id,
+										error,
+									});
+								}
 							},
 							// If not, emit an error
 							None => {
@@ -860,9 +888,16 @@ pub mod pallet {
 					UniversalEvent::AssetRemoved(asset_removed) => {
 						// Check if the Asset exists
 						match T::AssetPallet::get_asset(asset_removed.id) {
-							// If yes, remove it
+							// If yes, archive it unless markets or balances still depend on it
 							Some(_) => {
-								T::AssetPallet::remove_asset_internal(asset_removed.id);
+								if let Err(error) =
+									T::AssetPallet::archive_asset_internal(asset_removed.id)
+								{
+									Self::deposit_event(Event::AssetRemovalRefused {
+										id: asset_removed.id,
+										error,
+									});
+								}
 							},
 							// If not, emit an error
 							None => {
@@ -980,46 +1015,25 @@ pub mod pallet {
 						);
 					},
 					UniversalEvent::MarketUpdatedV2(market_updated_v2) => {
-						let (insurance_fund, fee_split) = market_updated_v2.fee_split_details;
-						let result =
-							if let Some(_) = T::MarketPallet::get_market(market_updated_v2.id) {
-								// Update existing market
-								T::MarketPallet::update_market_internal(ExtendedMarket {
-									market: market_updated_v2.market.clone(),
-									metadata_url: market_updated_v2.metadata_url.clone(),
-								})
-							} else {
-								// Add new market
-								T::MarketPallet::add_market_internal(ExtendedMarket {
-									market: market_updated_v2.market.clone(),
-									metadata_url: market_updated_v2.metadata_url.clone(),
-								})
-							};
+						// The hash of the event only covers the legacy flags of the state
+						if !market_updated_v2.market.state.is_legacy() {
+							Self::deposit_event(Event::InvalidLegacyMarketState {
+								event_index: market_updated_v2.event_index,
+								block_number: market_updated_v2.block_number,
+							});
+							continue;
+						}
 
-						match result {
-							Ok(_) => {
-								if insurance_fund != U256::zero() &&
-									fee_split >= FixedI128::zero() && fee_split <=
-									FixedI128::from_u32(1)
-								{
-									T::TradingAccountPallet::update_fee_split_details_internal(
-										market_updated_v2.market.id,
-										insurance_fund,
-										fee_split,
-									);
-								} else {
-									Self::deposit_event(Event::InvalidFeeSplitData {
-										event_index: market_updated_v2.event_index,
-										block_number: market_updated_v2.block_number,
-									});
-								}
-							},
-							Err(_) => {
-								Self::deposit_event(Event::AddMarketError {
-									id: market_updated_v2.id,
-								});
+						Self::handle_market_updated(
+							market_updated_v2.event_index,
+							market_updated_v2.id,
+							ExtendedMarket {
+								market: market_updated_v2.market.clone(),
+								metadata_url: market_updated_v2.metadata_url.clone(),
 							},
-						}
+							market_updated_v2.fee_split_details,
+							market_updated_v2.block_number,
+						);
 					},
 					UniversalEvent::InsuranceFundDeposited(insurance_fund_deposited) =>
 						if insurance_fund_deposited.insurance_fund > U256::zero() {
@@ -1034,10 +1048,105 @@ pub mod pallet {
 								block_number: insurance_fund_deposited.block_number,
 							});
 						},
+					UniversalEvent::FeeOverrideUpdated(fee_override_updated) => {
+						// An expiry of 0 removes the fee override
+						if fee_override_updated.expires_at == 0 {
+							T::TradingFeesPallet::remove_fee_override_internal(
+								fee_override_updated.account_id,
+								fee_override_updated.market_id,
+							);
+							continue;
+						}
+
+						if T::TradingFeesPallet::set_fee_override_internal(
+							fee_override_updated.account_id,
+							fee_override_updated.market_id,
+							FeeOverride {
+								maker_fee: fee_override_updated.maker_fee,
+								taker_fee: fee_override_updated.taker_fee,
+								expires_at: fee_override_updated.expires_at,
+							},
+						)
+						.is_err()
+						{
+							Self::deposit_event(Event::InvalidFeeOverrideData {
+								event_index: fee_override_updated.event_index,
+								block_number: fee_override_updated.block_number,
+							});
+						}
+					},
+					UniversalEvent::MarketUpdatedV3(market_updated_v3) => {
+						let is_market_updated = Self::handle_market_updated(
+							market_updated_v3.event_index,
+							market_updated_v3.id,
+							ExtendedMarket {
+								market: market_updated_v3.market.clone(),
+								metadata_url: market_updated_v3.metadata_url.clone(),
+							},
+							market_updated_v3.fee_split_details,
+							market_updated_v3.block_number,
+						);
+
+						// An empty list of fee recipients clears the fee split policy
+						if is_market_updated &&
+							!T::TradingAccountPallet::update_fee_recipients_internal(
+								market_updated_v3.market.id,
+								market_updated_v3.fee_recipients.clone(),
+							) {
+							Self::deposit_event(Event::InvalidFeeRecipientsData {
+								event_index: market_updated_v3.event_index,
+								block_number: market_updated_v3.block_number,
+							});
+						}
+					},
 				}
 			}
 		}
 
+		fn handle_market_updated(
+			event_index: u32,
+			id: u128,
+			extended_market: ExtendedMarket,
+			fee_split_details: (U256, FixedI128),
+			block_number: u64,
+		) -> bool {
+			let (insurance_fund, fee_split) = fee_split_details;
+			let market_id = extended_market.market.id;
+			let result = if let Some(_) = T::MarketPallet::get_market(id) {
+				// Update existing market, changes of risk parameters
+				// wait for the notice period
+				T::MarketPallet::update_market_with_notice_internal(extended_market)
+			} else {
+				// Add new market
+				T::MarketPallet::add_market_internal(extended_market)
+			};
+
+			match result {
+				Ok(_) => {
+					if insurance_fund != U256::zero() &&
+						fee_split >= FixedI128::zero() &&
+						fee_split <= FixedI128::from_u32(1)
+					{
+						T::TradingAccountPallet::update_fee_split_details_internal(
+							market_id,
+							insurance_fund,
+							fee_split,
+						);
+					} else {
+						Self::deposit_event(Event::InvalidFeeSplitData {
+							event_index,
+							block_number,
+						});
+					}
+					true
+				},
+				Err(_) => {
+					Self::deposit_event(Event::AddMarketError { id });
+					false
+				},
+			}
+		}
+
 		fn has_quorum(signatures: Vec<SyncSignature>, hash: FieldElement) -> bool {
 			// Get the required data
 			let quorum = SignersQuorum::<T>::get() as usize;
@@ -1122,6 +1231,10 @@ pub mod pallet {
 					(market_updated_v2.block_number, market_updated_v2.event_index),
 				UniversalEvent::InsuranceFundDeposited(insurance_fund_deposited) =>
 					(insurance_fund_deposited.block_number, insurance_fund_deposited.event_index),
+				UniversalEvent::FeeOverrideUpdated(fee_override_updated) =>
+					(fee_override_updated.block_number, fee_override_updated.event_index),
+				UniversalEvent::MarketUpdatedV3(market_updated_v3) =>
+					(market_updated_v3.block_number, market_updated_v3.event_index),
 			}
 		}
 	}
@@ -10,10 +10,11 @@ use pallet_support::{
 	traits::{FieldElementExt, TradingAccountInterface, TradingFeesInterface, TradingInterface},
 	types::{
 		Asset, AssetRemoved, AssetUpdated, BaseFeeAggregate, ExtendedAsset, ExtendedMarket,
-		FeeSettingsType, FeeShareDetails, FeeShareSettingsType, InsuranceFundDeposited,
-		MarketRemoved, MarketUpdated, MarketUpdatedV2, MasterAccountLevelChanged, OrderSide,
-		QuorumSet, ReferralDetails, ReferralDetailsAdded, SettingsAdded, Side, SignerAdded,
-		SignerRemoved, SyncSignature, TradingAccountMinimal, UniversalEvent, UserDeposit,
+		FeeOverride, FeeOverrideUpdated, FeeRecipients, FeeSettingsType, FeeShareDetails,
+		FeeShareSettingsType, InsuranceFundDeposited, MarketRemoved, MarketState, MarketUpdated,
+		MarketUpdatedV2, MarketUpdatedV3, MasterAccountLevelChanged, OrderSide, QuorumSet,
+		ReferralDetails, ReferralDetailsAdded, SettingsAdded, Side, SignerAdded, SignerRemoved,
+		SyncSignature, TradingAccountMinimal, UniversalEvent, UserDeposit,
 	},
 	FieldElement,
 };
@@ -555,7 +556,15 @@ fn sync_asset_event_add_asset_remove_asset() {
 		)
 		.expect("error while updating asset");
 
-		assert_eq!(Assets::assets_count(), 3);
+		// The asset is still used by a market, so it is kept
+		assert_eq!(Assets::assets_count(), 4);
+		System::assert_has_event(
+			Event::AssetRemovalRefused {
+				id: btc().asset.id,
+				error: pallet_asset::Error::<Test>::AssetUsedByMarket.into(),
+			}
+			.into(),
+		);
 	});
 }
 
@@ -609,6 +618,123 @@ fn sync_update_market_event_add_market() {
 	});
 }
 
+#[test]
+fn sync_update_market_event_add_market_with_fee_recipients() {
+	// Get a test environment
+	let mut env = setup();
+	let fee_split_details = (U256::one(), FixedI128::from_float(0.1));
+	let fee_recipients = FeeRecipients::try_from(vec![
+		(U256::from(2_u8), FixedI128::from_inner(500000000000000000)),
+		(U256::from(3_u8), FixedI128::from_inner(250000000000000000)),
+	])
+	.unwrap();
+
+	let update_market_event_1 = <MarketUpdatedV3 as MarketUpdatedTraitV3>::new(
+		1,
+		eth_usdc().market.id,
+		eth_usdc().market,
+		eth_usdc().metadata_url.clone(),
+		fee_split_details,
+		fee_recipients.clone(),
+		1337,
+	);
+
+	let mut events_batch: Vec<UniversalEvent> = <Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch.add_market_updated_v3_event(update_market_event_1);
+
+	let events_batch_hash = events_batch.compute_hash();
+
+	let mut signature_array = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array.add_new_signature(
+		events_batch_hash,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	env.execute_with(|| {
+		// add assets
+		assert_ok!(Assets::replace_all_assets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![usdc(), eth()]
+		));
+
+		// synchronize the events
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch,
+			signature_array,
+		)
+		.expect("error while updating market");
+
+		assert_eq!(Markets::markets(eth_usdc().market.id).unwrap(), eth_usdc());
+		assert_eq!(TradingAccounts::fee_recipients(eth_usdc().market.id), fee_recipients);
+	});
+}
+
+#[test]
+fn sync_update_market_event_clear_fee_recipients() {
+	// Get a test environment
+	let mut env = setup();
+	let fee_split_details = (U256::one(), FixedI128::from_float(0.1));
+	let fee_recipients = FeeRecipients::try_from(vec![(
+		U256::from(2_u8),
+		FixedI128::from_inner(500000000000000000),
+	)])
+	.unwrap();
+
+	let update_market_event_1 = <MarketUpdatedV3 as MarketUpdatedTraitV3>::new(
+		1,
+		eth_usdc().market.id,
+		eth_usdc().market,
+		eth_usdc().metadata_url.clone(),
+		fee_split_details,
+		fee_recipients,
+		1337,
+	);
+
+	// An empty list of recipients clears the fee split policy
+	let update_market_event_2 = <MarketUpdatedV3 as MarketUpdatedTraitV3>::new(
+		2,
+		eth_usdc().market.id,
+		eth_usdc().market,
+		eth_usdc().metadata_url.clone(),
+		fee_split_details,
+		FeeRecipients::default(),
+		1337,
+	);
+
+	let mut events_batch: Vec<UniversalEvent> = <Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch.add_market_updated_v3_event(update_market_event_1);
+	events_batch.add_market_updated_v3_event(update_market_event_2);
+
+	let events_batch_hash = events_batch.compute_hash();
+
+	let mut signature_array = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array.add_new_signature(
+		events_batch_hash,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	env.execute_with(|| {
+		// add assets
+		assert_ok!(Assets::replace_all_assets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![usdc(), eth()]
+		));
+
+		// synchronize the events
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch,
+			signature_array,
+		)
+		.expect("error while updating market");
+
+		assert!(TradingAccounts::fee_recipients(eth_usdc().market.id).is_empty());
+	});
+}
+
 #[test]
 #[should_panic]
 fn sync_update_market_event_add_market_invalid_input_fee_split() {
@@ -838,7 +964,7 @@ fn sync_update_market_event_update_market() {
 	let eth_fee_split_details = (U256::one(), FixedI128::from_float(0.1));
 
 	let mut updated_market = eth_usdc();
-	updated_market.market.is_archived = true;
+	updated_market.market.ttl = 1800;
 
 	let update_market_event_1 = <MarketUpdatedV2 as MarketUpdatedTraitV2>::new(
 		1,
@@ -891,6 +1017,61 @@ fn sync_update_market_event_update_market() {
 	});
 }
 
+#[test]
+fn sync_update_market_event_with_non_legacy_state() {
+	// Get a test environment
+	let mut env = setup();
+
+	// Reduce only can't be told apart from active by the legacy flags
+	let updated_market = eth_usdc().set_state(MarketState::ReduceOnly);
+
+	let update_market_event_1 = <MarketUpdatedV2 as MarketUpdatedTraitV2>::new(
+		1,
+		updated_market.market.id,
+		updated_market.market.clone(),
+		updated_market.metadata_url.clone(),
+		(U256::one(), FixedI128::from_float(0.1)),
+		1337,
+	);
+
+	let mut events_batch: Vec<UniversalEvent> = <Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch.add_market_updated_v2_event(update_market_event_1);
+
+	let events_batch_hash = events_batch.compute_hash();
+
+	let mut signature_array = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array.add_new_signature(
+		events_batch_hash,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	env.execute_with(|| {
+		// add assets
+		assert_ok!(Assets::replace_all_assets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![usdc(), eth()]
+		));
+		// add markets
+		assert_ok!(Markets::replace_all_markets(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![eth_usdc()]
+		));
+		// synchronize the events
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch,
+			signature_array,
+		)
+		.expect("error while updating market");
+
+		System::assert_has_event(
+			Event::InvalidLegacyMarketState { event_index: 1, block_number: 1337 }.into(),
+		);
+		assert_eq!(Markets::markets(eth_usdc().market.id).unwrap(), eth_usdc());
+	});
+}
+
 #[test]
 fn sync_remove_market_event() {
 	// Get a test environment
@@ -931,6 +1112,10 @@ fn sync_remove_market_event() {
 		.expect("error while updating market");
 
 		assert_eq!(Markets::markets_count(), 0);
+
+		// The removed market is kept in the archive
+		let archived_market = Markets::archived_markets(eth_usdc().market.id).unwrap();
+		assert_eq!(archived_market.market.state, MarketState::Archived);
 	});
 }
 
@@ -1483,6 +1668,105 @@ fn sync_insurance_fund_updated_event_invalid_data() {
 	});
 }
 
+#[test]
+fn sync_fee_override_updated_event() {
+	// Get a test environment
+	let mut env = setup();
+
+	let alice_id = get_trading_account_id(alice());
+	let market_id = btc_usdc().market.id;
+
+	// Batch 1; set a valid override and an override with a negative taker fee
+	let mut events_batch_1: Vec<UniversalEvent> =
+		<Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch_1.add_fee_override_updated_event(
+		<FeeOverrideUpdated as FeeOverrideUpdatedTrait>::new(
+			1,
+			alice_id,
+			market_id,
+			FixedI128::from_inner(-100000000000000),
+			FixedI128::from_inner(300000000000000),
+			1800000000,
+			1337,
+		),
+	);
+	events_batch_1.add_fee_override_updated_event(
+		<FeeOverrideUpdated as FeeOverrideUpdatedTrait>::new(
+			2,
+			alice_id,
+			0,
+			FixedI128::zero(),
+			FixedI128::from_inner(-100000000000000),
+			1800000000,
+			1337,
+		),
+	);
+	let events_batch_hash_1 = events_batch_1.compute_hash();
+
+	let mut signature_array_1 = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array_1.add_new_signature(
+		events_batch_hash_1,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	// Batch 2; an expiry of 0 removes the override
+	let mut events_batch_2: Vec<UniversalEvent> =
+		<Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch_2.add_fee_override_updated_event(
+		<FeeOverrideUpdated as FeeOverrideUpdatedTrait>::new(
+			3,
+			alice_id,
+			market_id,
+			FixedI128::zero(),
+			FixedI128::zero(),
+			0,
+			1338,
+		),
+	);
+	let events_batch_hash_2 = events_batch_2.compute_hash();
+
+	let mut signature_array_2 = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array_2.add_new_signature(
+		events_batch_hash_2,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	env.execute_with(|| {
+		// synchronize the events batch 1
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch_1,
+			signature_array_1,
+		)
+		.expect("error while updating fee override");
+
+		assert_eq!(
+			TradingFees::fee_override(alice_id, market_id),
+			Some(FeeOverride {
+				maker_fee: FixedI128::from_inner(-100000000000000),
+				taker_fee: FixedI128::from_inner(300000000000000),
+				expires_at: 1800000000,
+			})
+		);
+		assert_eq!(TradingFees::fee_override(alice_id, 0), None);
+		System::assert_has_event(
+			Event::InvalidFeeOverrideData { event_index: 2, block_number: 1337 }.into(),
+		);
+
+		// synchronize the events batch 2
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch_2,
+			signature_array_2,
+		)
+		.expect("error while removing fee override");
+
+		assert_eq!(TradingFees::fee_override(alice_id, market_id), None);
+	});
+}
+
 #[test]
 fn sync_multiple_master_level_changed_event() {
 	// Get a test environment
@@ -1730,7 +2014,50 @@ fn sync_update_remove_asset() {
 		)
 		.expect("error while updating asset");
 
+		// The asset is still used by a market, so it is kept
+		assert_eq!(Assets::assets_count(), 4);
+		System::assert_has_event(
+			Event::AssetRemovalRefused {
+				id: usdc().asset.id,
+				error: pallet_asset::Error::<Test>::AssetUsedByMarket.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sync_remove_unused_asset() {
+	// Get a test environment
+	let mut env = setup();
+
+	let remove_asset_event_1 = <AssetRemoved as AssetRemovedTrait>::new(1, usdt().asset.id, 1337);
+
+	let mut events_batch = <Vec<UniversalEvent> as UniversalEventArray>::new();
+	events_batch.add_asset_removed_event(remove_asset_event_1);
+
+	let events_batch_hash = events_batch.compute_hash();
+
+	let mut signature_array = <Vec<SyncSignature> as SyncSignatureArray>::new();
+	signature_array.add_new_signature(
+		events_batch_hash,
+		U256::from("0x399ab58e2d17603eeccae95933c81d504ce475eb1bd0080d2316b84232e133c"),
+		FieldElement::from(12345_u16),
+	);
+
+	env.execute_with(|| {
+		// synchronize the events
+		SyncFacade::synchronize_events(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			events_batch,
+			signature_array,
+		)
+		.expect("error while updating asset");
+
+		// The removed asset is kept in the archive
 		assert_eq!(Assets::assets_count(), 3);
+		assert_eq!(Assets::assets(usdt().asset.id), None);
+		assert_eq!(Assets::archived_assets(usdt().asset.id).unwrap(), usdt());
 	});
 }
 
@@ -2522,6 +2849,7 @@ fn sync_settings_event_btc_usdc() {
 		// Check fees for maker
 		let fees_1 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2529,6 +2857,7 @@ fn sync_settings_event_btc_usdc() {
 		);
 		let fees_2 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2536,6 +2865,7 @@ fn sync_settings_event_btc_usdc() {
 		);
 		let fees_3 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2550,6 +2880,7 @@ fn sync_settings_event_btc_usdc() {
 		// Check fees for taker
 		let fees_1 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2557,6 +2888,7 @@ fn sync_settings_event_btc_usdc() {
 		);
 		let fees_2 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2564,6 +2896,7 @@ fn sync_settings_event_btc_usdc() {
 		);
 		let fees_3 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2571,6 +2904,7 @@ fn sync_settings_event_btc_usdc() {
 		);
 		let fees_4 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2702,6 +3036,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		// Check fees for maker
 		let fees_1 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2709,6 +3044,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		);
 		let fees_2 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2716,6 +3052,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		);
 		let fees_3 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Maker,
@@ -2730,6 +3067,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		// Check fees for taker
 		let fees_1 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2737,6 +3075,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		);
 		let fees_2 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2744,6 +3083,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		);
 		let fees_3 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
@@ -2751,6 +3091,7 @@ fn sync_settings_event_multiple_collaterals_markets() {
 		);
 		let fees_4 = Trading::get_fee_rate(
 			alice_id,
+			btc_usdc().market.id,
 			&fee_details,
 			Side::Buy,
 			OrderSide::Taker,
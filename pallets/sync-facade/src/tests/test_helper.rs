@@ -5,10 +5,10 @@ use pallet_support::{
 	traits::{FeltSerializedArrayExt, FieldElementExt},
 	types::{
 		Asset, AssetAddress, AssetRemoved, AssetUpdated, BaseFee, BaseFeeAggregate,
-		FeeShareDetails, InsuranceFundDeposited, Market, MarketRemoved, MarketUpdated,
-		MarketUpdatedV2, MasterAccountLevelChanged, QuorumSet, ReferralDetailsAdded, Setting,
-		SettingsAdded, SignerAdded, SignerRemoved, SyncSignature, TradingAccountMinimal,
-		UniversalEvent, UserDeposit,
+		FeeOverrideUpdated, FeeRecipients, FeeShareDetails, InsuranceFundDeposited, Market,
+		MarketRemoved, MarketUpdated, MarketUpdatedV2, MarketUpdatedV3, MasterAccountLevelChanged,
+		QuorumSet, ReferralDetailsAdded, Setting, SettingsAdded, SignerAdded, SignerRemoved,
+		SyncSignature, TradingAccountMinimal, UniversalEvent, UserDeposit,
 	},
 	FieldElement,
 };
@@ -37,6 +37,18 @@ pub trait MarketUpdatedTraitV2 {
 	) -> MarketUpdatedV2;
 }
 
+pub trait MarketUpdatedTraitV3 {
+	fn new(
+		event_index: u32,
+		id: u128,
+		market: Market,
+		metadata_url: BoundedVec<u8, ConstU32<256>>,
+		fee_split_details: (U256, FixedI128),
+		fee_recipients: FeeRecipients,
+		block_number: u64,
+	) -> MarketUpdatedV3;
+}
+
 pub trait AssetUpdatedTrait {
 	fn new(
 		event_index: u32,
@@ -129,6 +141,18 @@ pub trait InsuranceFundDepositedTrait {
 	) -> InsuranceFundDeposited;
 }
 
+pub trait FeeOverrideUpdatedTrait {
+	fn new(
+		event_index: u32,
+		account_id: U256,
+		market_id: u128,
+		maker_fee: FixedI128,
+		taker_fee: FixedI128,
+		expires_at: u64,
+		block_number: u64,
+	) -> FeeOverrideUpdated;
+}
+
 impl MarketUpdatedTrait for MarketUpdated {
 	fn new(
 		event_index: u32,
@@ -154,6 +178,28 @@ impl MarketUpdatedTraitV2 for MarketUpdatedV2 {
 	}
 }
 
+impl MarketUpdatedTraitV3 for MarketUpdatedV3 {
+	fn new(
+		event_index: u32,
+		id: u128,
+		market: Market,
+		metadata_url: BoundedVec<u8, ConstU32<256>>,
+		fee_split_details: (U256, FixedI128),
+		fee_recipients: FeeRecipients,
+		block_number: u64,
+	) -> MarketUpdatedV3 {
+		MarketUpdatedV3 {
+			event_index,
+			id,
+			market,
+			metadata_url,
+			fee_split_details,
+			fee_recipients,
+			block_number,
+		}
+	}
+}
+
 impl AssetUpdatedTrait for AssetUpdated {
 	fn new(
 		event_index: u32,
@@ -179,6 +225,28 @@ impl InsuranceFundDepositedTrait for InsuranceFundDeposited {
 	}
 }
 
+impl FeeOverrideUpdatedTrait for FeeOverrideUpdated {
+	fn new(
+		event_index: u32,
+		account_id: U256,
+		market_id: u128,
+		maker_fee: FixedI128,
+		taker_fee: FixedI128,
+		expires_at: u64,
+		block_number: u64,
+	) -> FeeOverrideUpdated {
+		FeeOverrideUpdated {
+			event_index,
+			account_id,
+			market_id,
+			maker_fee,
+			taker_fee,
+			expires_at,
+			block_number,
+		}
+	}
+}
+
 impl MarketRemovedTrait for MarketRemoved {
 	fn new(event_index: u32, id: u128, block_number: u64) -> MarketRemoved {
 		MarketRemoved { event_index, id, block_number }
@@ -695,6 +763,7 @@ pub trait UniversalEventArray {
 	fn add_settings_event(&mut self, settings_added_event: SettingsAdded);
 	fn add_referral_added_event(&mut self, referral_added_event: ReferralDetailsAdded);
 	fn add_market_updated_v2_event(&mut self, market_updated_event: MarketUpdatedV2);
+	fn add_market_updated_v3_event(&mut self, market_updated_event: MarketUpdatedV3);
 	fn add_master_level_changed_event(
 		&mut self,
 		master_level_changed_event: MasterAccountLevelChanged,
@@ -703,6 +772,7 @@ pub trait UniversalEventArray {
 		&mut self,
 		insurance_fund_deposited_event: InsuranceFundDeposited,
 	);
+	fn add_fee_override_updated_event(&mut self, fee_override_updated_event: FeeOverrideUpdated);
 	fn compute_hash(&self) -> FieldElement;
 }
 
@@ -749,6 +819,10 @@ impl UniversalEventArray for Vec<UniversalEvent> {
 		self.push(UniversalEvent::MarketUpdatedV2(market_updated_event));
 	}
 
+	fn add_market_updated_v3_event(&mut self, market_updated_event: MarketUpdatedV3) {
+		self.push(UniversalEvent::MarketUpdatedV3(market_updated_event));
+	}
+
 	fn add_asset_updated_event(&mut self, asset_updated_event: AssetUpdated) {
 		self.push(UniversalEvent::AssetUpdated(asset_updated_event));
 	}
@@ -799,6 +873,10 @@ impl UniversalEventArray for Vec<UniversalEvent> {
 		self.push(UniversalEvent::InsuranceFundDeposited(insurance_fund_deposited_event));
 	}
 
+	fn add_fee_override_updated_event(&mut self, fee_override_updated_event: FeeOverrideUpdated) {
+		self.push(UniversalEvent::FeeOverrideUpdated(fee_override_updated_event));
+	}
+
 	fn compute_hash(&self) -> FieldElement {
 		let mut flattened_array: Vec<FieldElement> = Vec::new();
 		flattened_array.try_append_universal_event_array(&self).unwrap();
@@ -0,0 +1,118 @@
+//! Weights for pallet_sync_facade
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_sync_facade.
+pub trait WeightInfo {
+	fn add_signer() -> Weight;
+	fn set_signers_quorum() -> Weight;
+	fn remove_signer() -> Weight;
+	fn synchronize_events(e: u32, s: u32, ) -> Weight;
+}
+
+/// Weights for pallet_sync_facade using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: SyncFacade IsSignerWhitelisted (r:1 w:1)
+	/// Storage: SyncFacade Signers (r:0 w:1)
+	fn add_signer() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: SyncFacade Signers (r:1 w:0)
+	/// Storage: SyncFacade SignersQuorum (r:0 w:1)
+	fn set_signers_quorum() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SyncFacade IsSignerWhitelisted (r:1 w:1)
+	/// Storage: SyncFacade Signers (r:1 w:1)
+	/// Storage: SyncFacade SignersQuorum (r:1 w:0)
+	fn remove_signer() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: SyncFacade LastProcessed (r:1 w:1)
+	/// Storage: SyncFacade IsBatchProcessed (r:1 w:1)
+	/// Storage: SyncFacade SignersQuorum (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:0)
+	/// Storage: TradingAccount AccountMap (r:1 w:1)
+	/// Storage: TradingAccount MonetaryToTradingAccountsMap (r:1 w:1)
+	/// Storage: TradingAccount AccountsCount (r:1 w:1)
+	/// Storage: TradingAccount AccountsListMap (r:0 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount AccountCollateralsMap (r:1 w:1)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:1)
+	/// The range of component `e` is `[1, 100]`.
+	/// The range of component `s` is `[1, 10]`.
+	fn synchronize_events(e: u32, s: u32, ) -> Weight {
+		Weight::from_parts(310_000_000, 0)
+			.saturating_add(Weight::from_parts(64_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(1_450_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(e.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: SyncFacade IsSignerWhitelisted (r:1 w:1)
+	/// Storage: SyncFacade Signers (r:0 w:1)
+	fn add_signer() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: SyncFacade Signers (r:1 w:0)
+	/// Storage: SyncFacade SignersQuorum (r:0 w:1)
+	fn set_signers_quorum() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SyncFacade IsSignerWhitelisted (r:1 w:1)
+	/// Storage: SyncFacade Signers (r:1 w:1)
+	/// Storage: SyncFacade SignersQuorum (r:1 w:0)
+	fn remove_signer() -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: SyncFacade LastProcessed (r:1 w:1)
+	/// Storage: SyncFacade IsBatchProcessed (r:1 w:1)
+	/// Storage: SyncFacade SignersQuorum (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:0)
+	/// Storage: TradingAccount AccountMap (r:1 w:1)
+	/// Storage: TradingAccount MonetaryToTradingAccountsMap (r:1 w:1)
+	/// Storage: TradingAccount AccountsCount (r:1 w:1)
+	/// Storage: TradingAccount AccountsListMap (r:0 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount AccountCollateralsMap (r:1 w:1)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:1)
+	/// The range of component `e` is `[1, 100]`.
+	/// The range of component `s` is `[1, 10]`.
+	fn synchronize_events(e: u32, s: u32, ) -> Weight {
+		Weight::from_parts(310_000_000, 0)
+			.saturating_add(Weight::from_parts(64_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(1_450_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(e.into())))
+	}
+}
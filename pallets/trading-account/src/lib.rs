@@ -9,6 +9,7 @@ mod mock;
 mod tests;
 
 mod migrations;
+use migrations::migrations::AssetHoldersRebuildStage;
 
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
@@ -23,20 +24,27 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use pallet_support::{
 		ecdsa_verify,
-		helpers::{get_day_diff, shift_and_recompute, sig_u256_to_sig_felt},
+		helpers::{
+			get_day_diff, get_initial_margin_fraction, has_size_tiers, shift_and_recompute,
+			sig_u256_to_sig_felt,
+		},
 		traits::{
 			AssetInterface, FieldElementExt, FixedI128Ext, Hashable, MarketInterface,
 			PricesInterface, TradingAccountInterface, TradingInterface, U256Ext,
 		},
 		types::{
-			BalanceChangeReason, BalanceUpdate, Direction, FeeSharesInput, FundModifyType,
-			InsuranceWithdrawalRequest, MonetaryAccountDetails, Position, ReferralDetails,
-			TradingAccount, TradingAccountMinimal, VolumeType, WithdrawalRequest,
+			BalanceChangeReason, BalanceUpdate, Direction, FeeRecipients, FeeSharesInput,
+			FundModifyType, InsuranceWithdrawalRequest, Market, MonetaryAccountDetails, Position,
+			ReferralDetails, TradingAccount, TradingAccountMinimal, VolumeType, WithdrawalRequest,
 		},
 		Signature,
 	};
 	use primitive_types::U256;
-	use sp_arithmetic::{fixed_point::FixedI128, traits::Zero, FixedPointNumber};
+	use sp_arithmetic::{
+		fixed_point::FixedI128,
+		traits::{One, Zero},
+		FixedPointNumber,
+	};
 	use sp_io::hashing::blake2_256;
 
 	#[cfg(not(feature = "dev"))]
@@ -45,7 +53,13 @@ pub mod pallet {
 	#[cfg(feature = "dev")]
 	pub const IS_DEV_ENABLED: bool = true;
 
+	// Maximum no.of entries visited per block while rebuilding the asset holders count
+	pub(super) static MAX_ASSET_HOLDERS_VISITED: u32 = 500;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -125,6 +139,18 @@ pub mod pallet {
 	pub(super) type DeferredBalancesMap<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, U256, Blake2_128Concat, u128, FixedI128, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn asset_holders_count)]
+	// Here, key is asset_id and value is the number of non zero balances and deferred balances
+	pub(super) type AssetHoldersCount<T: Config> =
+		StorageMap<_, Twox64Concat, u128, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn asset_holders_rebuild)]
+	// Here, value is the stage of the asset holders count rebuild and the last key visited in it
+	pub(super) type AssetHoldersRebuild<T: Config> =
+		StorageValue<_, (AssetHoldersRebuildStage, Vec<u8>), OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn locked_margin)]
 	// Here, key1 is account_id,  key2 is asset_id and value is the locked margin
@@ -200,6 +226,21 @@ pub mod pallet {
 	pub(super) type MarketToFeeSplitMap<T: Config> =
 		StorageMap<_, Twox64Concat, u128, (U256, FixedI128), OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn fee_recipients)]
+	// Stores the fee split policy of a market
+	// Here, key1 is market_id, the value is the list of recipients with the fraction of fee that
+	// each of them receives. If it is empty, fee is split as per MarketToFeeSplitMap
+	pub(super) type MarketToFeeRecipientsMap<T: Config> =
+		StorageMap<_, Twox64Concat, u128, FeeRecipients, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unallocated_revenue)]
+	// Stores the share of the trading fees not credited to any insurance fund or fee recipient
+	// Here, key1 is the collateral_id and the value is the revenue that can be swept
+	pub(super) type UnallocatedRevenueMap<T: Config> =
+		StorageMap<_, Twox64Concat, u128, FixedI128, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn default_insurance_fund)]
 	// Stores the default insurance fund
@@ -264,6 +305,9 @@ pub mod pallet {
 		ZeroSigner,
 		/// Zero address passed for insurance withdrawal
 		ZeroRecipient,
+		/// Fee recipients have a zero address or a negative fraction, or fractions add up to
+		/// more than 1
+		InvalidFeeRecipients,
 	}
 
 	#[pallet::event]
@@ -367,6 +411,17 @@ pub mod pallet {
 			amount: FixedI128,
 			block_number: BlockNumberFor<T>,
 		},
+		/// Insurance fund balance updated with a reason
+		InsuranceFundBalanceUpdated {
+			insurance_fund: U256,
+			collateral_id: u128,
+			amount: FixedI128,
+			modify_type: u8,
+			reason: u8,
+			previous_balance: FixedI128,
+			new_balance: FixedI128,
+			block_number: BlockNumberFor<T>,
+		},
 		InsuranceFundWithdrawal {
 			insurance_fund: U256,
 			recipient: U256,
@@ -386,12 +441,30 @@ pub mod pallet {
 			amount: FixedI128,
 			block_number: BlockNumberFor<T>,
 		},
+		/// Fee split policy of a market updated
+		FeeRecipientsUpdated {
+			market_id: u128,
+			fee_recipients: FeeRecipients,
+		},
+		/// Share of a fee credited to a recipient of the fee split policy
+		FeeRecipientCredited {
+			recipient: U256,
+			market_id: u128,
+			collateral_id: u128,
+			amount: FixedI128,
+			block_number: BlockNumberFor<T>,
+		},
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			migrations::migrations::rebuild_asset_holders_count::<T>()
+		}
+
 		fn on_runtime_upgrade() -> frame_support::weights::Weight {
-			migrations::migrations::migrate_to_v2::<T>()
+			migrations::migrations::migrate_to_v1::<T>()
+				.saturating_add(migrations::migrations::migrate_to_v2::<T>())
 		}
 	}
 
@@ -456,6 +529,26 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Sets the fee split policy of a market
+		#[pallet::weight(0)]
+		pub fn update_fee_recipients(
+			origin: OriginFor<T>,
+			market_id: u128,
+			fee_recipients: FeeRecipients,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(
+				T::MarketPallet::get_market(market_id).is_some(),
+				Error::<T>::MarketDoesNotExist
+			);
+			ensure!(
+				Self::update_fee_recipients_internal(market_id, fee_recipients),
+				Error::<T>::InvalidFeeRecipients
+			);
+			Ok(())
+		}
+
 		// TODO(merkle-groot): To be removed in production
 		/// To test setting of default insurance funds
 		#[pallet::weight(0)]
@@ -531,7 +624,7 @@ pub mod pallet {
 
 				// Add predefined balance for default collateral to the account
 				let default_collateral = T::AssetPallet::get_default_collateral();
-				BalancesMap::<T>::set(account_id, default_collateral, 10000.into());
+				Self::set_balance(account_id, default_collateral, 10000.into());
 				let mut collaterals: Vec<u128> = Vec::new();
 				collaterals.push(default_collateral);
 				AccountCollateralsMap::<T>::insert(account_id, collaterals);
@@ -574,7 +667,7 @@ pub mod pallet {
 					Self::add_collateral(account_id, element.asset_id);
 				}
 				// Update the map with new balance
-				BalancesMap::<T>::set(account_id, element.asset_id, element.balance_value);
+				Self::set_balance(account_id, element.asset_id, element.balance_value);
 
 				let account = AccountMap::<T>::get(&account_id)
 					.ok_or(Error::<T>::AccountDoesNotExist)?
@@ -724,7 +817,7 @@ pub mod pallet {
 				let account_id = account_id.unwrap();
 				let current_balance: FixedI128 = BalancesMap::<T>::get(account_id, collateral_id);
 				let adjusted_balance = current_balance.floor_with_precision(precision);
-				BalancesMap::<T>::set(account_id, collateral_id, adjusted_balance);
+				Self::set_balance(account_id, collateral_id, adjusted_balance);
 			}
 
 			Ok(())
@@ -774,7 +867,7 @@ pub mod pallet {
 			// Get the new balance of the user
 			let new_balance = current_balance - withdrawal_fee;
 			// Update the balance, after deducting fees
-			BalancesMap::<T>::set(
+			Self::set_balance(
 				withdrawal_request.account_id,
 				withdrawal_request.collateral_id,
 				new_balance,
@@ -829,7 +922,7 @@ pub mod pallet {
 			);
 
 			// Update the balance
-			BalancesMap::<T>::set(
+			Self::set_balance(
 				withdrawal_request.account_id,
 				withdrawal_request.collateral_id,
 				new_balance - withdrawal_request.amount,
@@ -899,6 +992,56 @@ pub mod pallet {
 
 	// Pallet internal functions
 	impl<T: Config> Pallet<T> {
+		fn set_balance(account_id: U256, asset_id: u128, new_balance: FixedI128) {
+			let previous_balance = BalancesMap::<T>::get(account_id, asset_id);
+			if Self::is_asset_holder_counted(
+				AssetHoldersRebuildStage::Balances,
+				BalancesMap::<T>::hashed_key_for(account_id, asset_id),
+			) {
+				Self::update_asset_holders_count(asset_id, previous_balance, new_balance);
+			}
+			BalancesMap::<T>::set(account_id, asset_id, new_balance);
+		}
+
+		fn set_deferred_balance(account_id: U256, asset_id: u128, new_balance: FixedI128) {
+			let previous_balance = DeferredBalancesMap::<T>::get(account_id, asset_id);
+			if Self::is_asset_holder_counted(
+				AssetHoldersRebuildStage::DeferredBalances,
+				DeferredBalancesMap::<T>::hashed_key_for(account_id, asset_id),
+			) {
+				Self::update_asset_holders_count(asset_id, previous_balance, new_balance);
+			}
+			DeferredBalancesMap::<T>::set(account_id, asset_id, new_balance);
+		}
+
+		// While the asset holders count is being rebuilt, only the balances already visited by
+		// the rebuild are kept up to date, the others are counted once they are visited
+		fn is_asset_holder_counted(stage: AssetHoldersRebuildStage, key: Vec<u8>) -> bool {
+			match AssetHoldersRebuild::<T>::get() {
+				None => true,
+				Some((AssetHoldersRebuildStage::ClearCounts, _)) => false,
+				Some((rebuild_stage, last_key)) if rebuild_stage == stage =>
+					!last_key.is_empty() && key <= last_key,
+				// Balances are visited before deferred balances
+				Some((rebuild_stage, _)) =>
+					rebuild_stage == AssetHoldersRebuildStage::DeferredBalances,
+			}
+		}
+
+		// Keeps track of the balances held in an asset, so that its removal doesn't need to
+		// scan the balances of all the accounts
+		fn update_asset_holders_count(
+			asset_id: u128,
+			previous_balance: FixedI128,
+			new_balance: FixedI128,
+		) {
+			if previous_balance == FixedI128::zero() && new_balance != FixedI128::zero() {
+				AssetHoldersCount::<T>::mutate(asset_id, |count| *count += 1);
+			} else if previous_balance != FixedI128::zero() && new_balance == FixedI128::zero() {
+				AssetHoldersCount::<T>::mutate(asset_id, |count| *count -= 1);
+			}
+		}
+
 		fn add_collateral(account_id: U256, collateral_id: u128) {
 			let mut collaterals = AccountCollateralsMap::<T>::get(account_id);
 			for element in &collaterals {
@@ -944,25 +1087,46 @@ pub mod pallet {
 			account_id: U256,
 			new_position_maintanence_requirement: FixedI128,
 			markets: Vec<u128>,
-		) -> (FixedI128, FixedI128, FixedI128) {
+		) -> (FixedI128, FixedI128, FixedI128, FixedI128, FixedI128) {
 			let mut unrealized_pnl_sum: FixedI128 = FixedI128::zero();
 			let mut negative_unrealized_pnl_sum = FixedI128::zero();
+			let mut isolated_margin_sum = FixedI128::zero();
+			let mut initial_margin_shortfall = FixedI128::zero();
 			let mut maintenance_margin_requirement: FixedI128 =
 				new_position_maintanence_requirement;
 			for curr_market_id in markets {
 				// Get Long position
-				let long_position: Position =
+				let mut long_position: Position =
 					T::TradingPallet::get_position(account_id, curr_market_id, Direction::Long);
 
 				// Get Short position
-				let short_position: Position =
+				let mut short_position: Position =
 					T::TradingPallet::get_position(account_id, curr_market_id, Direction::Short);
 
+				// Isolated positions are backed only by their own margin, so they are left out
+				// of the cross margin calculation
+				if T::TradingPallet::is_isolated_position(
+					account_id,
+					curr_market_id,
+					Direction::Long,
+				) {
+					isolated_margin_sum = isolated_margin_sum + long_position.margin_amount;
+					long_position.size = FixedI128::zero();
+				}
+				if T::TradingPallet::is_isolated_position(
+					account_id,
+					curr_market_id,
+					Direction::Short,
+				) {
+					isolated_margin_sum = isolated_margin_sum + short_position.margin_amount;
+					short_position.size = FixedI128::zero();
+				}
+
 				// Get Mark price
 				let mark_price = T::PricesPallet::get_mark_price(curr_market_id);
 
 				if mark_price == FixedI128::zero() {
-					return (0.into(), 0.into(), 0.into())
+					return (0.into(), 0.into(), 0.into(), 0.into(), 0.into())
 				}
 
 				let long_maintanence_requirement;
@@ -1010,8 +1174,31 @@ pub mod pallet {
 				maintenance_margin_requirement = maintenance_margin_requirement +
 					short_maintanence_requirement +
 					long_maintanence_requirement;
+
+				// Positions beyond the baseline size of the market require a higher initial
+				// margin than the one locked when they were opened
+				let market = T::MarketPallet::get_market(curr_market_id).unwrap();
+				initial_margin_shortfall = initial_margin_shortfall +
+					Self::get_initial_margin_shortfall(&long_position, &market) +
+					Self::get_initial_margin_shortfall(&short_position, &market);
 			}
-			return (unrealized_pnl_sum, maintenance_margin_requirement, negative_unrealized_pnl_sum)
+			return (
+				unrealized_pnl_sum,
+				maintenance_margin_requirement,
+				negative_unrealized_pnl_sum,
+				isolated_margin_sum,
+				initial_margin_shortfall,
+			)
+		}
+
+		fn get_initial_margin_shortfall(position: &Position, market: &Market) -> FixedI128 {
+			if !has_size_tiers(market) {
+				return FixedI128::zero()
+			}
+
+			let required_initial_margin = get_initial_margin_fraction(market, position.size) *
+				position.size * position.avg_execution_price;
+			max(required_initial_margin - position.margin_amount, FixedI128::zero())
 		}
 
 		fn verify_insurance_withdrawal_signature(
@@ -1189,7 +1376,7 @@ pub mod pallet {
 			let current_balance = BalancesMap::<T>::get(&account_id, collateral_id);
 			let new_balance = current_balance.add(amount);
 			let block_number = <frame_system::Pallet<T>>::block_number();
-			BalancesMap::<T>::set(account_id, collateral_id, new_balance);
+			Self::set_balance(account_id, collateral_id, new_balance);
 
 			Self::deposit_event(Event::BalanceUpdated {
 				account_id,
@@ -1236,7 +1423,7 @@ pub mod pallet {
 			let current_balance = BalancesMap::<T>::get(&account_id, collateral_id);
 			let new_balance = current_balance.sub(amount);
 			let block_number = <frame_system::Pallet<T>>::block_number();
-			BalancesMap::<T>::set(account_id, collateral_id, new_balance);
+			Self::set_balance(account_id, collateral_id, new_balance);
 
 			Self::deposit_event(Event::BalanceUpdated {
 				account_id,
@@ -1317,12 +1504,13 @@ pub mod pallet {
 				)
 			}
 
-			let (unrealized_pnl_sum, maintenance_margin_requirement, negative_unrealized_pnl_sum) =
-				Self::calculate_margin_info(
-					account_id,
-					new_position_maintanence_requirement,
-					markets,
-				);
+			let (
+				unrealized_pnl_sum,
+				maintenance_margin_requirement,
+				negative_unrealized_pnl_sum,
+				isolated_margin_sum,
+				initial_margin_shortfall,
+			) = Self::calculate_margin_info(account_id, new_position_maintanence_requirement, markets);
 
 			let unrealized_pnl_sum =
 				unrealized_pnl_sum.round_to_precision(collateral_token_decimal.into());
@@ -1330,16 +1518,25 @@ pub mod pallet {
 			// Add the new position's margin
 			let total_initial_margin_sum = initial_margin_sum + new_position_margin;
 
-			// Compute total margin of the given collateral
-			let total_margin = collateral_balance + unrealized_pnl_sum;
+			// Compute total margin of the given collateral, margin of isolated positions is not
+			// available to the other positions
+			let total_margin = collateral_balance - isolated_margin_sum + unrealized_pnl_sum;
 
-			// Compute available margin of the given collateral
-			let available_margin = total_margin - total_initial_margin_sum;
+			// Compute available margin of the given collateral, including the additional initial
+			// margin required by the size tier of the positions
+			let available_margin = total_margin -
+				(total_initial_margin_sum - isolated_margin_sum) -
+				initial_margin_shortfall;
 
 			let mut is_liquidation = false;
 
 			// If it's a long position with 1x leverage, ignore it
-			if total_margin <= maintenance_margin_requirement {
+			// Isolated positions are liquidated on their own, so the account is not liquidatable
+			// if it does not have any other position
+			if total_margin <= maintenance_margin_requirement &&
+				(maintenance_margin_requirement > FixedI128::zero() ||
+					isolated_margin_sum == FixedI128::zero())
+			{
 				is_liquidation = true;
 			}
 
@@ -1353,6 +1550,42 @@ pub mod pallet {
 			)
 		}
 
+		fn get_isolated_margin_info(
+			account_id: U256,
+			market_id: u128,
+			direction: Direction,
+			new_position_maintanence_requirement: FixedI128,
+			new_position_margin: FixedI128,
+		) -> (bool, FixedI128, FixedI128) {
+			let position: Position =
+				T::TradingPallet::get_position(account_id, market_id, direction);
+
+			let mut unrealized_pnl = FixedI128::zero();
+			let mut maintenance_margin_requirement = new_position_maintanence_requirement;
+			if position.size != FixedI128::zero() {
+				let mark_price = T::PricesPallet::get_mark_price(market_id);
+				if mark_price == FixedI128::zero() {
+					return (false, position.margin_amount + new_position_margin, 0.into())
+				}
+
+				let (pnl, maintenance_requirement) =
+					Self::get_risk_parameters(&position, direction, mark_price, market_id);
+
+				let market = T::MarketPallet::get_market(market_id).unwrap();
+				let collateral_asset = T::AssetPallet::get_asset(market.asset_collateral).unwrap();
+				unrealized_pnl = pnl.round_to_precision(collateral_asset.decimals.into());
+				maintenance_margin_requirement =
+					maintenance_margin_requirement + maintenance_requirement;
+			}
+
+			// Total margin of an isolated position is limited to the margin locked for it
+			let total_margin = position.margin_amount + new_position_margin + unrealized_pnl;
+
+			let is_liquidation = total_margin <= maintenance_margin_requirement;
+
+			(is_liquidation, total_margin, maintenance_margin_requirement)
+		}
+
 		fn update_fee_split_details_internal(
 			market_id: u128,
 			insurance_fund: U256,
@@ -1361,6 +1594,28 @@ pub mod pallet {
 			MarketToFeeSplitMap::<T>::set(market_id, Some((insurance_fund, fee_split)));
 		}
 
+		fn update_fee_recipients_internal(market_id: u128, fee_recipients: FeeRecipients) -> bool {
+			let mut total_fraction = FixedI128::zero();
+			for (recipient, fraction) in fee_recipients.iter() {
+				if *recipient == U256::zero() || fraction.is_negative() {
+					return false
+				}
+				total_fraction = total_fraction + *fraction;
+			}
+			if total_fraction > FixedI128::one() {
+				return false
+			}
+
+			// An empty list clears the fee split policy of the market
+			if fee_recipients.is_empty() {
+				MarketToFeeRecipientsMap::<T>::remove(market_id);
+			} else {
+				MarketToFeeRecipientsMap::<T>::insert(market_id, fee_recipients.clone());
+			}
+			Self::deposit_event(Event::FeeRecipientsUpdated { market_id, fee_recipients });
+			true
+		}
+
 		fn deposit_internal(
 			trading_account: TradingAccountMinimal,
 			collateral_id: u128,
@@ -1380,7 +1635,7 @@ pub mod pallet {
 					DeferredBalancesMap::<T>::get(account_id, collateral_id);
 
 				// Save it to storage
-				DeferredBalancesMap::<T>::insert(
+				Self::set_deferred_balance(
 					account_id,
 					collateral_id,
 					previous_deferred_balance + amount,
@@ -1437,7 +1692,7 @@ pub mod pallet {
 
 			let new_balance: FixedI128 = amount + current_balance;
 			// Update the balance
-			BalancesMap::<T>::set(account_id, collateral_id, new_balance);
+			Self::set_balance(account_id, collateral_id, new_balance);
 
 			// Get the user account
 			let account = AccountMap::<T>::get(&account_id).unwrap().to_trading_account_minimal();
@@ -1462,25 +1717,52 @@ pub mod pallet {
 			market_id: u128,
 			amount: FixedI128,
 			fee_share_amount: FixedI128,
+			rebate_amount: FixedI128,
 		) {
 			// Get the insurance fund and fee split details
 			let collateral_asset = T::AssetPallet::get_asset(collateral_id).unwrap();
 			let collateral_token_decimal = collateral_asset.decimals;
 
-			let (insurance_fund, fee_split) = Self::get_fee_split_details(market_id);
-			let current_insurance_fund_balance =
-				InsuranceFundBalances::<T>::get(insurance_fund, collateral_id);
-			let amount_after_fee_share = amount - fee_share_amount;
-			let revenue_amount = (amount_after_fee_share * fee_split)
-				.round_to_precision(collateral_token_decimal.into());
-			let fee_amount = amount_after_fee_share - revenue_amount;
-
-			// Increment the local balance of insurance fund
-			InsuranceFundBalances::<T>::set(
-				insurance_fund,
-				collateral_id,
-				current_insurance_fund_balance + fee_amount,
-			);
+			// Rebates credited to the makers are paid out of the fee before it is split
+			let amount_after_fee_share = amount - fee_share_amount - rebate_amount;
+			let fee_recipients = MarketToFeeRecipientsMap::<T>::get(market_id);
+			let revenue_amount = if fee_recipients.is_empty() {
+				let (insurance_fund, fee_split) = Self::get_fee_split_details(market_id);
+				let current_insurance_fund_balance =
+					InsuranceFundBalances::<T>::get(insurance_fund, collateral_id);
+				let revenue_amount = (amount_after_fee_share * fee_split)
+					.round_to_precision(collateral_token_decimal.into());
+				let fee_amount = amount_after_fee_share - revenue_amount;
+
+				// Increment the local balance of insurance fund
+				InsuranceFundBalances::<T>::set(
+					insurance_fund,
+					collateral_id,
+					current_insurance_fund_balance + fee_amount,
+				);
+				revenue_amount
+			} else {
+				// Every recipient is credited its fraction of the fee, the rest is revenue
+				let mut revenue_amount = amount_after_fee_share;
+				for (recipient, fraction) in fee_recipients {
+					let recipient_amount = (amount_after_fee_share * fraction)
+						.round_to_precision(collateral_token_decimal.into());
+					InsuranceFundBalances::<T>::mutate(recipient, collateral_id, |balance| {
+						*balance = *balance + recipient_amount
+					});
+					revenue_amount = revenue_amount - recipient_amount;
+
+					Self::deposit_event(Event::FeeRecipientCredited {
+						recipient,
+						market_id,
+						collateral_id,
+						amount: recipient_amount,
+						block_number: <frame_system::Pallet<T>>::block_number(),
+					});
+				}
+				revenue_amount
+			};
+			Self::update_unallocated_revenue(collateral_id, revenue_amount);
 
 			// Emit the event to be picked up by the Synchronizer
 			Self::deposit_event(Event::UserBalanceChangeV2 {
@@ -1560,10 +1842,10 @@ pub mod pallet {
 				let new_balance = previous_balance + deferred_balance;
 
 				// Update the balance
-				BalancesMap::<T>::insert(account_id, collateral_id, new_balance);
+				Self::set_balance(account_id, collateral_id, new_balance);
 
 				// Reset the deferred balance
-				DeferredBalancesMap::<T>::insert(account_id, collateral_id, FixedI128::zero());
+				Self::set_deferred_balance(account_id, collateral_id, FixedI128::zero());
 
 				// Get the account details for the event
 				let account = AccountMap::<T>::get(&account_id)
@@ -1591,6 +1873,11 @@ pub mod pallet {
 			AccountsCount::<T>::get()
 		}
 
+		fn has_balances(asset_id: u128) -> bool {
+			// Counts can't be relied upon until they are rebuilt
+			AssetHoldersRebuild::<T>::exists() || AssetHoldersCount::<T>::get(asset_id) != 0
+		}
+
 		fn get_collaterals_of_user(account_id: U256) -> Vec<u128> {
 			AccountCollateralsMap::<T>::get(account_id)
 		}
@@ -1864,6 +2151,82 @@ pub mod pallet {
 			InsuranceFundBalances::<T>::set(insurance_fund, collateral_id, current_balance + amount)
 		}
 
+		fn transfer_collateral(
+			account_id: U256,
+			collateral_id: u128,
+			amount: FixedI128,
+			reason: BalanceChangeReason,
+		) {
+			if amount.is_negative() {
+				Self::deposit_event(Event::AmountIsNegative {
+					account_id,
+					collateral_id,
+					amount,
+					reason: reason.into(),
+				});
+				return
+			}
+			let account = AccountMap::<T>::get(&account_id).unwrap().to_trading_account_minimal();
+			let current_balance = BalancesMap::<T>::get(&account_id, collateral_id);
+			let new_balance = current_balance.add(amount);
+			let block_number = <frame_system::Pallet<T>>::block_number();
+			Self::set_balance(account_id, collateral_id, new_balance);
+
+			Self::deposit_event(Event::BalanceUpdated {
+				account_id,
+				account,
+				collateral_id,
+				amount,
+				modify_type: FundModifyType::Increase.into(),
+				reason: reason.into(),
+				previous_balance: current_balance,
+				new_balance,
+				block_number,
+			});
+
+			// Event to be synced by L2; the amount is not associated with any market
+			Self::deposit_event(Event::UserBalanceChange {
+				trading_account: account,
+				collateral_id,
+				amount,
+				modify_type: FundModifyType::Increase,
+				reason: reason.into(),
+				block_number,
+			});
+		}
+
+		fn deposit_to_insurance_fund(
+			insurance_fund: U256,
+			collateral_id: u128,
+			amount: FixedI128,
+			reason: BalanceChangeReason,
+		) {
+			let current_balance = InsuranceFundBalances::<T>::get(insurance_fund, collateral_id);
+			let new_balance = current_balance + amount;
+			InsuranceFundBalances::<T>::set(insurance_fund, collateral_id, new_balance);
+
+			Self::deposit_event(Event::InsuranceFundBalanceUpdated {
+				insurance_fund,
+				collateral_id,
+				amount,
+				modify_type: FundModifyType::Increase.into(),
+				reason: reason.into(),
+				previous_balance: current_balance,
+				new_balance,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+		}
+
+		fn get_unallocated_revenue(collateral_id: u128) -> FixedI128 {
+			UnallocatedRevenueMap::<T>::get(collateral_id)
+		}
+
+		fn update_unallocated_revenue(collateral_id: u128, amount: FixedI128) {
+			UnallocatedRevenueMap::<T>::mutate(collateral_id, |revenue| {
+				*revenue = *revenue + amount
+			});
+		}
+
 		fn get_fee_discount(trading_account_id: U256) -> FixedI128 {
 			let trading_account = AccountMap::<T>::get(trading_account_id);
 			// Here, unwrap will not lead to any error becuase, we are checking
@@ -1915,5 +2278,9 @@ pub mod pallet {
 				},
 			}
 		}
+
+		fn get_insurance_fund_balance(insurance_fund: U256, collateral_id: u128) -> FixedI128 {
+			InsuranceFundBalances::<T>::get(insurance_fund, collateral_id)
+		}
 	}
 }
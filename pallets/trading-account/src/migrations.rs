@@ -2,14 +2,18 @@ use super::*;
 
 pub mod migrations {
 	use super::*;
+	use codec::{Decode, Encode};
 	use frame_support::{
+		dispatch::Vec,
 		traits::{Get, GetStorageVersion, StorageVersion},
 		weights::Weight,
+		RuntimeDebug,
 	};
 	use primitive_types::U256;
-	use sp_arithmetic::fixed_point::FixedI128;
+	use scale_info::TypeInfo;
+	use sp_arithmetic::{fixed_point::FixedI128, traits::Zero};
 
-	pub fn migrate_to_v2<T: Config>() -> Weight {
+	pub fn migrate_to_v1<T: Config>() -> Weight {
 		// It Should be the address returned by get_insurance_fund fn inside L2 contract
 		let insurance_fund_address: U256 =
 			U256::from("0x0578b12cd73ebca3e8edd00a959d5428ebde350a36f896e2a5c5b87b6e6b6caf");
@@ -33,4 +37,89 @@ pub mod migrations {
 			Weight::zero()
 		}
 	}
+
+	pub fn migrate_to_v2<T: Config>() -> Weight {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+		if onchain_version < 2 {
+			// Asset holders are counted from scratch over the following blocks, as the balances
+			// are too many to be visited in the upgrade block
+			AssetHoldersRebuild::<T>::put((
+				AssetHoldersRebuildStage::ClearCounts,
+				Vec::<u8>::new(),
+			));
+
+			// Update the storage version
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(1, 2)
+		} else {
+			Weight::zero()
+		}
+	}
+
+	// Stage of the asset holders count rebuild, along with the last key visited in it
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum AssetHoldersRebuildStage {
+		ClearCounts,
+		Balances,
+		DeferredBalances,
+	}
+
+	// Continues the asset holders count rebuild started by migrate_to_v2, visiting at most
+	// MAX_ASSET_HOLDERS_VISITED entries per block
+	pub fn rebuild_asset_holders_count<T: Config>() -> Weight {
+		let (stage, last_key) = match AssetHoldersRebuild::<T>::get() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		let next_cursor = match stage {
+			AssetHoldersRebuildStage::ClearCounts => {
+				let maybe_cursor = if last_key.is_empty() { None } else { Some(&last_key[..]) };
+				let result = AssetHoldersCount::<T>::clear(MAX_ASSET_HOLDERS_VISITED, maybe_cursor);
+				reads += result.loops as u64;
+				writes += result.unique as u64;
+
+				match result.maybe_cursor {
+					Some(cursor) => Some((AssetHoldersRebuildStage::ClearCounts, cursor)),
+					None => Some((AssetHoldersRebuildStage::Balances, Vec::new())),
+				}
+			},
+			AssetHoldersRebuildStage::Balances | AssetHoldersRebuildStage::DeferredBalances => {
+				let mut iter = match (stage, last_key.is_empty()) {
+					(AssetHoldersRebuildStage::Balances, true) => BalancesMap::<T>::iter(),
+					(AssetHoldersRebuildStage::Balances, false) =>
+						BalancesMap::<T>::iter_from(last_key),
+					(_, true) => DeferredBalancesMap::<T>::iter(),
+					(_, false) => DeferredBalancesMap::<T>::iter_from(last_key),
+				};
+
+				let mut visited: u32 = 0;
+				for (_, asset_id, balance) in iter.by_ref().take(MAX_ASSET_HOLDERS_VISITED as usize)
+				{
+					visited += 1;
+					if balance != FixedI128::zero() {
+						AssetHoldersCount::<T>::mutate(asset_id, |holders| *holders += 1);
+						reads += 1;
+						writes += 1;
+					}
+				}
+				reads += visited as u64;
+
+				if visited == MAX_ASSET_HOLDERS_VISITED {
+					Some((stage, iter.last_raw_key().to_vec()))
+				} else if stage == AssetHoldersRebuildStage::Balances {
+					Some((AssetHoldersRebuildStage::DeferredBalances, Vec::new()))
+				} else {
+					None
+				}
+			},
+		};
+		AssetHoldersRebuild::<T>::set(next_cursor);
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
 }
@@ -69,12 +69,18 @@ impl pallet_trading_account::Config for Test {
 
 impl pallet_asset::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MarketPallet = Markets;
+	type TradingAccountPallet = TradingAccountModule;
+	type WeightInfo = ();
 }
 
 impl pallet_market::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type PricesPallet = Prices;
+	type TradingPallet = Trading;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -92,12 +98,15 @@ impl pallet_prices::Config for Test {
 	type TradingAccountPallet = TradingAccountModule;
 	type TradingPallet = Trading;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_trading_fees::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type MarketPallet = Markets;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_trading::Config for Test {
@@ -110,6 +119,7 @@ impl pallet_trading::Config for Test {
 	type RiskManagementPallet = RiskManagement;
 	type TimeProvider = Timestamp;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_risk_management::Config for Test {
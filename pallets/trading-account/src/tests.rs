@@ -1,5 +1,9 @@
-use crate::{mock::*, Event};
-use frame_support::{assert_ok, pallet_prelude::Hooks};
+use crate::{mock::*, BalancesMap, DeferredBalancesMap, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::Hooks,
+	traits::{GetStorageVersion, StorageVersion},
+};
 use pallet_support::{
 	test_helpers::{
 		accounts_helper::{
@@ -10,7 +14,7 @@ use pallet_support::{
 		create_insurance_withdrawal_request,
 		market_helper::{btc_usdc, link_usdc},
 	},
-	traits::TradingAccountInterface,
+	traits::{AssetInterface, TradingAccountInterface},
 	types::{
 		trading::{Direction, OrderType},
 		BalanceUpdate, FeeSharesInput, MonetaryAccountDetails, Order, ReferralDetails,
@@ -302,6 +306,39 @@ fn test_add_balances() {
 	});
 }
 
+#[test]
+fn test_remove_asset_with_balances() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Get the trading account of Alice
+		let trading_account_id = get_trading_account_id(alice());
+		assert_ok!(TradingAccountModule::set_balances(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			trading_account_id,
+			vec![BalanceUpdate { asset_id: usdt().asset.id, balance_value: 500.into() }]
+		));
+
+		// The asset is kept while Alice holds a balance of it
+		assert_eq!(TradingAccountModule::asset_holders_count(usdt().asset.id), 1);
+		assert!(TradingAccountModule::has_balances(usdt().asset.id));
+		assert_noop!(
+			Assets::remove_asset_internal(usdt().asset.id),
+			pallet_asset::Error::<Test>::AssetHasBalances
+		);
+
+		// Once the balance is withdrawn, the asset can be removed
+		assert_ok!(TradingAccountModule::set_balances(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			trading_account_id,
+			vec![BalanceUpdate { asset_id: usdt().asset.id, balance_value: FixedI128::zero() }]
+		));
+		assert_eq!(TradingAccountModule::asset_holders_count(usdt().asset.id), 0);
+		assert!(!TradingAccountModule::has_balances(usdt().asset.id));
+		assert_ok!(Assets::remove_asset_internal(usdt().asset.id));
+	});
+}
+
 #[test]
 fn test_deposit() {
 	let mut env = setup();
@@ -2274,3 +2311,65 @@ fn migration_should_work() {
 		);
 	});
 }
+
+#[test]
+fn migration_to_v2_should_count_asset_holders() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let alice_id = get_trading_account_id(alice());
+		let bob_id = get_trading_account_id(bob());
+		BalancesMap::<Test>::insert(alice_id, usdt().asset.id, FixedI128::from_u32(500));
+		BalancesMap::<Test>::insert(bob_id, usdt().asset.id, FixedI128::zero());
+		DeferredBalancesMap::<Test>::insert(bob_id, link().asset.id, FixedI128::from_u32(100));
+		StorageVersion::new(1).put::<TradingAccountModule>();
+
+		// Trigger migration
+		TradingAccountModule::on_runtime_upgrade();
+		assert_eq!(TradingAccountModule::on_chain_storage_version(), 2);
+
+		// Assets can't be removed until the counts are rebuilt
+		assert!(TradingAccountModule::has_balances(eth().asset.id));
+
+		// Counts are cleared, then balances and deferred balances are counted in later blocks
+		let mut block_number = 2;
+		while TradingAccountModule::asset_holders_rebuild().is_some() {
+			TradingAccountModule::on_initialize(block_number);
+			block_number += 1;
+		}
+
+		// Balances set in the test setup are counted along with the ones above
+		assert_eq!(TradingAccountModule::asset_holders_count(usdt().asset.id), 1);
+		assert_eq!(TradingAccountModule::asset_holders_count(link().asset.id), 1);
+		assert_eq!(TradingAccountModule::asset_holders_count(usdc().asset.id), 4);
+		assert!(!TradingAccountModule::has_balances(eth().asset.id));
+	});
+}
+
+#[test]
+fn migration_to_v2_should_count_balance_changes_during_rebuild() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let alice_id = get_trading_account_id(alice());
+		StorageVersion::new(1).put::<TradingAccountModule>();
+
+		// Trigger migration and count the balances
+		TradingAccountModule::on_runtime_upgrade();
+		TradingAccountModule::on_initialize(2);
+		TradingAccountModule::on_initialize(3);
+
+		// Balance changes made while deferred balances are being counted are not lost
+		assert_ok!(TradingAccountModule::set_balances(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			alice_id,
+			vec![BalanceUpdate { asset_id: usdt().asset.id, balance_value: 500.into() }]
+		));
+		while TradingAccountModule::asset_holders_rebuild().is_some() {
+			TradingAccountModule::on_initialize(4);
+		}
+
+		assert_eq!(TradingAccountModule::asset_holders_count(usdt().asset.id), 1);
+		assert_eq!(TradingAccountModule::asset_holders_count(usdc().asset.id), 4);
+	});
+}
@@ -0,0 +1,141 @@
+//! Benchmarking setup for pallet-trading-fees
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{BaseFeeMap, FeeOverrideMap, FeeScheduleMap, FeeShare};
+#[allow(unused)]
+use crate::Pallet as TradingFees;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	test_helpers::asset_helper::usdc,
+	traits::AssetInterface,
+	types::{BaseFee, BaseFeeAggregate, FeeOverride, FeeSchedule, FeeShareDetails},
+};
+use primitive_types::U256;
+use sp_arithmetic::{fixed_point::FixedI128, FixedPointNumber};
+
+// Creates t fee tiers with increasing volume, every tier being valid for validation
+fn get_base_fees(t: u32) -> Vec<BaseFee> {
+	(0..t)
+		.map(|tier| BaseFee {
+			volume: FixedI128::from_u32(tier * 100000),
+			fee: FixedI128::from_rational(1, 1000),
+		})
+		.collect()
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn update_base_fees(t: Linear<1, 20>) {
+		let collateral_id = usdc().asset.id;
+		T::AssetPallet::add_asset_internal(usdc()).unwrap();
+		let fee_details = BaseFeeAggregate {
+			maker_buy: get_base_fees(t),
+			maker_sell: get_base_fees(t),
+			taker_buy: get_base_fees(t),
+			taker_sell: get_base_fees(t),
+		};
+
+		#[extrinsic_call]
+		update_base_fees(RawOrigin::Root, collateral_id, fee_details.clone());
+
+		assert_eq!(BaseFeeMap::<T>::get(collateral_id), Some(fee_details));
+	}
+
+	#[benchmark]
+	fn update_fee_share(s: Linear<1, 100>) {
+		let collateral_id = usdc().asset.id;
+		let fee_share_details = Vec::from([(0..s)
+			.map(|tier| FeeShareDetails {
+				volume: FixedI128::from_u32(tier * 100000),
+				fee_share: FixedI128::from_rational(5, 100),
+			})
+			.collect::<Vec<FeeShareDetails>>()]);
+
+		#[extrinsic_call]
+		update_fee_share(RawOrigin::Root, collateral_id, fee_share_details.clone());
+
+		assert_eq!(FeeShare::<T>::get(collateral_id), Some(fee_share_details));
+	}
+
+	#[benchmark]
+	fn set_fee_override() {
+		let account_id = U256::from(1_u8);
+		let fee_override = FeeOverride {
+			maker_fee: FixedI128::from_rational(1, 10000),
+			taker_fee: FixedI128::from_rational(3, 10000),
+			expires_at: u64::MAX,
+		};
+
+		#[extrinsic_call]
+		set_fee_override(RawOrigin::Root, account_id, 0, fee_override);
+
+		assert_eq!(FeeOverrideMap::<T>::get(account_id, 0), Some(fee_override));
+	}
+
+	#[benchmark]
+	fn remove_fee_override() {
+		let account_id = U256::from(1_u8);
+		FeeOverrideMap::<T>::insert(account_id, 0, FeeOverride::default());
+
+		#[extrinsic_call]
+		remove_fee_override(RawOrigin::Root, account_id, 0);
+
+		assert_eq!(FeeOverrideMap::<T>::get(account_id, 0), None);
+	}
+
+	#[benchmark]
+	fn prune_fee_overrides(p: Linear<1, 50>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Default overrides have already expired
+		let fee_overrides: Vec<(U256, u128)> =
+			(0..p).map(|account| (U256::from(account), 0_u128)).collect();
+		for (account_id, market_id) in fee_overrides.iter() {
+			FeeOverrideMap::<T>::insert(account_id, market_id, FeeOverride::default());
+		}
+
+		#[extrinsic_call]
+		prune_fee_overrides(RawOrigin::Signed(caller), fee_overrides);
+
+		assert_eq!(FeeOverrideMap::<T>::iter().count(), 0);
+	}
+
+	#[benchmark]
+	fn set_fee_schedule(t: Linear<1, 20>) {
+		let collateral_id = usdc().asset.id;
+		T::AssetPallet::add_asset_internal(usdc()).unwrap();
+		let fee_schedule = FeeSchedule {
+			base_fee_aggregate: BaseFeeAggregate {
+				maker_buy: get_base_fees(t),
+				maker_sell: get_base_fees(t),
+				taker_buy: get_base_fees(t),
+				taker_sell: get_base_fees(t),
+			},
+			start_timestamp: 1700000000,
+			end_timestamp: 1700604800,
+		};
+
+		#[extrinsic_call]
+		set_fee_schedule(RawOrigin::Root, collateral_id, fee_schedule.clone());
+
+		assert_eq!(FeeScheduleMap::<T>::get(collateral_id), Some(fee_schedule));
+	}
+
+	#[benchmark]
+	fn remove_fee_schedule() {
+		let collateral_id = usdc().asset.id;
+		FeeScheduleMap::<T>::insert(collateral_id, FeeSchedule::default());
+
+		#[extrinsic_call]
+		remove_fee_schedule(RawOrigin::Root, collateral_id);
+
+		assert_eq!(FeeScheduleMap::<T>::get(collateral_id), None);
+	}
+
+	impl_benchmark_test_suite!(TradingFees, crate::mock::new_test_ext(), crate::mock::Test);
+}
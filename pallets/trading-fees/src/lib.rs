@@ -8,23 +8,40 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use core::option::Option;
 	use frame_support::{
 		dispatch::{DispatchResult, Vec},
 		pallet_prelude::{OptionQuery, ValueQuery, *},
+		traits::UnixTime,
 	};
 	use frame_system::pallet_prelude::*;
 	use pallet_support::{
 		traits::{AssetInterface, MarketInterface, TradingFeesInterface},
-		types::{BaseFee, BaseFeeAggregate, FeeShareDetails, OrderSide, Side},
+		types::{
+			BaseFee, BaseFeeAggregate, FeeOverride, FeeSchedule, FeeShareDetails, OrderSide, Side,
+		},
 	};
+	use primitive_types::U256;
 	use sp_arithmetic::{
 		fixed_point::FixedI128,
 		traits::{One, Zero},
 	};
 
+	use super::WeightInfo;
+
+	// Maximum no.of fee schedules, all of them are visited in on_initialize
+	pub(super) static MAX_FEE_SCHEDULES: u32 = 20;
+
+	// Maximum no.of expired fee overrides that can be pruned in a single call
+	pub(super) static MAX_FEE_OVERRIDES_PRUNED: u32 = 50;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -33,6 +50,8 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type AssetPallet: AssetInterface;
 		type MarketPallet: MarketInterface;
+		type TimeProvider: UnixTime;
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -79,6 +98,40 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn fee_schedule)]
+	// Promotional fee tiers that replace BaseFeeMap while the schedule is running
+	pub(super) type FeeScheduleMap<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u128, // collateral_id or market_id
+		FeeSchedule,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fee_schedules_count)]
+	pub(super) type FeeSchedulesCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_fee_schedule_active)]
+	// Whether the activation of a schedule has been announced
+	pub(super) type IsFeeScheduleActive<T: Config> =
+		StorageMap<_, Twox64Concat, u128, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fee_override)]
+	// Fee rates negotiated with an account; market_id 0 applies to every market
+	pub(super) type FeeOverrideMap<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		U256, // account_id
+		Twox64Concat,
+		u128, // market_id
+		FeeOverride,
+		OptionQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Invalid fee
@@ -97,6 +150,20 @@ pub mod pallet {
 		EmptyFeeShares,
 		/// Invalid value for fee share
 		InvalidFeeShare,
+		/// Maker rebate is larger than the taker fee it is paired with
+		MakerRebateExceedsTakerFee,
+		/// Fee override does not exist for the account
+		FeeOverrideNotFound,
+		/// Fee schedule must end after it starts and after the current time
+		InvalidFeeSchedule,
+		/// Fee schedule does not exist for the id
+		FeeScheduleNotFound,
+		/// No.of fee schedules cannot exceed MAX_FEE_SCHEDULES
+		TooManyFeeSchedules,
+		/// No.of fee overrides pruned cannot exceed MAX_FEE_OVERRIDES_PRUNED
+		TooManyFeeOverrides,
+		/// Fee override has not expired yet
+		FeeOverrideNotExpired,
 	}
 
 	#[pallet::event]
@@ -114,13 +181,84 @@ pub mod pallet {
 		FeeShareSet {
 			fee_share: Vec<Vec<FeeShareDetails>>,
 		},
+		FeeOverrideSet {
+			account_id: U256,
+			market_id: u128,
+			fee_override: FeeOverride,
+		},
+		FeeOverrideRemoved {
+			account_id: U256,
+			market_id: u128,
+		},
+		FeeScheduleSet {
+			id: u128,
+			fee_schedule: FeeSchedule,
+		},
+		FeeScheduleRemoved {
+			id: u128,
+		},
+		FeeScheduleActivated {
+			id: u128,
+			base_fee_aggregate: BaseFeeAggregate,
+			end_timestamp: u64,
+		},
+		FeeScheduleExpired {
+			id: u128,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// During on_initialize, now() returns the timestamp of the parent block,
+			// so a transition is announced in the block following the first one past it
+			let current_timestamp = T::TimeProvider::now().as_secs();
+			let mut reads: u64 = 0;
+			let mut writes: u64 = 0;
+
+			let fee_schedules: Vec<(u128, FeeSchedule)> = FeeScheduleMap::<T>::iter().collect();
+			for (id, fee_schedule) in fee_schedules {
+				reads += 2;
+				if current_timestamp >= fee_schedule.end_timestamp {
+					FeeScheduleMap::<T>::remove(id);
+					IsFeeScheduleActive::<T>::remove(id);
+					FeeSchedulesCount::<T>::mutate(|count| *count -= 1);
+					writes += 3;
+
+					Self::deposit_event(Event::FeeScheduleExpired { id });
+				} else if current_timestamp >= fee_schedule.start_timestamp &&
+					!IsFeeScheduleActive::<T>::get(id)
+				{
+					IsFeeScheduleActive::<T>::insert(id, true);
+					writes += 1;
+
+					Self::deposit_event(Event::FeeScheduleActivated {
+						id,
+						base_fee_aggregate: fee_schedule.base_fee_aggregate,
+						end_timestamp: fee_schedule.end_timestamp,
+					});
+				}
+			}
+
+			T::DbWeight::get().reads_writes(reads + 1, writes)
+		}
 	}
 
 	// Pallet callable functions
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// External function for updating fee details
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_base_fees(
+			[
+				fee_details.maker_buy.len(),
+				fee_details.maker_sell.len(),
+				fee_details.taker_buy.len(),
+				fee_details.taker_sell.len(),
+			]
+			.into_iter()
+			.max()
+			.unwrap_or_default() as u32
+		))]
 		pub fn update_base_fees(
 			origin: OriginFor<T>,
 			id: u128,
@@ -134,7 +272,9 @@ pub mod pallet {
 		}
 
 		/// External function for updating fee share details
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_fee_share(
+			fee_share_details.iter().map(|level| level.len()).sum::<usize>() as u32
+		))]
 		pub fn update_fee_share(
 			origin: OriginFor<T>,
 			id: u128,
@@ -146,17 +286,130 @@ pub mod pallet {
 			Self::update_fee_shares_internal(id, fee_share_details)?;
 			Ok(())
 		}
+
+		/// External function for scheduling promotional fee details
+		#[pallet::weight(T::WeightInfo::set_fee_schedule(
+			[
+				fee_schedule.base_fee_aggregate.maker_buy.len(),
+				fee_schedule.base_fee_aggregate.maker_sell.len(),
+				fee_schedule.base_fee_aggregate.taker_buy.len(),
+				fee_schedule.base_fee_aggregate.taker_sell.len(),
+			]
+			.into_iter()
+			.max()
+			.unwrap_or_default() as u32
+		))]
+		pub fn set_fee_schedule(
+			origin: OriginFor<T>,
+			id: u128,
+			fee_schedule: FeeSchedule,
+		) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			Self::validate_fee_id(id)?;
+			Self::validate_fee_details(&fee_schedule.base_fee_aggregate)?;
+			ensure!(
+				fee_schedule.start_timestamp < fee_schedule.end_timestamp &&
+					fee_schedule.end_timestamp > T::TimeProvider::now().as_secs(),
+				Error::<T>::InvalidFeeSchedule
+			);
+
+			// A new schedule replaces the previous one and has to be announced again
+			if !FeeScheduleMap::<T>::contains_key(id) {
+				let fee_schedules_count = FeeSchedulesCount::<T>::get();
+				ensure!(fee_schedules_count < MAX_FEE_SCHEDULES, Error::<T>::TooManyFeeSchedules);
+				FeeSchedulesCount::<T>::put(fee_schedules_count + 1);
+			}
+			FeeScheduleMap::<T>::insert(id, fee_schedule.clone());
+			IsFeeScheduleActive::<T>::remove(id);
+
+			Self::deposit_event(Event::FeeScheduleSet { id, fee_schedule });
+
+			Ok(())
+		}
+
+		/// External function for cancelling promotional fee details
+		#[pallet::weight(T::WeightInfo::remove_fee_schedule())]
+		pub fn remove_fee_schedule(origin: OriginFor<T>, id: u128) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			ensure!(FeeScheduleMap::<T>::contains_key(id), Error::<T>::FeeScheduleNotFound);
+			FeeScheduleMap::<T>::remove(id);
+			IsFeeScheduleActive::<T>::remove(id);
+			FeeSchedulesCount::<T>::mutate(|count| *count -= 1);
+
+			Self::deposit_event(Event::FeeScheduleRemoved { id });
+
+			Ok(())
+		}
+
+		/// External function for setting the fee rates negotiated with an account
+		#[pallet::weight(T::WeightInfo::set_fee_override())]
+		pub fn set_fee_override(
+			origin: OriginFor<T>,
+			account_id: U256,
+			market_id: u128,
+			fee_override: FeeOverride,
+		) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			Self::set_fee_override_internal(account_id, market_id, fee_override)
+		}
+
+		/// External function for removing the fee rates negotiated with an account
+		#[pallet::weight(T::WeightInfo::remove_fee_override())]
+		pub fn remove_fee_override(
+			origin: OriginFor<T>,
+			account_id: U256,
+			market_id: u128,
+		) -> DispatchResult {
+			// Make sure the caller is root
+			ensure_root(origin)?;
+
+			ensure!(
+				FeeOverrideMap::<T>::contains_key(account_id, market_id),
+				Error::<T>::FeeOverrideNotFound
+			);
+			Self::remove_fee_override_internal(account_id, market_id);
+			Ok(())
+		}
+
+		/// External function for removing fee overrides that have expired
+		#[pallet::weight(T::WeightInfo::prune_fee_overrides(fee_overrides.len() as u32))]
+		pub fn prune_fee_overrides(
+			origin: OriginFor<T>,
+			fee_overrides: Vec<(U256, u128)>,
+		) -> DispatchResult {
+			// Anyone can clean up expired overrides
+			ensure_signed(origin)?;
+
+			ensure!(
+				fee_overrides.len() as u32 <= MAX_FEE_OVERRIDES_PRUNED,
+				Error::<T>::TooManyFeeOverrides
+			);
+
+			let current_timestamp = T::TimeProvider::now().as_secs();
+			for (account_id, market_id) in fee_overrides {
+				let fee_override = FeeOverrideMap::<T>::get(account_id, market_id)
+					.ok_or(Error::<T>::FeeOverrideNotFound)?;
+				ensure!(
+					fee_override.expires_at <= current_timestamp,
+					Error::<T>::FeeOverrideNotExpired
+				);
+				Self::remove_fee_override_internal(account_id, market_id);
+			}
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> TradingFeesInterface for Pallet<T> {
 		fn update_base_fees_internal(id: u128, fee_details: BaseFeeAggregate) -> DispatchResult {
-			// Validate that the asset exists and it is a collateral
-			if let Some(asset) = T::AssetPallet::get_asset(id) {
-				ensure!(asset.is_collateral, Error::<T>::AssetNotCollateral);
-			} else {
-				// If it's not an asset, ensure that it's a valid market
-				ensure!(T::MarketPallet::get_market(id).is_some(), Error::<T>::MarketNotFound);
-			}
+			// Validate that the asset exists and it is a collateral or a valid market
+			Self::validate_fee_id(id)?;
 
 			// Validate the fee details
 			Self::validate_fee_details(&fee_details)?;
@@ -173,10 +426,14 @@ pub mod pallet {
 		}
 
 		fn get_all_fees(market_id: u128, collateral_id: u128) -> BaseFeeAggregate {
-			// First try to fetch market fees
+			// Running promotions take precedence, first for the market and then for the asset
+			// Otherwise, try to fetch market fees
 			// If it doesn't exist, fetch asset fees
 			// NOTE: Asset fees can be 0
-			BaseFeeMap::<T>::get(market_id)
+			let current_timestamp = T::TimeProvider::now().as_secs();
+			Self::get_running_fee_schedule(market_id, current_timestamp)
+				.or_else(|| Self::get_running_fee_schedule(collateral_id, current_timestamp))
+				.or_else(|| BaseFeeMap::<T>::get(market_id))
 				.or_else(|| BaseFeeMap::<T>::get(collateral_id))
 				.unwrap_or_else(BaseFeeAggregate::default)
 		}
@@ -205,6 +462,48 @@ pub mod pallet {
 			FeeShare::<T>::get(collateral_id).unwrap_or_default()
 		}
 
+		fn set_fee_override_internal(
+			account_id: U256,
+			market_id: u128,
+			fee_override: FeeOverride,
+		) -> DispatchResult {
+			// Market specific overrides need a valid market
+			if market_id != 0 {
+				ensure!(
+					T::MarketPallet::get_market(market_id).is_some(),
+					Error::<T>::MarketNotFound
+				);
+			}
+
+			// Taker fee cannot be negative
+			ensure!(fee_override.taker_fee >= FixedI128::zero(), Error::<T>::InvalidFee);
+
+			// Maker rebate cannot be larger than the taker fee
+			ensure!(
+				fee_override.maker_fee + fee_override.taker_fee >= FixedI128::zero(),
+				Error::<T>::MakerRebateExceedsTakerFee
+			);
+
+			FeeOverrideMap::<T>::insert(account_id, market_id, fee_override);
+
+			Self::deposit_event(Event::FeeOverrideSet { account_id, market_id, fee_override });
+
+			Ok(())
+		}
+
+		fn remove_fee_override_internal(account_id: U256, market_id: u128) {
+			FeeOverrideMap::<T>::remove(account_id, market_id);
+
+			Self::deposit_event(Event::FeeOverrideRemoved { account_id, market_id });
+		}
+
+		fn get_fee_override(account_id: U256, market_id: u128) -> Option<FeeOverride> {
+			// Market specific override takes precedence over the account wide one
+			let current_timestamp = T::TimeProvider::now().as_secs();
+			Self::get_active_fee_override(account_id, market_id, current_timestamp)
+				.or_else(|| Self::get_active_fee_override(account_id, 0, current_timestamp))
+		}
+
 		fn get_fee_share(account_level: u8, collateral_id: u128, volume: FixedI128) -> FixedI128 {
 			let fee_share_details = FeeShare::<T>::get(collateral_id);
 
@@ -235,23 +534,59 @@ pub mod pallet {
 
 	// Pallet internal functions
 	impl<T: Config> Pallet<T> {
+		fn validate_fee_id(id: u128) -> DispatchResult {
+			// Validate that the asset exists and it is a collateral
+			if let Some(asset) = T::AssetPallet::get_asset(id) {
+				ensure!(asset.is_collateral, Error::<T>::AssetNotCollateral);
+			} else {
+				// If it's not an asset, ensure that it's a valid market
+				ensure!(T::MarketPallet::get_market(id).is_some(), Error::<T>::MarketNotFound);
+			}
+
+			Ok(())
+		}
+
+		fn get_active_fee_override(
+			account_id: U256,
+			market_id: u128,
+			timestamp: u64,
+		) -> Option<FeeOverride> {
+			// Expired overrides are ignored here and removed through prune_fee_overrides
+			FeeOverrideMap::<T>::get(account_id, market_id)
+				.filter(|fee_override| fee_override.expires_at > timestamp)
+		}
+
+		fn get_running_fee_schedule(id: u128, timestamp: u64) -> Option<BaseFeeAggregate> {
+			FeeScheduleMap::<T>::get(id)
+				.filter(|fee_schedule| {
+					fee_schedule.start_timestamp <= timestamp &&
+						timestamp < fee_schedule.end_timestamp
+				})
+				.map(|fee_schedule| fee_schedule.base_fee_aggregate)
+		}
+
 		fn validate_fee_details(fee_details: &BaseFeeAggregate) -> DispatchResult {
-			// Validate each variant of BaseFee
-			Self::validate_base_fees(&fee_details.maker_buy)?;
-			Self::validate_base_fees(&fee_details.maker_sell)?;
-			Self::validate_base_fees(&fee_details.taker_buy)?;
-			Self::validate_base_fees(&fee_details.taker_sell)?;
+			// Validate each variant of BaseFee; only maker fees can be negative (rebates)
+			Self::validate_base_fees(&fee_details.maker_buy, true)?;
+			Self::validate_base_fees(&fee_details.maker_sell, true)?;
+			Self::validate_base_fees(&fee_details.taker_buy, false)?;
+			Self::validate_base_fees(&fee_details.taker_sell, false)?;
+
+			// A taker buy is always filled by maker sells and vice versa;
+			// the lowest taker fee must cover the highest maker rebate
+			Self::validate_maker_rebate(&fee_details.maker_sell, &fee_details.taker_buy)?;
+			Self::validate_maker_rebate(&fee_details.maker_buy, &fee_details.taker_sell)?;
 
 			Ok(())
 		}
 
-		fn validate_base_fees(base_fees: &Vec<BaseFee>) -> DispatchResult {
+		fn validate_base_fees(base_fees: &Vec<BaseFee>, allow_rebates: bool) -> DispatchResult {
 			// The base_fees array cannot be empty
 			ensure!(!base_fees.is_empty(), Error::<T>::ZeroFeeTiers);
 
 			// Validate the first fee tier
 			let first_fee = &base_fees[0];
-			ensure!(first_fee.fee >= FixedI128::zero(), Error::<T>::InvalidFee);
+			ensure!(allow_rebates || first_fee.fee >= FixedI128::zero(), Error::<T>::InvalidFee);
 			// The volume of first tier must be 0
 			ensure!(first_fee.volume == FixedI128::zero(), Error::<T>::InvalidVolume);
 
@@ -267,6 +602,29 @@ pub mod pallet {
 				ensure!(current_fee.fee <= prev_fee.fee, Error::<T>::InvalidFee);
 			}
 
+			// Fees are non-increasing, so the last tier holds the lowest fee
+			if !allow_rebates {
+				ensure!(
+					base_fees[base_fees.len() - 1].fee >= FixedI128::zero(),
+					Error::<T>::InvalidFee
+				);
+			}
+
+			Ok(())
+		}
+
+		fn validate_maker_rebate(
+			maker_fees: &Vec<BaseFee>,
+			taker_fees: &Vec<BaseFee>,
+		) -> DispatchResult {
+			// Both arrays are validated to be non-empty and non-increasing
+			let lowest_maker_fee = maker_fees[maker_fees.len() - 1].fee;
+			let lowest_taker_fee = taker_fees[taker_fees.len() - 1].fee;
+			ensure!(
+				lowest_maker_fee + lowest_taker_fee >= FixedI128::zero(),
+				Error::<T>::MakerRebateExceedsTakerFee
+			);
+
 			Ok(())
 		}
 
@@ -62,12 +62,18 @@ impl frame_system::Config for Test {
 
 impl pallet_asset::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MarketPallet = Markets;
+	type TradingAccountPallet = TradingAccounts;
+	type WeightInfo = ();
 }
 
 impl pallet_market::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type PricesPallet = Prices;
+	type TradingPallet = Trading;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 impl pallet_prices::Config for Test {
@@ -78,6 +84,7 @@ impl pallet_prices::Config for Test {
 	type TradingAccountPallet = TradingAccounts;
 	type TradingPallet = Trading;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_trading::Config for Test {
@@ -90,6 +97,7 @@ impl pallet_trading::Config for Test {
 	type RiskManagementPallet = RiskManagement;
 	type TimeProvider = Timestamp;
 	type AuthorityId = AuthId;
+	type WeightInfo = ();
 }
 
 impl pallet_risk_management::Config for Test {
@@ -119,6 +127,8 @@ impl trading_fees::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type MarketPallet = Markets;
+	type TimeProvider = Timestamp;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
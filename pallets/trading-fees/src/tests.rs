@@ -1,13 +1,14 @@
-use crate::{mock::*, Event};
-use frame_support::assert_ok;
+use crate::{mock::*, Event, FeeSchedulesCount, MAX_FEE_SCHEDULES};
+use frame_support::{assert_ok, traits::Hooks};
 use pallet_support::{
 	test_helpers::{asset_helper::usdc, btc, btc_usdc, eth_usdc, link},
 	traits::TradingFeesInterface,
-	types::{BaseFeeAggregate, FeeShareDetails},
+	types::{BaseFee, BaseFeeAggregate, FeeOverride, FeeSchedule, FeeShareDetails},
 };
 
 // declare test_helper module
 pub mod test_helper;
+use primitive_types::U256;
 use sp_arithmetic::FixedI128;
 use sp_runtime::traits::Zero;
 use test_helper::*;
@@ -549,3 +550,380 @@ fn test_update_fees_with_multiple_calls() {
 		);
 	});
 }
+
+#[test]
+fn test_update_fees_with_maker_rebate() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		// Maker rebate of 0.01% is covered by the lowest taker fee of 0.02%
+		let expected_fees =
+			get_maker_rebate_aggregate_fees(FixedI128::from_inner(-100000000000000));
+
+		// Dispatch a signed extrinsic.
+		assert_ok!(TradingFeesModule::update_base_fees(
+			RuntimeOrigin::root(),
+			usdc().asset.id,
+			expected_fees.clone()
+		));
+
+		// Check the state
+		assert_eq!(TradingFeesModule::get_all_fees(0_u128, usdc().asset.id), expected_fees);
+	});
+}
+
+#[test]
+#[should_panic(expected = "MakerRebateExceedsTakerFee")]
+fn test_update_fees_with_maker_rebate_exceeding_taker_fee() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		// Maker rebate of 0.03% is larger than the lowest taker fee of 0.02%
+		assert_ok!(TradingFeesModule::update_base_fees(
+			RuntimeOrigin::root(),
+			usdc().asset.id,
+			get_maker_rebate_aggregate_fees(FixedI128::from_inner(-300000000000000))
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "InvalidFee")]
+fn test_update_fees_with_negative_taker_fee() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		// Taker fees can never be negative
+		let fees = get_maker_rebate_aggregate_fees(FixedI128::zero());
+		let negative_taker_fees = vec![
+			BaseFee { volume: FixedI128::from_u32(0), fee: FixedI128::zero() },
+			BaseFee {
+				volume: FixedI128::from_u32(1000000),
+				fee: FixedI128::from_inner(-100000000000000),
+			},
+		];
+
+		assert_ok!(TradingFeesModule::update_base_fees(
+			RuntimeOrigin::root(),
+			usdc().asset.id,
+			BaseFeeAggregate {
+				maker_buy: fees.maker_buy,
+				maker_sell: fees.maker_sell,
+				taker_buy: negative_taker_fees,
+				taker_sell: fees.taker_sell
+			},
+		));
+	});
+}
+
+#[test]
+fn test_set_fee_override() {
+	let account_id = U256::from(100_u8);
+	let market_id = btc_usdc().market.id;
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		let account_fee_override = FeeOverride {
+			maker_fee: FixedI128::zero(),
+			taker_fee: FixedI128::from_inner(400000000000000),
+			expires_at: 1800000000,
+		};
+		let market_fee_override = FeeOverride {
+			maker_fee: FixedI128::from_inner(-100000000000000),
+			taker_fee: FixedI128::from_inner(300000000000000),
+			expires_at: 1800000000,
+		};
+
+		// Account wide override applies to every market
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			0,
+			account_fee_override
+		));
+		assert_eq!(
+			TradingFeesModule::get_fee_override(account_id, market_id),
+			Some(account_fee_override)
+		);
+
+		// Market specific override takes precedence
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			market_id,
+			market_fee_override
+		));
+		assert_eq!(
+			TradingFeesModule::get_fee_override(account_id, market_id),
+			Some(market_fee_override)
+		);
+		System::assert_last_event(
+			Event::FeeOverrideSet { account_id, market_id, fee_override: market_fee_override }
+				.into(),
+		);
+
+		// Removing the market override falls back to the account wide one
+		assert_ok!(TradingFeesModule::remove_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			market_id
+		));
+		assert_eq!(
+			TradingFeesModule::get_fee_override(account_id, market_id),
+			Some(account_fee_override)
+		);
+		System::assert_last_event(Event::FeeOverrideRemoved { account_id, market_id }.into());
+	});
+}
+
+#[test]
+fn test_expired_fee_override() {
+	let account_id = U256::from(100_u8);
+	let market_id = btc_usdc().market.id;
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		let account_fee_override = FeeOverride {
+			maker_fee: FixedI128::zero(),
+			taker_fee: FixedI128::from_inner(400000000000000),
+			expires_at: 1800000000,
+		};
+		let market_fee_override = FeeOverride {
+			maker_fee: FixedI128::from_inner(-100000000000000),
+			taker_fee: FixedI128::from_inner(300000000000000),
+			expires_at: 1700000000,
+		};
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			0,
+			account_fee_override
+		));
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			market_id,
+			market_fee_override
+		));
+
+		// Expired market override falls back to the account wide one but stays in storage
+		Timestamp::set_timestamp(1750000000000);
+		assert_eq!(
+			TradingFeesModule::get_fee_override(account_id, market_id),
+			Some(account_fee_override)
+		);
+		assert_eq!(
+			TradingFeesModule::fee_override(account_id, market_id),
+			Some(market_fee_override)
+		);
+
+		// Expired override is pruned
+		assert_ok!(TradingFeesModule::prune_fee_overrides(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![(account_id, market_id)]
+		));
+		assert_eq!(TradingFeesModule::fee_override(account_id, market_id), None);
+		System::assert_last_event(Event::FeeOverrideRemoved { account_id, market_id }.into());
+
+		// Once the account wide override expires, there is no override
+		Timestamp::set_timestamp(1850000000000);
+		assert_eq!(TradingFeesModule::get_fee_override(account_id, market_id), None);
+		assert_eq!(TradingFeesModule::fee_override(account_id, 0), Some(account_fee_override));
+	});
+}
+
+#[test]
+#[should_panic(expected = "FeeOverrideNotExpired")]
+fn test_prune_active_fee_override() {
+	let account_id = U256::from(100_u8);
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		Timestamp::set_timestamp(1700000000000);
+
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			account_id,
+			0,
+			FeeOverride {
+				maker_fee: FixedI128::zero(),
+				taker_fee: FixedI128::from_inner(400000000000000),
+				expires_at: 1800000000,
+			}
+		));
+
+		// Override is still active, so it can't be pruned
+		assert_ok!(TradingFeesModule::prune_fee_overrides(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![(account_id, 0)]
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "MakerRebateExceedsTakerFee")]
+fn test_set_fee_override_with_rebate_exceeding_taker_fee() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		// Maker rebate of 0.04% is larger than the taker fee of 0.03%
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			U256::from(100_u8),
+			btc_usdc().market.id,
+			FeeOverride {
+				maker_fee: FixedI128::from_inner(-400000000000000),
+				taker_fee: FixedI128::from_inner(300000000000000),
+				expires_at: 1800000000,
+			}
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "MarketNotFound")]
+fn test_set_fee_override_invalid_market() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+
+		assert_ok!(TradingFeesModule::set_fee_override(
+			RuntimeOrigin::root(),
+			U256::from(100_u8),
+			eth_usdc().market.id,
+			FeeOverride::default()
+		));
+	});
+}
+
+#[test]
+fn test_fee_schedule() {
+	let market_id = btc_usdc().market.id;
+	let collateral_id = usdc().asset.id;
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		Timestamp::set_timestamp(1700000000000);
+
+		// Regular fees for the collateral
+		assert_ok!(TradingFeesModule::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_usdc_aggregate_fees()
+		));
+
+		// Zero fee week for the market
+		let fee_schedule = FeeSchedule {
+			base_fee_aggregate: get_0_aggregate_fees(),
+			start_timestamp: 1700000100,
+			end_timestamp: 1700604900,
+		};
+		assert_ok!(TradingFeesModule::set_fee_schedule(
+			RuntimeOrigin::root(),
+			market_id,
+			fee_schedule.clone()
+		));
+		System::assert_last_event(
+			Event::FeeScheduleSet { id: market_id, fee_schedule: fee_schedule.clone() }.into(),
+		);
+
+		// Schedule has not started yet
+		TradingFeesModule::on_initialize(1);
+		assert_eq!(
+			TradingFeesModule::get_all_fees(market_id, collateral_id),
+			get_usdc_aggregate_fees()
+		);
+		assert!(!TradingFeesModule::is_fee_schedule_active(market_id));
+
+		// Schedule is running
+		System::set_block_number(2);
+		Timestamp::set_timestamp(1700000100000);
+		TradingFeesModule::on_initialize(2);
+		assert_eq!(
+			TradingFeesModule::get_all_fees(market_id, collateral_id),
+			get_0_aggregate_fees()
+		);
+		assert!(TradingFeesModule::is_fee_schedule_active(market_id));
+		System::assert_last_event(
+			Event::FeeScheduleActivated {
+				id: market_id,
+				base_fee_aggregate: get_0_aggregate_fees(),
+				end_timestamp: 1700604900,
+			}
+			.into(),
+		);
+
+		// Schedule has ended, regular fees apply again
+		System::set_block_number(3);
+		Timestamp::set_timestamp(1700604900000);
+		assert_eq!(
+			TradingFeesModule::get_all_fees(market_id, collateral_id),
+			get_usdc_aggregate_fees()
+		);
+		TradingFeesModule::on_initialize(3);
+		assert_eq!(TradingFeesModule::fee_schedule(market_id), None);
+		assert_eq!(TradingFeesModule::fee_schedules_count(), 0);
+		assert!(!TradingFeesModule::is_fee_schedule_active(market_id));
+		System::assert_last_event(Event::FeeScheduleExpired { id: market_id }.into());
+	});
+}
+
+#[test]
+#[should_panic(expected = "InvalidFeeSchedule")]
+fn test_fee_schedule_ending_before_start() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		Timestamp::set_timestamp(1700000000000);
+
+		assert_ok!(TradingFeesModule::set_fee_schedule(
+			RuntimeOrigin::root(),
+			usdc().asset.id,
+			FeeSchedule {
+				base_fee_aggregate: get_0_aggregate_fees(),
+				start_timestamp: 1700604900,
+				end_timestamp: 1700000100,
+			}
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "TooManyFeeSchedules")]
+fn test_fee_schedule_exceeding_max_schedules() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// Go past genesis block so events get deposited
+		System::set_block_number(1);
+		Timestamp::set_timestamp(1700000000000);
+
+		// Every slot is taken by another schedule
+		FeeSchedulesCount::<Test>::put(MAX_FEE_SCHEDULES);
+
+		assert_ok!(TradingFeesModule::set_fee_schedule(
+			RuntimeOrigin::root(),
+			usdc().asset.id,
+			FeeSchedule {
+				base_fee_aggregate: get_0_aggregate_fees(),
+				start_timestamp: 1700000100,
+				end_timestamp: 1700604900,
+			}
+		));
+	});
+}
@@ -144,6 +144,26 @@ pub fn get_invalid_aggregate_fee() -> BaseFeeAggregate {
 	}
 }
 
+pub fn get_maker_rebate_aggregate_fees(maker_rebate: FixedI128) -> BaseFeeAggregate {
+	let maker_fees = vec![
+		BaseFee { volume: FixedI128::from_u32(0), fee: FixedI128::from_inner(200000000000000) },
+		BaseFee { volume: FixedI128::from_u32(1000000), fee: maker_rebate },
+	];
+	let taker_fees = vec![
+		BaseFee { volume: FixedI128::from_u32(0), fee: FixedI128::from_inner(500000000000000) },
+		BaseFee {
+			volume: FixedI128::from_u32(1000000),
+			fee: FixedI128::from_inner(200000000000000),
+		},
+	];
+	BaseFeeAggregate {
+		maker_buy: maker_fees.clone(),
+		maker_sell: maker_fees,
+		taker_buy: taker_fees.clone(),
+		taker_sell: taker_fees,
+	}
+}
+
 fn get_usdc_maker_open_fees() -> Vec<BaseFee> {
 	vec![
 		BaseFee { volume: FixedI128::from_u32(0), fee: FixedI128::from_float(0.02) },
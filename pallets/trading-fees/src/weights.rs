@@ -0,0 +1,149 @@
+//! Weights for pallet_trading_fees
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_trading_fees.
+pub trait WeightInfo {
+	fn update_base_fees(t: u32, ) -> Weight;
+	fn update_fee_share(s: u32, ) -> Weight;
+	fn set_fee_override() -> Weight;
+	fn remove_fee_override() -> Weight;
+	fn prune_fee_overrides(p: u32, ) -> Weight;
+	fn set_fee_schedule(t: u32, ) -> Weight;
+	fn remove_fee_schedule() -> Weight;
+}
+
+/// Weights for pallet_trading_fees using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: TradingFees BaseFeeMap (r:0 w:1)
+	/// The range of component `t` is `[1, 20]`.
+	fn update_base_fees(t: u32, ) -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingFees FeeShare (r:0 w:1)
+	/// The range of component `s` is `[1, 100]`.
+	fn update_fee_share(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(310_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: TradingFees FeeOverrideMap (r:0 w:1)
+	fn set_fee_override() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingFees FeeOverrideMap (r:1 w:1)
+	fn remove_fee_override() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: TradingFees FeeOverrideMap (r:1 w:1)
+	/// The range of component `p` is `[1, 50]`.
+	fn prune_fee_overrides(p: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: TradingFees FeeScheduleMap (r:1 w:1)
+	/// Storage: TradingFees FeeSchedulesCount (r:1 w:1)
+	/// Storage: TradingFees IsFeeScheduleActive (r:0 w:1)
+	/// The range of component `t` is `[1, 20]`.
+	fn set_fee_schedule(t: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TradingFees FeeScheduleMap (r:1 w:1)
+	/// Storage: TradingFees FeeSchedulesCount (r:1 w:1)
+	/// Storage: TradingFees IsFeeScheduleActive (r:0 w:1)
+	fn remove_fee_schedule() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: TradingFees BaseFeeMap (r:0 w:1)
+	/// The range of component `t` is `[1, 20]`.
+	fn update_base_fees(t: u32, ) -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingFees FeeShare (r:0 w:1)
+	/// The range of component `s` is `[1, 100]`.
+	fn update_fee_share(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(310_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: TradingFees FeeOverrideMap (r:0 w:1)
+	fn set_fee_override() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingFees FeeOverrideMap (r:1 w:1)
+	fn remove_fee_override() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: TradingFees FeeOverrideMap (r:1 w:1)
+	/// The range of component `p` is `[1, 50]`.
+	fn prune_fee_overrides(p: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: TradingFees FeeScheduleMap (r:1 w:1)
+	/// Storage: TradingFees FeeSchedulesCount (r:1 w:1)
+	/// Storage: TradingFees IsFeeScheduleActive (r:0 w:1)
+	/// The range of component `t` is `[1, 20]`.
+	fn set_fee_schedule(t: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TradingFees FeeScheduleMap (r:1 w:1)
+	/// Storage: TradingFees FeeSchedulesCount (r:1 w:1)
+	/// Storage: TradingFees IsFeeScheduleActive (r:0 w:1)
+	fn remove_fee_schedule() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
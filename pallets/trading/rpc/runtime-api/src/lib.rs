@@ -1,7 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::dispatch::Vec;
-use pallet_support::types::{AccountInfo, FeeRates, MarginInfo, PositionExtended};
+use pallet_support::types::{
+	AccountInfo, AccruedFees, FeeRates, MarginInfo, OrderState, PositionExtended,
+};
 use primitive_types::U256;
 use sp_arithmetic::fixed_point::FixedI128;
 
@@ -16,5 +18,8 @@ sp_api::decl_runtime_apis! {
 		fn get_fee(account_id: U256, market_id: U256) -> (FeeRates, u64);
 		fn get_withdrawable_amount(account_id: U256, collateral_id: u128) -> FixedI128;
 		fn get_remaining_trading_cleanup_calls() -> u64;
+		fn get_order_state(order_id: U256) -> OrderState;
+		fn get_batch_status(batch_id: U256) -> bool;
+		fn get_accrued_fees(collateral_id: u128) -> AccruedFees;
 	}
 }
@@ -3,7 +3,9 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{CallError, ErrorObject},
 };
-use pallet_support::types::{AccountInfo, FeeRates, MarginInfo, PositionExtended};
+use pallet_support::types::{
+	AccountInfo, AccruedFees, FeeRates, MarginInfo, OrderState, PositionExtended,
+};
 pub use pallet_trading_runtime_api::TradingApi as TradingRuntimeApi;
 use primitive_types::U256;
 use sp_api::ProvideRuntimeApi;
@@ -64,6 +66,19 @@ pub trait TradingApi<BlockHash> {
 
 	#[method(name = "trading_get_remaining_cleanup_calls")]
 	fn get_remaining_trading_cleanup_calls(&self, at: Option<BlockHash>) -> RpcResult<u64>;
+
+	#[method(name = "trading_get_order_state")]
+	fn get_order_state(&self, order_id: U256, at: Option<BlockHash>) -> RpcResult<OrderState>;
+
+	#[method(name = "trading_get_batch_status")]
+	fn get_batch_status(&self, batch_id: U256, at: Option<BlockHash>) -> RpcResult<bool>;
+
+	#[method(name = "trading_get_accrued_fees")]
+	fn get_accrued_fees(
+		&self,
+		collateral_id: u128,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccruedFees>;
 }
 
 /// A struct that implements the `TemplateApi`.
@@ -172,6 +187,39 @@ where
 
 		api.get_remaining_trading_cleanup_calls(at).map_err(runtime_error_into_rpc_err)
 	}
+
+	fn get_order_state(
+		&self,
+		order_id: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<OrderState> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_order_state(at, order_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_batch_status(
+		&self,
+		batch_id: U256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<bool> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_batch_status(at, batch_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn get_accrued_fees(
+		&self,
+		collateral_id: u128,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccruedFees> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_accrued_fees(at, collateral_id).map_err(runtime_error_into_rpc_err)
+	}
 }
 
 const RUNTIME_ERROR: i32 = 1;
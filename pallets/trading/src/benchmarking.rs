@@ -0,0 +1,380 @@
+//! Benchmarking setup for pallet-trading
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::pallet::{
+	BatchStatusMap, BatchesMap, ForceClosureFlagMap, IsLiquidatorSignerWhitelisted,
+	IsLossSocialisationEnabled, MarketCancelBeforeMap, MatchingTimeLimit, OpenInterestMap,
+	OrderAccountMap, OrderHashMap, OrderStateMap, OrdersMap, StartTimestamp, CLEANUP_COUNT,
+	LOSS_SOCIALISATION_COUNT,
+};
+#[allow(unused)]
+use crate::Pallet as Trading;
+use frame_benchmarking::v2::*;
+use frame_support::dispatch::Vec;
+use frame_system::RawOrigin;
+use pallet_support::{
+	test_helpers::{
+		asset_helper::{btc, usdc},
+		market_helper::btc_usdc,
+	},
+	traits::{AssetInterface, FieldElementExt, MarketInterface, TradingAccountInterface},
+	types::{
+		CancelAllRequest, CancelRequest, Direction, FeeSweepDestination, MarketState,
+		MultiplePrices, Order, OrderType, TradingAccountMinimal,
+	},
+};
+use primitive_types::U256;
+use sp_arithmetic::{traits::Zero, FixedI128};
+use sp_runtime::traits::SaturatedConversion;
+use starknet_crypto::{get_public_key, FieldElement};
+
+// Timestamp of the batches executed in the benchmarks, in milliseconds
+const BATCH_TIMESTAMP: u64 = 1699940367000;
+
+fn setup_market<T: Config + pallet_timestamp::Config>() {
+	pallet_timestamp::Pallet::<T>::set_timestamp(BATCH_TIMESTAMP.saturated_into());
+
+	T::AssetPallet::add_asset_internal(usdc()).unwrap();
+	T::AssetPallet::add_asset_internal(btc()).unwrap();
+	T::MarketPallet::add_market_internal(btc_usdc()).unwrap();
+
+	// Fees of closed positions are credited to the insurance fund
+	T::TradingAccountPallet::update_fee_split_details_internal(
+		btc_usdc().market.id,
+		U256::from(1_u8),
+		FixedI128::zero(),
+	);
+
+	MatchingTimeLimit::<T>::put(2419200);
+}
+
+// Creates a funded trading account and returns its id with the private key that signs its orders
+fn create_account<T: Config>(index: u32) -> (U256, FieldElement) {
+	create_account_with_balance::<T>(index, 1000000.into())
+}
+
+fn create_account_with_balance<T: Config>(index: u32, balance: FixedI128) -> (U256, FieldElement) {
+	let private_key = FieldElement::from(10000_u128 + index as u128);
+	let trading_account = TradingAccountMinimal {
+		account_address: U256::from(1000_u128 + index as u128),
+		index: 0,
+		pub_key: get_public_key(&private_key).to_u256(),
+	};
+	let account_id = T::TradingAccountPallet::get_trading_account_id(trading_account);
+	T::TradingAccountPallet::deposit_internal(trading_account, usdc().asset.id, balance);
+
+	(account_id, private_key)
+}
+
+// Creates a batch in which n - 1 makers open long positions against a single short taker,
+// so that every order touches a different position
+fn create_orders<T: Config>(n: u32) -> Vec<Order> {
+	let mut orders = Vec::<Order>::new();
+	for index in 1..n {
+		let (account_id, private_key) = create_account::<T>(index);
+		orders.push(Order::new(U256::from(index), account_id).sign_order(private_key));
+	}
+
+	let (account_id, private_key) = create_account::<T>(n);
+	orders.push(
+		Order::new(U256::from(n), account_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_size((n - 1).into())
+			.sign_order(private_key),
+	);
+
+	orders
+}
+
+#[benchmarks(where T: pallet_timestamp::Config + pallet_prices::Config)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn update_market_to_user_map(p: Linear<2, 100>) {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = btc_usdc().market.id;
+		Trading::<T>::execute_trade(
+			RawOrigin::Signed(caller.clone()).into(),
+			U256::from(1_u8),
+			(p - 1).into(),
+			market_id,
+			100.into(),
+			create_orders::<T>(p),
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		update_market_to_user_map(RawOrigin::Signed(caller), market_id, p);
+	}
+
+	#[benchmark]
+	fn execute_trade(n: Linear<2, 100>) {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let batch_id = U256::from(1_u8);
+		let orders = create_orders::<T>(n);
+
+		#[extrinsic_call]
+		execute_trade(
+			RawOrigin::Signed(caller),
+			batch_id,
+			(n - 1).into(),
+			btc_usdc().market.id,
+			100.into(),
+			orders,
+			BATCH_TIMESTAMP,
+		);
+
+		assert!(BatchStatusMap::<T>::get(batch_id));
+	}
+
+	#[benchmark]
+	fn add_liquidator_signer() {
+		let pub_key = U256::from(12345_u16);
+
+		#[extrinsic_call]
+		add_liquidator_signer(RawOrigin::Root, pub_key);
+
+		assert!(IsLiquidatorSignerWhitelisted::<T>::get(pub_key));
+	}
+
+	#[benchmark]
+	fn remove_liquidator_signer() {
+		let pub_key = U256::from(12345_u16);
+		Trading::<T>::add_liquidator_signer(RawOrigin::Root.into(), pub_key).unwrap();
+
+		#[extrinsic_call]
+		remove_liquidator_signer(RawOrigin::Root, pub_key);
+
+		assert!(!IsLiquidatorSignerWhitelisted::<T>::get(pub_key));
+	}
+
+	#[benchmark]
+	fn cancel_order() {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let (account_id, private_key) = create_account::<T>(1);
+		let order_id = U256::from(1_u8);
+
+		// Worst case is an order which is already partially executed
+		OrderHashMap::<T>::insert(order_id, U256::from(1_u8));
+		OrderAccountMap::<T>::insert(order_id, account_id);
+		let cancel_request = CancelRequest::new(account_id, order_id).sign_request(private_key);
+
+		#[extrinsic_call]
+		cancel_order(RawOrigin::Signed(caller), cancel_request);
+
+		assert!(OrderStateMap::<T>::get(order_id).1);
+	}
+
+	#[benchmark]
+	fn cancel_all_orders() {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let (account_id, private_key) = create_account::<T>(1);
+		let market_id = btc_usdc().market.id;
+		let cancel_all_request =
+			CancelAllRequest::new(account_id, Some(market_id)).sign_request(private_key);
+
+		#[extrinsic_call]
+		cancel_all_orders(RawOrigin::Signed(caller), cancel_all_request);
+
+		assert!(MarketCancelBeforeMap::<T>::get(account_id, market_id) != 0);
+	}
+
+	#[benchmark]
+	fn perform_cleanup() {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Every timestamp to be cleaned up has a batch with a taker and a maker order
+		StartTimestamp::<T>::put(1);
+		for timestamp in 1..CLEANUP_COUNT + 1 {
+			let batch_id = U256::from(timestamp);
+			let order_ids = [U256::from(2 * timestamp), U256::from(2 * timestamp + 1)];
+			BatchStatusMap::<T>::insert(batch_id, true);
+			BatchesMap::<T>::insert(timestamp, Vec::from([batch_id]));
+			for order_id in order_ids {
+				OrderStateMap::<T>::insert(order_id, (FixedI128::from(1), false));
+				OrderHashMap::<T>::insert(order_id, order_id);
+				OrderAccountMap::<T>::insert(order_id, order_id);
+			}
+			OrdersMap::<T>::insert(timestamp, Vec::from(order_ids));
+		}
+
+		#[extrinsic_call]
+		perform_cleanup(RawOrigin::Signed(caller));
+
+		assert_eq!(StartTimestamp::<T>::get(), Some(CLEANUP_COUNT + 1));
+	}
+
+	#[benchmark]
+	fn set_matching_time_limit() {
+		#[extrinsic_call]
+		set_matching_time_limit(RawOrigin::Root, 2419200);
+
+		assert_eq!(MatchingTimeLimit::<T>::get(), 2419200);
+	}
+
+	#[benchmark]
+	fn close_delisted_market_positions(p: Linear<2, 100>) {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = btc_usdc().market.id;
+		Trading::<T>::execute_trade(
+			RawOrigin::Signed(caller.clone()).into(),
+			U256::from(1_u8),
+			(p - 1).into(),
+			market_id,
+			100.into(),
+			create_orders::<T>(p),
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+
+		// Delisting a market requires a mark price to close the positions at
+		pallet_prices::Pallet::<T>::update_prices(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::from([MultiplePrices {
+				market_id,
+				index_price: 100.into(),
+				mark_price: 100.into(),
+			}]),
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+		T::MarketPallet::update_market_internal(btc_usdc().set_state(MarketState::Delisting))
+			.unwrap();
+
+		#[extrinsic_call]
+		close_delisted_market_positions(RawOrigin::Signed(caller), market_id);
+
+		assert_eq!(OpenInterestMap::<T>::get(market_id), FixedI128::zero());
+	}
+
+	#[benchmark]
+	fn flag_liquidatable_accounts(n: Linear<1, 100>) {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = btc_usdc().market.id;
+
+		// Every maker opens a long position of size 1 with its entire balance as margin
+		let mut orders = Vec::<Order>::new();
+		let mut accounts = Vec::<(U256, u128)>::new();
+		for index in 1..n + 1 {
+			let (account_id, private_key) = create_account_with_balance::<T>(index, 20.into());
+			orders.push(
+				Order::new(U256::from(index), account_id)
+					.set_leverage(5.into())
+					.sign_order(private_key),
+			);
+			accounts.push((account_id, usdc().asset.id));
+		}
+		let (account_id, private_key) = create_account::<T>(n + 1);
+		orders.push(
+			Order::new(U256::from(n + 1), account_id)
+				.set_direction(Direction::Short)
+				.set_order_type(OrderType::Market)
+				.set_size(n.into())
+				.sign_order(private_key),
+		);
+		Trading::<T>::execute_trade(
+			RawOrigin::Signed(caller.clone()).into(),
+			U256::from(1_u8),
+			n.into(),
+			market_id,
+			100.into(),
+			orders,
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+
+		// Fall in the mark price makes all the makers liquidatable
+		pallet_prices::Pallet::<T>::update_prices(
+			RawOrigin::Signed(caller.clone()).into(),
+			Vec::from([MultiplePrices {
+				market_id,
+				index_price: 80.into(),
+				mark_price: 80.into(),
+			}]),
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		flag_liquidatable_accounts(RawOrigin::Signed(caller), accounts.clone());
+
+		for (account_id, collateral_id) in accounts {
+			assert!(ForceClosureFlagMap::<T>::contains_key(account_id, collateral_id));
+		}
+	}
+
+	#[benchmark]
+	fn set_loss_socialisation() {
+		#[extrinsic_call]
+		set_loss_socialisation(RawOrigin::Root, true);
+
+		assert_eq!(IsLossSocialisationEnabled::<T>::get(), true);
+	}
+
+	// p is the number of opposite positions that share the loss
+	#[benchmark]
+	fn socialise_loss(p: Linear<1, LOSS_SOCIALISATION_COUNT>) {
+		setup_market::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// p makers open long positions against the short taker, whose loss is socialised
+		let orders = create_orders::<T>(p + 1);
+		let taker_order = orders.last().unwrap().clone();
+		Trading::<T>::execute_trade(
+			RawOrigin::Signed(caller).into(),
+			U256::from(1_u8),
+			p.into(),
+			market_id,
+			100.into(),
+			orders,
+			BATCH_TIMESTAMP,
+		)
+		.unwrap();
+
+		#[block]
+		{
+			Trading::<T>::socialise_loss(
+				&taker_order,
+				collateral_id,
+				usdc().asset.decimals,
+				p.into(),
+			);
+		}
+	}
+
+	#[benchmark]
+	fn sweep_fees() {
+		setup_market::<T>();
+		let (account_id, _) = create_account::<T>(1);
+		let collateral_id = usdc().asset.id;
+		T::TradingAccountPallet::update_unallocated_revenue(collateral_id, 100.into());
+
+		#[extrinsic_call]
+		sweep_fees(
+			RawOrigin::Root,
+			collateral_id,
+			100.into(),
+			FeeSweepDestination::TradingAccount(account_id),
+		);
+
+		assert_eq!(
+			T::TradingAccountPallet::get_unallocated_revenue(collateral_id),
+			FixedI128::zero()
+		);
+	}
+
+	impl_benchmark_test_suite!(Trading, crate::mock::new_test_ext(), crate::mock::Test);
+}
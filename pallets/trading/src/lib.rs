@@ -8,9 +8,14 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
-	use core::option::Option;
+	use core::{cmp::max, option::Option};
 	use frame_support::{
 		dispatch::Vec,
 		ensure,
@@ -23,16 +28,21 @@ pub mod pallet {
 	};
 	use pallet_support::{
 		ecdsa_verify,
-		helpers::{get_expiry_timestamp, sig_u256_to_sig_felt},
+		helpers::{
+			get_expiry_timestamp, get_initial_margin_fraction, get_max_leverage, has_size_tiers,
+			sig_u256_to_sig_felt,
+		},
 		traits::{
 			AssetInterface, FieldElementExt, FixedI128Ext, Hashable, MarketInterface,
 			PricesInterface, RiskManagementInterface, TradingAccountInterface,
 			TradingFeesInterface, TradingInterface, U256Ext,
 		},
 		types::{
-			AccountInfo, BalanceChangeReason, BaseFeeAggregate, Direction, FeeRates,
-			ForceClosureFlag, FundModifyType, MarginInfo, Market, Order, OrderSide, OrderType,
-			Position, PositionExtended, Side, SignatureInfo, TimeInForce, VolumeType,
+			AccountInfo, AccruedFees, BalanceChangeReason, BaseFeeAggregate, CancelAllRequest,
+			CancelRequest, Direction, FeeRates, FeeSweepDestination, ForceClosureFlag,
+			FundModifyType, HashType, MarginInfo, Market, MarketState, Order, OrderSide,
+			OrderState, OrderType, Position, PositionExtended, Side, SignatureInfo, TimeInForce,
+			VolumeType,
 		},
 		Signature,
 	};
@@ -41,11 +51,13 @@ pub mod pallet {
 		traits::{One, Zero},
 		FixedI128, FixedPointNumber,
 	};
-	use sp_runtime::traits::SaturatedConversion;
+	use sp_runtime::{offchain::storage::StorageValueRef, traits::SaturatedConversion};
+
+	use super::WeightInfo;
 
 	static LEVERAGE_ONE: FixedI128 = FixedI128::from_inner(1000000000000000000);
 	static FOUR_WEEKS: u64 = 2419200;
-	static CLEANUP_COUNT: u64 = 120;
+	pub(super) static CLEANUP_COUNT: u64 = 120;
 	// Block interval at which offchain workers will be executed for clearing order details
 	const BLOCK_INTERVAL: u32 = 130;
 	// Block interval at which offchain workers will be executed for closing delisted market
@@ -53,6 +65,14 @@ pub mod pallet {
 	const BLOCK_INTERVAL_FOR_DELISTING: u32 = 120;
 	// No.of positions to be closed for a delisted market
 	static POSITIONS_CLOSE_COUNT: u32 = 100;
+	// Block interval at which offchain workers will be executed for flagging liquidatable accounts
+	const BLOCK_INTERVAL_FOR_LIQUIDATION_SCAN: u32 = 10;
+	// No.of accounts to be checked for liquidation in a block interval
+	static ACCOUNTS_SCAN_COUNT: u32 = 100;
+	// Offchain storage key of the index of the account from which the next scan starts
+	const LIQUIDATION_SCAN_INDEX_KEY: &[u8] = b"trading::liquidation_scan_index";
+	// Max no.of opposite positions that share the loss of a bankrupt account
+	pub(crate) static LOSS_SOCIALISATION_COUNT: u32 = 100;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -68,6 +88,7 @@ pub mod pallet {
 		type RiskManagementPallet: RiskManagementInterface;
 		type TimeProvider: UnixTime;
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -151,11 +172,69 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn isolated_position)]
+	// k1 - account_id, k2 - (market_id, direction), v - true if the position is isolated
+	pub(super) type IsolatedPositionsMap<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		U256,
+		Blake2_128Concat,
+		(u128, Direction),
+		bool,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn isolated_force_closure_flag)]
+	// k1 - account_id, k2 - (market_id, direction), v - true if the isolated position is to be
+	// liquidated
+	pub(super) type IsolatedForceClosureMap<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		U256,
+		Blake2_128Concat,
+		(u128, Direction),
+		bool,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn order_hash)]
 	// k1 - order id, v - order hash
 	pub(super) type OrderHashMap<T: Config> = StorageMap<_, Twox64Concat, U256, U256, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn order_account)]
+	// k1 - order id, v - account id of the order owner
+	pub(super) type OrderAccountMap<T: Config> =
+		StorageMap<_, Twox64Concat, U256, U256, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_order_cancelled)]
+	// k1 - account id, k2 - order id, v - whether the account has cancelled the order
+	pub(super) type CancelledOrdersMap<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, U256, Twox64Concat, U256, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn cancellations)]
+	// k1 - timestamp, v - vector of (account id, order id) cancelled at the timestamp
+	pub(super) type CancellationsMap<T: Config> =
+		StorageMap<_, Twox64Concat, u64, Vec<(U256, U256)>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn cancel_before)]
+	// k1 - account id, v - timestamp before which all orders of the account are cancelled
+	pub(super) type CancelBeforeMap<T: Config> =
+		StorageMap<_, Blake2_128Concat, U256, u64, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn market_cancel_before)]
+	// k1 - account id, k2 - market id, v - timestamp before which all orders of the account in
+	// the market are cancelled
+	pub(super) type MarketCancelBeforeMap<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, U256, Blake2_128Concat, u128, u64, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn liquidator_signers)]
 	// Array of U256 signers
@@ -199,6 +278,12 @@ pub mod pallet {
 	#[pallet::getter(fn matching_time_limit)]
 	pub(super) type MatchingTimeLimit<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn is_loss_socialisation_enabled)]
+	// Loss of a bankrupt account which the insurance fund cannot cover is shared by the opposite
+	// positions of the market, if enabled
+	pub(super) type IsLossSocialisationEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn withdrawal_signer)]
 	// Array of U256 signers
@@ -302,6 +387,30 @@ pub mod pallet {
 		TradeBatchError547,
 		// The resulting position size is larger than the max size allowed in the market
 		TradeBatchError548,
+		/// Order was placed before a cancel all orders request of the account
+		TradeBatchError549,
+		/// Trigger price of a conditional order must be > 0
+		TradeBatchError550,
+		/// Conditional order is not triggered since mark price has not crossed the trigger price
+		TradeBatchError551,
+		/// Reduce only order cannot be a buy order
+		TradeBatchError552,
+		/// Reduce only order does not have a position to decrease
+		TradeBatchError553,
+		/// Margin mode of the order does not match the margin mode of the position
+		TradeBatchError554,
+		/// Open interest of the market would exceed its maximum
+		TradeBatchError555,
+		/// GTT order has expired
+		TradeBatchError556,
+		/// Leverage of the order or margin of the resulting position does not meet its size tier
+		TradeBatchError557,
+		/// Rebates of the makers exceed the taker fee collected by the insurance fund
+		TradeBatchError558,
+		/// Market only accepts orders that reduce a position
+		TradeBatchError559,
+		/// GTT order expires before it is placed
+		TradeBatchError560,
 		/// FoK, IoC error when maker has some issue
 		IocFokError(u8, u16),
 		/// When a zero signer is being added
@@ -316,8 +425,29 @@ pub mod pallet {
 		StartTimestampEmpty,
 		/// When mark price for ADS is not available
 		ADSPriceNotAvailable,
+		/// No.of accounts to be flagged is more than the accounts checked in a scan
+		TooManyAccountsToFlag,
+		/// Amount to be swept must be > 0 and <= the accrued fee
+		InvalidSweepAmount,
+		/// Treasury trading account to which the fees are swept does not exist
+		TreasuryAccountNotFound,
 		/// Error while adding deferred deposit
 		DeferredDepositError,
+		/// Invalid cancel request hash - cancel request could not be hashed into a Field Element
+		InvalidCancelRequestHash,
+		/// Order being cancelled belongs to a different account
+		OrderAccountMismatch,
+		/// No.of positions is more than the bound passed to the call
+		TooManyPositions,
+		/// Account of the cancel request is not registered
+		CancelAccountNotRegistered,
+		/// Invalid Signature Field Elements - sig_r and/or sig_s of the cancel request could not
+		/// be converted into a Signature
+		InvalidCancelRequestSignature,
+		/// ECDSA Signature of the cancel request could not be verified
+		CancelRequestSignatureNotVerified,
+		/// Invalid public key - public key of the account could not be converted to Field Element
+		InvalidCancelRequestPublicKey,
 	}
 
 	#[pallet::event]
@@ -353,6 +483,8 @@ pub mod pallet {
 		},
 		/// Force closure flag updation event
 		ForceClosureFlagsChanged { account_id: U256, collateral_id: u128, force_closure_flag: u8 },
+		/// Isolated position is flagged for liquidation
+		IsolatedPositionLiquidatable { account_id: U256, market_id: u128, direction: u8 },
 		/// Liquidator signer added
 		LiquidatorSignerAdded { signer: U256 },
 		/// Liquidator signer removed
@@ -365,6 +497,15 @@ pub mod pallet {
 			amount: FixedI128,
 			block_number: BlockNumberFor<T>,
 		},
+		/// Loss of a liquidated account above its balance is covered by the insurance fund
+		BankruptcyCovered { account_id: U256, order_id: U256, market_id: u128, amount: FixedI128 },
+		/// Loss of a liquidated account which the insurance fund could not cover is shared by the
+		/// opposite positions of the market
+		LossSocialised { account_id: U256, order_id: U256, market_id: u128, amount: FixedI128 },
+		/// Loss socialisation is enabled or disabled
+		LossSocialisationUpdated { is_enabled: bool },
+		/// Unallocated revenue is swept to the treasury
+		FeesSwept { collateral_id: u128, amount: FixedI128, destination: FeeSweepDestination },
 		MasterFeeShareUpdated {
 			master_account_address: U256,
 			referral_account_address: U256,
@@ -372,17 +513,32 @@ pub mod pallet {
 			collateral_id: u128,
 			fee_share: FixedI128,
 		},
+		/// Order cancelled by its owner
+		OrderCancelled { account_id: U256, order_id: U256 },
+		/// All orders of an account placed before the timestamp are cancelled, market_id is None
+		/// if orders in all markets are cancelled
+		AllOrdersCancelled { account_id: U256, market_id: Option<u128>, timestamp: u64 },
 	}
 
 	// Pallet callable functions
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// External function to populate market to user map
-		#[pallet::weight(0)]
-		pub fn update_market_to_user_map(origin: OriginFor<T>, market_id: u128) -> DispatchResult {
+		// The call iterates over every position, so the caller passes an upper bound on the
+		// number of positions which is charged upfront and the unused part is refunded
+		#[pallet::weight(T::WeightInfo::update_market_to_user_map(*positions_count))]
+		pub fn update_market_to_user_map(
+			origin: OriginFor<T>,
+			market_id: u128,
+			positions_count: u32,
+		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 
+			let mut positions_iterated: u32 = 0;
 			for (account_id, (market, direction), _) in PositionsMap::<T>::iter() {
+				positions_iterated += 1;
+				ensure!(positions_iterated <= positions_count, Error::<T>::TooManyPositions);
+
 				if market_id == market {
 					MarketToAccountMap::<T>::set(
 						(market_id, direction),
@@ -392,11 +548,18 @@ pub mod pallet {
 				}
 			}
 
-			Ok(())
+			Ok(Some(T::WeightInfo::update_market_to_user_map(positions_iterated)).into())
 		}
 
 		/// External function to be called for trade execution
-		#[pallet::weight(0)]
+		// Loss of a bankrupt account can be socialised only in a batch with a forced order
+		#[pallet::weight(T::WeightInfo::execute_trade(orders.len() as u32).saturating_add(
+			if orders.iter().any(|order| order.order_type == OrderType::Forced) {
+				T::WeightInfo::socialise_loss(LOSS_SOCIALISATION_COUNT)
+			} else {
+				Weight::zero()
+			}
+		))]
 		pub fn execute_trade(
 			origin: OriginFor<T>,
 			batch_id: U256,
@@ -425,7 +588,7 @@ pub mod pallet {
 			let market = T::MarketPallet::get_market(market_id);
 			ensure!(market.is_some(), Error::<T>::TradeBatchError509);
 			let market = market.unwrap();
-			ensure!(market.is_tradable == true, Error::<T>::TradeBatchError509);
+			ensure!(market.state.is_tradable(), Error::<T>::TradeBatchError509);
 
 			let tick_precision = market.tick_precision;
 
@@ -482,6 +645,9 @@ pub mod pallet {
 				InitialMarginMap::<T>::get((market_id, Direction::Short));
 			let mut min_timestamp: u64 = batch_timestamp;
 			let mut maker_error_codes = Vec::<u16>::new();
+			let mut total_maker_rebate: FixedI128 = FixedI128::zero();
+			// Makers filled against a forced order are not credited any rebate
+			let is_rebate_allowed = taker_order.order_type != OrderType::Forced;
 
 			for (index, element) in orders.iter().enumerate() {
 				let mut margin_amount: FixedI128;
@@ -618,6 +784,9 @@ pub mod pallet {
 					order_side = OrderSide::Maker;
 				} else {
 					// Taker Order
+					// A conditional order can be executed only once it is triggered
+					Self::validate_trigger_price(element)?;
+
 					let validation_response = Self::validate_taker(
 						orders[0].direction,
 						orders[0].side,
@@ -666,6 +835,13 @@ pub mod pallet {
 
 				new_portion_executed = order_portion_executed + quantity_to_execute;
 
+				// Rebates credited to the makers are paid out of the fee of the taker
+				let maker_rebate = if element.order_id == taker_order.order_id {
+					total_maker_rebate
+				} else {
+					FixedI128::zero()
+				};
+
 				let mut is_final: bool;
 				// BUY order
 				if element.side == Side::Buy {
@@ -680,6 +856,9 @@ pub mod pallet {
 						collateral_token_decimal,
 						&position_details,
 						&market_fees,
+						OpenInterestMap::<T>::get(market_id) + open_interest,
+						is_rebate_allowed,
+						maker_rebate,
 					);
 					match response {
 						Ok((
@@ -751,6 +930,15 @@ pub mod pallet {
 							(element.account_id, 0),
 						);
 
+						// Margin mode of the position is decided by the order opening it
+						if element.is_isolated {
+							IsolatedPositionsMap::<T>::insert(
+								element.account_id,
+								(market_id, element.direction),
+								true,
+							);
+						}
+
 						let opposite_direction = Self::get_opposite_direction(element.direction);
 						let opposite_position = PositionsMap::<T>::get(
 							&element.account_id,
@@ -829,6 +1017,8 @@ pub mod pallet {
 						collateral_token_decimal,
 						&position_details,
 						&market_fees,
+						is_rebate_allowed,
+						maker_rebate,
 					);
 					match response {
 						Ok((
@@ -886,9 +1076,14 @@ pub mod pallet {
 
 					let force_closure_flag =
 						ForceClosureFlagMap::<T>::get(element.account_id, collateral_id);
+					let is_isolated = IsolatedPositionsMap::<T>::get(
+						element.account_id,
+						(market_id, element.direction),
+					);
 					// Deleveraging case, update deleveragable position and force closure flag
 					// accordingly
-					if force_closure_flag.is_some() &&
+					if !is_isolated &&
+						force_closure_flag.is_some() &&
 						force_closure_flag.unwrap() == ForceClosureFlag::Deleverage
 					{
 						let deleveragable_amount =
@@ -932,6 +1127,11 @@ pub mod pallet {
 							(market_id, element.direction),
 							element.account_id,
 						);
+						Self::remove_isolated_flags(
+							element.account_id,
+							market_id,
+							element.direction,
+						);
 
 						let opposite_direction = Self::get_opposite_direction(element.direction);
 						let opposite_position = PositionsMap::<T>::get(
@@ -950,11 +1150,11 @@ pub mod pallet {
 								&markets,
 							);
 
-							// If force closure flag is liquidation and if all positions are closed,
-							// it means that liquidation is complete
+							// If force closure flag is liquidation and if all positions apart from
+							// the isolated ones are closed, it means that liquidation is complete
 							if force_closure_flag.is_some() &&
 								force_closure_flag.unwrap() == ForceClosureFlag::Liquidate &&
-								markets.is_empty()
+								Self::are_all_positions_isolated(element.account_id, &markets)
 							{
 								// Remove the liquidation flag and check for deferred deposits
 								Self::reset_force_closure_flags(element.account_id, collateral_id)?;
@@ -1056,7 +1256,11 @@ pub mod pallet {
 					}
 				}
 
-				// Store the trading fee
+				if element.order_id != taker_order.order_id && fee.is_negative() {
+					total_maker_rebate = total_maker_rebate - fee;
+				}
+
+				// Store the trading fee, net of the rebates credited to the makers
 				let current_trading_fee = TradingFeeMap::<T>::get(collateral_id);
 				TradingFeeMap::<T>::insert(collateral_id, current_trading_fee + fee);
 
@@ -1123,7 +1327,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To add origin restriction in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_liquidator_signer())]
 		pub fn add_liquidator_signer(origin: OriginFor<T>, pub_key: U256) -> DispatchResult {
 			ensure_root(origin)?;
 
@@ -1141,7 +1345,7 @@ pub mod pallet {
 		}
 
 		// TODO(merkle-groot): To add origin restriction in production
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::remove_liquidator_signer())]
 		pub fn remove_liquidator_signer(origin: OriginFor<T>, pub_key: U256) -> DispatchResult {
 			ensure_root(origin)?;
 
@@ -1158,25 +1362,114 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
-		pub fn cancel_order(origin: OriginFor<T>, order_id: U256) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, cancel_request: CancelRequest) -> DispatchResult {
 			ensure_signed(origin)?;
 
-			// TODO: Add signature verification
+			let account_id = cancel_request.account_id;
+			let order_id = cancel_request.order_id;
 
 			// The order_id cannot be 0
 			ensure!(order_id != U256::zero(), Error::<T>::ZeroOrderId);
 
-			let (order_portion_executed, _) = OrderStateMap::<T>::get(order_id);
+			// Validate that the user is registered
+			let is_registered = T::TradingAccountPallet::is_registered_user(account_id);
+			ensure!(is_registered, Error::<T>::CancelAccountNotRegistered);
+
+			// Check if the cancel request is signed by the account
+			Self::verify_request_signature(
+				&cancel_request,
+				&account_id,
+				&cancel_request.hash_type,
+				&cancel_request.sig_r,
+				&cancel_request.sig_s,
+			)?;
+
+			// If the order has already been executed, its owner is known and only the owner
+			// can cancel it
+			if let Some(owner) = OrderAccountMap::<T>::get(order_id) {
+				ensure!(owner == account_id, Error::<T>::OrderAccountMismatch);
+
+				let (order_portion_executed, _) = OrderStateMap::<T>::get(order_id);
+				OrderStateMap::<T>::insert(order_id, (order_portion_executed, true));
+			}
+
+			// Cancellations are keyed by the account that signed them, so that a cancel request
+			// of one account never affects an order of another account
+			if !CancelledOrdersMap::<T>::get(account_id, order_id) {
+				CancelledOrdersMap::<T>::insert(account_id, order_id, true);
+
+				// Track the cancellation so that it is removed in perform_cleanup, even if the
+				// order never gets executed
+				let current_timestamp: u64 = T::TimeProvider::now().as_secs();
+				CancellationsMap::<T>::append(current_timestamp, (account_id, order_id));
+
+				let start_timestamp = StartTimestamp::<T>::get();
+				if (start_timestamp.is_some() && current_timestamp < start_timestamp.unwrap()) ||
+					start_timestamp.is_none()
+				{
+					StartTimestamp::<T>::put(current_timestamp);
+				}
+			}
 
-			// Mark the order as cancelled order
-			OrderStateMap::<T>::insert(order_id, (order_portion_executed, true));
+			Self::deposit_event(Event::OrderCancelled { account_id, order_id });
 
 			// Return ok
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::cancel_all_orders())]
+		pub fn cancel_all_orders(
+			origin: OriginFor<T>,
+			cancel_all_request: CancelAllRequest,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let account_id = cancel_all_request.account_id;
+			let market_id = cancel_all_request.market_id;
+			let timestamp = cancel_all_request.timestamp;
+
+			// Validate that the user is registered
+			let is_registered = T::TradingAccountPallet::is_registered_user(account_id);
+			ensure!(is_registered, Error::<T>::CancelAccountNotRegistered);
+
+			// Validate market
+			if let Some(id) = market_id {
+				ensure!(T::MarketPallet::get_market(id).is_some(), Error::<T>::TradeBatchError509);
+			}
+
+			// Check if the cancel request is signed by the account
+			Self::verify_request_signature(
+				&cancel_all_request,
+				&account_id,
+				&cancel_all_request.hash_type,
+				&cancel_all_request.sig_r,
+				&cancel_all_request.sig_s,
+			)?;
+
+			// Cancel-before timestamp can only move forward, so that replaying an older request
+			// has no effect
+			match market_id {
+				Some(id) => {
+					let current_timestamp = MarketCancelBeforeMap::<T>::get(account_id, id);
+					if timestamp > current_timestamp {
+						MarketCancelBeforeMap::<T>::insert(account_id, id, timestamp);
+					}
+				},
+				None => {
+					let current_timestamp = CancelBeforeMap::<T>::get(account_id);
+					if timestamp > current_timestamp {
+						CancelBeforeMap::<T>::insert(account_id, timestamp);
+					}
+				},
+			}
+
+			Self::deposit_event(Event::AllOrdersCancelled { account_id, market_id, timestamp });
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::perform_cleanup())]
 		pub fn perform_cleanup(origin: OriginFor<T>) -> DispatchResult {
 			// Make sure the caller is from a signed origin
 			ensure_signed(origin)?;
@@ -1205,9 +1498,18 @@ pub mod pallet {
 					for order in orders.unwrap() {
 						OrderStateMap::<T>::remove(order);
 						OrderHashMap::<T>::remove(order);
+						OrderAccountMap::<T>::remove(order);
 					}
 					OrdersMap::<T>::remove(timestamp);
 				}
+
+				let cancellations = CancellationsMap::<T>::get(timestamp);
+				if cancellations.is_some() {
+					for (account_id, order_id) in cancellations.unwrap() {
+						CancelledOrdersMap::<T>::remove(account_id, order_id);
+					}
+					CancellationsMap::<T>::remove(timestamp);
+				}
 				cleanup_count -= 1;
 			}
 			if start_timestamp < timestamp_limit {
@@ -1217,7 +1519,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::set_matching_time_limit())]
 		pub fn set_matching_time_limit(origin: OriginFor<T>, time_limit: u64) -> DispatchResult {
 			// Make sure the caller is a sudo user
 			ensure_root(origin)?;
@@ -1225,11 +1527,11 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::close_delisted_market_positions(POSITIONS_CLOSE_COUNT))]
 		pub fn close_delisted_market_positions(
 			origin: OriginFor<T>,
 			market_id: u128,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			// Make sure the caller is from a signed origin
 			ensure_signed(origin)?;
 
@@ -1237,8 +1539,8 @@ pub mod pallet {
 			let market = T::MarketPallet::get_market(market_id);
 			ensure!(market.is_some(), Error::<T>::TradeBatchError509);
 			let market = market.unwrap();
-			// Market should be non tradable
-			ensure!(market.is_tradable == false, Error::<T>::TradeBatchError509);
+			// Market should be in the process of delisting
+			ensure!(market.state == MarketState::Delisting, Error::<T>::TradeBatchError509);
 
 			// Get collateral_token_decimal
 			let collateral_asset = T::AssetPallet::get_asset(market.asset_collateral).unwrap();
@@ -1369,11 +1671,245 @@ pub mod pallet {
 				initial_margin_locked_short,
 			);
 
+			// Refund the weight of the positions that were not closed
+			let positions_closed = POSITIONS_CLOSE_COUNT - positions_close_count;
+			Ok(Some(T::WeightInfo::close_delisted_market_positions(positions_closed)).into())
+		}
+
+		#[pallet::weight(T::WeightInfo::flag_liquidatable_accounts(accounts.len() as u32))]
+		pub fn flag_liquidatable_accounts(
+			origin: OriginFor<T>,
+			accounts: Vec<(U256, u128)>,
+		) -> DispatchResult {
+			// Make sure the caller is from a signed origin
+			ensure_signed(origin)?;
+
+			ensure!(
+				accounts.len() as u32 <= ACCOUNTS_SCAN_COUNT,
+				Error::<T>::TooManyAccountsToFlag
+			);
+
+			for (account_id, collateral_id) in accounts {
+				// Accounts and isolated positions which are already flagged are known to the
+				// liquidators
+				// Margin of the account is checked again at the current mark prices, so that the
+				// flags are set only if the account is still under the maintenance requirement
+				if !ForceClosureFlagMap::<T>::contains_key(account_id, collateral_id) {
+					if let Some((market_id, direction)) =
+						Self::get_cross_margin_position(account_id, collateral_id)
+					{
+						T::RiskManagementPallet::check_for_force_closure(
+							account_id,
+							collateral_id,
+							market_id,
+							direction,
+						);
+					}
+				}
+
+				// Isolated positions are checked against their own margin
+				for (market_id, direction) in
+					Self::get_unflagged_isolated_positions(account_id, collateral_id)
+				{
+					T::RiskManagementPallet::check_for_force_closure(
+						account_id,
+						collateral_id,
+						market_id,
+						direction,
+					);
+				}
+			}
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_loss_socialisation())]
+		pub fn set_loss_socialisation(origin: OriginFor<T>, is_enabled: bool) -> DispatchResult {
+			// Make sure the caller is a sudo user
+			ensure_root(origin)?;
+			IsLossSocialisationEnabled::<T>::put(is_enabled);
+
+			Self::deposit_event(Event::LossSocialisationUpdated { is_enabled });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::sweep_fees())]
+		pub fn sweep_fees(
+			origin: OriginFor<T>,
+			collateral_id: u128,
+			amount: FixedI128,
+			destination: FeeSweepDestination,
+		) -> DispatchResult {
+			// Make sure the caller is a sudo user
+			ensure_root(origin)?;
+
+			// Rest of the accrued fees is already credited to the insurance funds, the fee
+			// recipients and the master accounts
+			let unallocated_revenue =
+				T::TradingAccountPallet::get_unallocated_revenue(collateral_id);
+			ensure!(
+				amount > FixedI128::zero() && amount <= unallocated_revenue,
+				Error::<T>::InvalidSweepAmount
+			);
+
+			match destination {
+				FeeSweepDestination::TradingAccount(account_id) => {
+					ensure!(
+						T::TradingAccountPallet::is_registered_user(account_id),
+						Error::<T>::TreasuryAccountNotFound
+					);
+					// Swept fees are accrued per collateral, not per market
+					T::TradingAccountPallet::transfer_collateral(
+						account_id,
+						collateral_id,
+						amount,
+						BalanceChangeReason::FeeSweep,
+					);
+				},
+				FeeSweepDestination::InsuranceFund(insurance_fund) => {
+					T::TradingAccountPallet::deposit_to_insurance_fund(
+						insurance_fund,
+						collateral_id,
+						amount,
+						BalanceChangeReason::FeeSweep,
+					);
+				},
+			}
+
+			T::TradingAccountPallet::update_unallocated_revenue(
+				collateral_id,
+				FixedI128::zero() - amount,
+			);
+
+			Self::deposit_event(Event::FeesSwept { collateral_id, amount, destination });
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn remove_isolated_flags(account_id: U256, market_id: u128, direction: Direction) {
+			IsolatedPositionsMap::<T>::remove(account_id, (market_id, direction));
+			IsolatedForceClosureMap::<T>::remove(account_id, (market_id, direction));
+		}
+
+		// Returns the first position of the account which is part of its cross margin
+		fn get_cross_margin_position(
+			account_id: U256,
+			collateral_id: u128,
+		) -> Option<(u128, Direction)> {
+			for market_id in CollateralToMarketMap::<T>::get(account_id, collateral_id) {
+				for direction in [Direction::Long, Direction::Short] {
+					let position = PositionsMap::<T>::get(account_id, (market_id, direction));
+					if position.size != FixedI128::zero() &&
+						!IsolatedPositionsMap::<T>::get(account_id, (market_id, direction))
+					{
+						return Some((market_id, direction))
+					}
+				}
+			}
+			None
+		}
+
+		// Returns the isolated positions of the account in the given collateral, which are not
+		// flagged yet
+		fn get_unflagged_isolated_positions(
+			account_id: U256,
+			collateral_id: u128,
+		) -> Vec<(u128, Direction)> {
+			let mut isolated_positions = Vec::<(u128, Direction)>::new();
+			for market_id in CollateralToMarketMap::<T>::get(account_id, collateral_id) {
+				for direction in [Direction::Long, Direction::Short] {
+					let position = PositionsMap::<T>::get(account_id, (market_id, direction));
+					if position.size != FixedI128::zero() &&
+						IsolatedPositionsMap::<T>::get(account_id, (market_id, direction)) &&
+						!IsolatedForceClosureMap::<T>::get(account_id, (market_id, direction))
+					{
+						isolated_positions.push((market_id, direction));
+					}
+				}
+			}
+			isolated_positions
+		}
+
+		// Returns the accounts in the given index range, along with their collateral, whose cross
+		// margin or one of the isolated positions is liquidatable at the current mark prices and
+		// is not flagged yet
+		// At most ACCOUNTS_SCAN_COUNT pairs are returned, so it also returns the index of the
+		// account from which the next scan is to start
+		fn get_liquidatable_accounts(
+			start_index: u128,
+			end_index: u128,
+		) -> (Vec<(U256, u128)>, u128) {
+			let mut liquidatable_accounts = Vec::<(U256, u128)>::new();
+			let mut next_index = start_index;
+			for account_id in T::TradingAccountPallet::get_account_list(start_index, end_index) {
+				let mut account_pairs = Vec::<(U256, u128)>::new();
+				for (collateral_id, markets) in CollateralToMarketMap::<T>::iter_prefix(account_id)
+				{
+					if markets.is_empty() {
+						continue
+					}
+
+					let mut is_liquidatable = false;
+					if !ForceClosureFlagMap::<T>::contains_key(account_id, collateral_id) {
+						let (is_liquidation, _, _, _, _, _) =
+							T::TradingAccountPallet::get_margin_info(
+								account_id,
+								collateral_id,
+								FixedI128::zero(),
+								FixedI128::zero(),
+							);
+						is_liquidatable = is_liquidation;
+					}
+
+					if !is_liquidatable {
+						is_liquidatable =
+							Self::get_unflagged_isolated_positions(account_id, collateral_id)
+								.iter()
+								.any(|&(market_id, direction)| {
+									let (is_liquidation, _, _) =
+										T::TradingAccountPallet::get_isolated_margin_info(
+											account_id,
+											market_id,
+											direction,
+											FixedI128::zero(),
+											FixedI128::zero(),
+										);
+									is_liquidation
+								});
+					}
+
+					if is_liquidatable {
+						account_pairs.push((account_id, collateral_id));
+					}
+				}
+
+				// Pairs of an account are returned together, so if they do not fit in this scan
+				// the account is carried over to the next scan
+				let remaining = ACCOUNTS_SCAN_COUNT as usize - liquidatable_accounts.len();
+				if account_pairs.len() > remaining {
+					if !liquidatable_accounts.is_empty() {
+						return (liquidatable_accounts, next_index)
+					}
+					account_pairs.truncate(remaining);
+				}
+				liquidatable_accounts.append(&mut account_pairs);
+				next_index += 1;
+			}
+			(liquidatable_accounts, next_index)
+		}
+
+		// Isolated positions are not force closed by the liquidation of the account
+		fn are_all_positions_isolated(account_id: U256, markets: &Vec<u128>) -> bool {
+			markets.iter().all(|&market_id| {
+				[Direction::Long, Direction::Short].iter().all(|&direction| {
+					let position = PositionsMap::<T>::get(account_id, (market_id, direction));
+					position.size == FixedI128::zero() ||
+						IsolatedPositionsMap::<T>::get(account_id, (market_id, direction))
+				})
+			})
+		}
+
 		fn reset_force_closure_flags(account_id: U256, collateral_id: u128) -> DispatchResult {
 			// Reset the flag
 			ForceClosureFlagMap::<T>::remove(account_id, collateral_id);
@@ -1406,9 +1942,20 @@ pub mod pallet {
 					order.direction,
 				);
 
-				let force_closure_flag =
-					ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
-				ensure!(force_closure_flag.is_some(), Error::<T>::TradeBatchError540);
+				// An isolated position can be force closed only if it is flagged on its own
+				if IsolatedPositionsMap::<T>::get(order.account_id, (market_id, order.direction)) {
+					ensure!(
+						IsolatedForceClosureMap::<T>::get(
+							order.account_id,
+							(market_id, order.direction)
+						),
+						Error::<T>::TradeBatchError540
+					);
+				} else {
+					let force_closure_flag =
+						ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
+					ensure!(force_closure_flag.is_some(), Error::<T>::TradeBatchError540);
+				}
 			}
 
 			let quantity_response = Self::calculate_quantity_to_execute(
@@ -1440,7 +1987,12 @@ pub mod pallet {
 			if order.side == Side::Buy {
 				Ok(quantity_to_execute)
 			} else {
-				if order.order_type == OrderType::Forced {
+				// Isolated positions are never deleveraged
+				if order.order_type == OrderType::Forced &&
+					!IsolatedPositionsMap::<T>::get(
+						order.account_id,
+						(order.market_id, order.direction),
+					) {
 					let force_closure_flag =
 						ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
 
@@ -1479,19 +2031,52 @@ pub mod pallet {
 			ensure!(is_registered, Error::<T>::TradeBatchError510);
 
 			// Check whether the order is a cancelled order
-			let (_, is_cancelled) = OrderStateMap::<T>::get(order.order_id);
-			ensure!(!is_cancelled, Error::<T>::TradeBatchError543);
+			// A cancellation is honoured only if it was signed by the owner of the order
+			ensure!(
+				!CancelledOrdersMap::<T>::get(order.account_id, order.order_id),
+				Error::<T>::TradeBatchError543
+			);
+
+			// Check whether the order was placed before a cancel all orders request of the user
+			// Forced orders are placed by liquidators, hence they are not affected
+			if order.order_type != OrderType::Forced {
+				let cancel_before = max(
+					CancelBeforeMap::<T>::get(order.account_id),
+					MarketCancelBeforeMap::<T>::get(order.account_id, order.market_id),
+				);
+				ensure!(order.timestamp >= cancel_before, Error::<T>::TradeBatchError549);
+			}
 
 			// Check whether the order is older than 4 weeks
 			let timestamp_limit = current_timestamp - FOUR_WEEKS;
 			ensure!(order.timestamp >= timestamp_limit, Error::<T>::TradeBatchError544);
 
+			// Check whether a GTT order has expired
+			// Expiry is in milliseconds, like the timestamp of the order
+			if order.time_in_force == TimeInForce::GTT {
+				ensure!(order.expiry > order.timestamp, Error::<T>::TradeBatchError560);
+				let current_timestamp_ms = T::TimeProvider::now().as_millis() as u64;
+				ensure!(current_timestamp_ms <= order.expiry, Error::<T>::TradeBatchError556);
+			}
+
 			// Validate that if force closure flag is set
 			// order type can only be 'Forced'
 			if order.order_type != OrderType::Forced {
 				let force_closure_flag =
 					ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
 				ensure!(force_closure_flag.is_none(), Error::<T>::TradeBatchError539);
+				ensure!(
+					!IsolatedForceClosureMap::<T>::get(
+						order.account_id,
+						(market.id, order.direction)
+					),
+					Error::<T>::TradeBatchError539
+				);
+			}
+
+			// Orders opening a position are rejected in a reduce only market
+			if market.state == MarketState::ReduceOnly {
+				ensure!(order.side == Side::Sell, Error::<T>::TradeBatchError559);
 			}
 
 			// Validate that size of BUY order is >= min quantity for market
@@ -1518,6 +2103,29 @@ pub mod pallet {
 				Error::<T>::TradeBatchError502
 			);
 
+			// Reduce only order can only close an existing position; the size of a sell order
+			// is capped to the position size, so the position cannot be flipped
+			if order.reduce_only {
+				ensure!(order.side == Side::Sell, Error::<T>::TradeBatchError552);
+				let position_details =
+					PositionsMap::<T>::get(&order.account_id, (market.id, order.direction));
+				ensure!(position_details.size > FixedI128::zero(), Error::<T>::TradeBatchError553);
+			}
+
+			// An order cannot change the margin mode of an existing position
+			if order.side == Side::Buy {
+				let position_details =
+					PositionsMap::<T>::get(&order.account_id, (market.id, order.direction));
+				ensure!(
+					position_details.size == FixedI128::zero() ||
+						IsolatedPositionsMap::<T>::get(
+							&order.account_id,
+							(market.id, order.direction)
+						) == order.is_isolated,
+					Error::<T>::TradeBatchError554
+				);
+			}
+
 			Self::validate_signature(&order)?;
 
 			Ok(())
@@ -1569,6 +2177,42 @@ pub mod pallet {
 			let is_success = Self::order_hash_check(order.order_id, order_hash_u256);
 			ensure!(is_success, Error::<T>::TradeBatchError541);
 
+			// Order hash covers the account id, so the order is owned by this account
+			OrderAccountMap::<T>::insert(order.order_id, order.account_id);
+
+			Ok(())
+		}
+
+		fn verify_request_signature<R: Hashable>(
+			request: &R,
+			account_id: &U256,
+			hash_type: &HashType,
+			sig_r: &U256,
+			sig_s: &U256,
+		) -> Result<(), Error<T>> {
+			// Hash the request
+			let request_hash =
+				request.hash(hash_type).map_err(|_| Error::<T>::InvalidCancelRequestHash)?;
+
+			// Convert to FieldElement
+			let (sig_r_felt, sig_s_felt) = sig_u256_to_sig_felt(sig_r, sig_s)
+				.map_err(|_| Error::<T>::InvalidCancelRequestSignature)?;
+
+			let sig = Signature { r: sig_r_felt, s: sig_s_felt };
+
+			// Fetch the public key of the account
+			let public_key_felt = T::TradingAccountPallet::get_public_key(account_id)
+				.and_then(|key| key.try_to_felt().ok())
+				.ok_or(Error::<T>::InvalidCancelRequestPublicKey)?;
+
+			let verification_result = ecdsa_verify(&public_key_felt, &request_hash, &sig);
+
+			// Signature verification returned error or false
+			ensure!(
+				verification_result.is_ok() && verification_result.unwrap(),
+				Error::<T>::CancelRequestSignatureNotVerified
+			);
+
 			Ok(())
 		}
 
@@ -1594,7 +2238,7 @@ pub mod pallet {
 
 			ensure!(order_type == OrderType::Limit, Error::<T>::TradeBatchError518);
 
-			if taker_order.order_type == OrderType::Limit {
+			if taker_order.order_type.is_limit() {
 				// Check whether the maker price is valid with respect to taker limit price
 				Self::validate_limit_price(
 					taker_order.price,
@@ -1602,7 +2246,7 @@ pub mod pallet {
 					taker_order.direction,
 					taker_order.side,
 				)?;
-			} else if taker_order.order_type == OrderType::Market {
+			} else if taker_order.order_type.is_market() {
 				// Check whether the maker price is valid with respect to taker slippage
 				Self::validate_within_slippage(
 					taker_order.slippage,
@@ -1626,7 +2270,7 @@ pub mod pallet {
 			order_type: OrderType,
 			slippage: FixedI128,
 		) -> Result<(), Error<T>> {
-			if order_type == OrderType::Market {
+			if order_type.is_market() {
 				ensure!(
 					slippage >= FixedI128::zero() &&
 						slippage <= FixedI128::from_inner(150000000000000000),
@@ -1649,6 +2293,34 @@ pub mod pallet {
 			Ok(())
 		}
 
+		fn validate_trigger_price(order: &Order) -> Result<(), Error<T>> {
+			if !order.order_type.is_conditional() {
+				return Ok(())
+			}
+
+			ensure!(order.trigger_price > FixedI128::zero(), Error::<T>::TradeBatchError550);
+
+			// The order cannot be triggered if the mark price is not available
+			let mark_price = T::PricesPallet::get_mark_price(order.market_id);
+			ensure!(mark_price > FixedI128::zero(), Error::<T>::TradeBatchError551);
+
+			// Long buy and short sell orders buy the asset, the others sell it
+			let is_buy = (order.direction == Direction::Long && order.side == Side::Buy) ||
+				(order.direction == Direction::Short && order.side == Side::Sell);
+
+			// Stop orders are triggered when the price moves against the order,
+			// take profit orders when it moves in favour of the order
+			let is_triggered = match (order.order_type, is_buy) {
+				(OrderType::StopMarket | OrderType::StopLimit, true) |
+				(OrderType::TakeProfitMarket | OrderType::TakeProfitLimit, false) =>
+					mark_price >= order.trigger_price,
+				_ => mark_price <= order.trigger_price,
+			};
+			ensure!(is_triggered, Error::<T>::TradeBatchError551);
+
+			Ok(())
+		}
+
 		fn validate_limit_price(
 			price: FixedI128,
 			execution_price: FixedI128,
@@ -1705,6 +2377,9 @@ pub mod pallet {
 			collateral_token_decimal: u8,
 			position_details: &Position,
 			market_fees: &BaseFeeAggregate,
+			current_open_interest: FixedI128,
+			is_rebate_allowed: bool,
+			maker_rebate: FixedI128,
 		) -> Result<(FixedI128, FixedI128, FixedI128, FixedI128, FixedI128, FixedI128), Error<T>> {
 			let margin_amount: FixedI128;
 			let borrowed_amount: FixedI128;
@@ -1728,12 +2403,41 @@ pub mod pallet {
 				Error::<T>::TradeBatchError548
 			);
 
+			// Opening fills cannot take the open interest of the market above its cap
+			if let Some(max_open_interest) = market.max_open_interest {
+				ensure!(
+					current_open_interest + order_size <= max_open_interest,
+					Error::<T>::TradeBatchError555
+				);
+			}
+
+			// Larger positions are allowed a lower leverage, as per the size tier of the market
+			ensure!(
+				order.leverage <= get_max_leverage(&market, position_details.size + order_size),
+				Error::<T>::TradeBatchError557
+			);
+
 			let leveraged_order_value = order_size * execution_price;
 			let margin_order_value = leveraged_order_value / order.leverage;
 			let amount_to_be_borrowed = leveraged_order_value - margin_order_value;
 			margin_amount = position_details.margin_amount + margin_order_value;
 			borrowed_amount = position_details.borrowed_amount + amount_to_be_borrowed;
 
+			// Margin of the whole resulting position has to cover the initial margin of its size
+			// tier, including the part opened earlier at a higher leverage
+			if has_size_tiers(&market) {
+				let position_size = position_details.size + order_size;
+				let required_initial_margin =
+					(get_initial_margin_fraction(&market, position_size) *
+						position_size * average_execution_price)
+						.round_to_precision(collateral_token_decimal.into());
+				ensure!(
+					margin_amount.round_to_precision(collateral_token_decimal.into()) >=
+						required_initial_margin,
+					Error::<T>::TradeBatchError557
+				);
+			}
+
 			// Check if the position can be opened
 			let (available_margin, is_liquidation) = T::RiskManagementPallet::check_for_risk(
 				order,
@@ -1755,13 +2459,17 @@ pub mod pallet {
 				)
 				.or_else(|_| Err(Error::<T>::TradeBatchError546))?;
 
-			let (fee_rate, _) = Self::get_fee_rate(
+			let (mut fee_rate, _) = Self::get_fee_rate(
 				order.account_id,
+				order.market_id,
 				&market_fees,
 				Side::Buy,
 				order_side,
 				total_30day_volume,
 			);
+			if !is_rebate_allowed && fee_rate.is_negative() {
+				fee_rate = FixedI128::zero();
+			}
 
 			let mut fee = fee_rate * current_volume;
 			fee = fee.round_to_precision(collateral_token_decimal.into());
@@ -1776,23 +2484,15 @@ pub mod pallet {
 			);
 
 			ensure!(fee <= available_margin, Error::<T>::TradeBatchError501);
-			if fee != FixedI128::zero() {
-				T::TradingAccountPallet::transfer_from(
-					order.account_id,
-					collateral_id,
-					order.market_id,
-					fee,
-					BalanceChangeReason::Fee,
-				);
-
-				T::TradingAccountPallet::handle_fee_split(
-					order.account_id,
-					collateral_id,
-					order.market_id,
-					fee,
-					fee_share_amount,
-				);
-			}
+			Self::ensure_maker_rebate_is_covered(fee, fee_share_amount, maker_rebate)?;
+			Self::settle_fee(
+				order.account_id,
+				collateral_id,
+				order.market_id,
+				fee,
+				fee_share_amount,
+				maker_rebate,
+			);
 
 			Ok((
 				margin_amount,
@@ -1813,6 +2513,8 @@ pub mod pallet {
 			collateral_token_decimal: u8,
 			position_details: &Position,
 			market_fees: &BaseFeeAggregate,
+			is_rebate_allowed: bool,
+			maker_rebate: FixedI128,
 		) -> Result<
 			(FixedI128, FixedI128, FixedI128, FixedI128, FixedI128, FixedI128, FixedI128),
 			Error<T>,
@@ -1848,7 +2550,16 @@ pub mod pallet {
 			let borrowed_amount: FixedI128;
 			let margin_amount: FixedI128;
 
-			let force_closure_flag = ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
+			// Isolated positions are never deleveraged, they are force closed only by liquidation
+			let is_isolated = IsolatedPositionsMap::<T>::get(
+				order.account_id,
+				(order.market_id, order.direction),
+			);
+			let force_closure_flag = if is_isolated {
+				Some(ForceClosureFlag::Liquidate)
+			} else {
+				ForceClosureFlagMap::<T>::get(order.account_id, collateral_id)
+			};
 			if force_closure_flag.is_some() &&
 				force_closure_flag.unwrap() == ForceClosureFlag::Deleverage
 			{
@@ -1867,6 +2578,13 @@ pub mod pallet {
 
 			// If the order type is Liquidate or Deleverage
 			if order.order_type == OrderType::Forced {
+				// Loss of an isolated position can be covered only by its own margin
+				let balance = if is_isolated {
+					FixedI128::min(balance, margin_amount_to_reduce)
+				} else {
+					balance
+				};
+
 				// Check if user is under water, ie,
 				// user has lost some borrowed funds
 				if margin_plus_pnl.is_negative() {
@@ -1875,16 +2593,12 @@ pub mod pallet {
 					// Check if user's balance can cover the deficit
 					if pnl_abs > balance {
 						if balance.is_negative() {
-							// Complete funds lost by user should be taken from insurance fund
-							T::TradingAccountPallet::handle_insurance_fund_update(
+							// Complete funds lost by user should be covered by the insurance fund
+							Self::handle_bankruptcy(
+								order,
 								collateral_id,
-								order.market_id,
+								collateral_token_decimal,
 								pnl_abs,
-								FundModifyType::Decrease,
-							);
-							LiquidationFeeMap::<T>::insert(
-								collateral_id,
-								current_liquidation_fee - pnl_abs,
 							);
 							Self::deposit_event(Event::LiquidationPNL {
 								account_id: order.account_id,
@@ -1895,17 +2609,12 @@ pub mod pallet {
 							});
 						} else {
 							// Some amount of lost funds can be taken from user available balance
-							// Rest of the funds should be taken from insurance fund
-							T::TradingAccountPallet::handle_insurance_fund_update(
+							// Rest of the funds should be covered by the insurance fund
+							Self::handle_bankruptcy(
+								order,
 								collateral_id,
-								order.market_id,
+								collateral_token_decimal,
 								pnl_abs - balance,
-								FundModifyType::Decrease,
-							);
-
-							LiquidationFeeMap::<T>::insert(
-								collateral_id,
-								current_liquidation_fee - (pnl_abs - balance),
 							);
 							Self::deposit_event(Event::LiquidationPNL {
 								account_id: order.account_id,
@@ -1917,19 +2626,23 @@ pub mod pallet {
 						}
 					}
 
-					// Deduct loss from user
-					T::TradingAccountPallet::transfer_from(
-						order.account_id,
-						collateral_id,
-						order.market_id,
+					// Deduct loss from user, the rest of the loss is covered above so that the
+					// balance does not go below zero
+					let loss = FixedI128::min(
 						pnl.saturating_abs(),
-						BalanceChangeReason::PnlRealization,
+						FixedI128::max(balance, FixedI128::zero()),
 					);
+					if loss != FixedI128::zero() {
+						T::TradingAccountPallet::transfer_from(
+							order.account_id,
+							collateral_id,
+							order.market_id,
+							loss,
+							BalanceChangeReason::PnlRealization,
+						);
+					}
 				} else {
 					// User is not underwater, order type is Liquidate or Deleverage
-					let force_closure_flag =
-						ForceClosureFlagMap::<T>::get(order.account_id, collateral_id);
-
 					// If order type is Forced, force closure flag will always be
 					// one of Deleverage or Liquidate
 					match force_closure_flag.unwrap() {
@@ -1958,17 +2671,12 @@ pub mod pallet {
 								});
 							} else {
 								if balance.is_negative() {
-									// Deduct margin_amount_to_reduce from insurance fund
-									T::TradingAccountPallet::handle_insurance_fund_update(
+									// Cover margin_amount_to_reduce by the insurance fund
+									Self::handle_bankruptcy(
+										order,
 										collateral_id,
-										order.market_id,
+										collateral_token_decimal,
 										margin_amount_to_reduce,
-										FundModifyType::Decrease,
-									);
-
-									LiquidationFeeMap::<T>::insert(
-										collateral_id,
-										current_liquidation_fee - margin_amount_to_reduce,
 									);
 
 									Self::deposit_event(Event::LiquidationPNL {
@@ -1982,17 +2690,12 @@ pub mod pallet {
 									// if user has some balance
 									let pnl_abs = pnl.saturating_abs();
 									if balance <= pnl_abs {
-										// Deduct (pnl_abs -  balance) from insurance fund
-										T::TradingAccountPallet::handle_insurance_fund_update(
+										// Cover (pnl_abs -  balance) by the insurance fund
+										Self::handle_bankruptcy(
+											order,
 											collateral_id,
-											order.market_id,
+											collateral_token_decimal,
 											pnl_abs - balance,
-											FundModifyType::Decrease,
-										);
-
-										LiquidationFeeMap::<T>::insert(
-											collateral_id,
-											current_liquidation_fee - (pnl_abs - balance),
 										);
 
 										Self::deposit_event(Event::LiquidationPNL {
@@ -2025,14 +2728,21 @@ pub mod pallet {
 									}
 								}
 							}
-							// Deduct proportionate margin amount from user
-							T::TradingAccountPallet::transfer_from(
-								order.account_id,
-								collateral_id,
-								order.market_id,
+							// Deduct proportionate margin amount from user, to the extent of the
+							// balance
+							let margin_lost = FixedI128::min(
 								margin_amount_to_reduce,
-								BalanceChangeReason::Liquidation,
+								FixedI128::max(balance, FixedI128::zero()),
 							);
+							if margin_lost != FixedI128::zero() {
+								T::TradingAccountPallet::transfer_from(
+									order.account_id,
+									collateral_id,
+									order.market_id,
+									margin_lost,
+									BalanceChangeReason::Liquidation,
+								);
+							}
 						},
 						ForceClosureFlag::Deleverage => {
 							pnl = FixedI128::zero();
@@ -2079,13 +2789,17 @@ pub mod pallet {
 				.or_else(|_| Err(Error::<T>::TradeBatchError546))?;
 
 			let fee = if order.order_type != OrderType::Forced {
-				let (fee_rate, _) = Self::get_fee_rate(
+				let (mut fee_rate, _) = Self::get_fee_rate(
 					order.account_id,
+					order.market_id,
 					&market_fees,
 					Side::Sell,
 					order_side,
 					total_30day_volume,
 				);
+				if !is_rebate_allowed && fee_rate.is_negative() {
+					fee_rate = FixedI128::zero();
+				}
 
 				let mut fee = fee_rate * current_volume;
 				fee = fee.round_to_precision(collateral_token_decimal.into());
@@ -2099,25 +2813,16 @@ pub mod pallet {
 					collateral_token_decimal,
 				);
 
-				// Deduct fee while closing a position
-				if fee != FixedI128::zero() {
-					// Transfer fee from the user
-					T::TradingAccountPallet::transfer_from(
-						order.account_id,
-						collateral_id,
-						order.market_id,
-						fee,
-						BalanceChangeReason::Fee,
-					);
-
-					T::TradingAccountPallet::handle_fee_split(
-						order.account_id,
-						collateral_id,
-						order.market_id,
-						fee,
-						fee_share_amount,
-					);
-				}
+				// Deduct fee (or credit rebate) while closing a position
+				Self::ensure_maker_rebate_is_covered(fee, fee_share_amount, maker_rebate)?;
+				Self::settle_fee(
+					order.account_id,
+					collateral_id,
+					order.market_id,
+					fee,
+					fee_share_amount,
+					maker_rebate,
+				);
 
 				fee
 			} else {
@@ -2135,6 +2840,58 @@ pub mod pallet {
 			))
 		}
 
+		// Rebates of the makers are paid out of the fee of the taker, after the share of its
+		// master account
+		fn ensure_maker_rebate_is_covered(
+			fee: FixedI128,
+			fee_share_amount: FixedI128,
+			maker_rebate: FixedI128,
+		) -> Result<(), Error<T>> {
+			if maker_rebate > FixedI128::zero() {
+				ensure!(fee - fee_share_amount >= maker_rebate, Error::<T>::TradeBatchError558);
+			}
+			Ok(())
+		}
+
+		fn settle_fee(
+			account_id: U256,
+			collateral_id: u128,
+			market_id: u128,
+			fee: FixedI128,
+			fee_share_amount: FixedI128,
+			maker_rebate: FixedI128,
+		) {
+			if fee > FixedI128::zero() {
+				T::TradingAccountPallet::transfer_from(
+					account_id,
+					collateral_id,
+					market_id,
+					fee,
+					BalanceChangeReason::Fee,
+				);
+
+				// Maker rebates are taken out of the fee before it is split
+				T::TradingAccountPallet::handle_fee_split(
+					account_id,
+					collateral_id,
+					market_id,
+					fee,
+					fee_share_amount,
+					maker_rebate,
+				);
+			} else if fee < FixedI128::zero() {
+				// A negative fee is a maker rebate, it is paid out of the fee of the taker
+				// of the same batch
+				T::TradingAccountPallet::transfer(
+					account_id,
+					collateral_id,
+					market_id,
+					FixedI128::zero() - fee,
+					BalanceChangeReason::MakerRebate,
+				);
+			}
+		}
+
 		fn update_and_get_fee_share(
 			account_id: U256,
 			collateral_id: u128,
@@ -2144,6 +2901,11 @@ pub mod pallet {
 			collateral_token_decimal: u8,
 		) -> FixedI128 {
 			let mut fee_share = FixedI128::zero();
+
+			// Rebates do not generate any fee share for the master account
+			if fee.is_negative() {
+				return fee_share
+			}
 			if let Some(referral_details) =
 				T::TradingAccountPallet::get_account_address_and_referral_details(account_id)
 			{
@@ -2235,6 +2997,18 @@ pub mod pallet {
 				Error::<T>::TradeBatchError546 => 546,
 				Error::<T>::TradeBatchError547 => 547,
 				Error::<T>::TradeBatchError548 => 548,
+				Error::<T>::TradeBatchError549 => 549,
+				Error::<T>::TradeBatchError550 => 550,
+				Error::<T>::TradeBatchError551 => 551,
+				Error::<T>::TradeBatchError552 => 552,
+				Error::<T>::TradeBatchError553 => 553,
+				Error::<T>::TradeBatchError554 => 554,
+				Error::<T>::TradeBatchError555 => 555,
+				Error::<T>::TradeBatchError556 => 556,
+				Error::<T>::TradeBatchError557 => 557,
+				Error::<T>::TradeBatchError558 => 558,
+				Error::<T>::TradeBatchError559 => 559,
+				Error::<T>::TradeBatchError560 => 560,
 				_ => 500,
 			}
 		}
@@ -2293,6 +3067,136 @@ pub mod pallet {
 			true
 		}
 
+		// Covers the loss of a liquidated account above its balance from the insurance fund of the
+		// market, the part that the insurance fund cannot cover is socialised if enabled
+		fn handle_bankruptcy(
+			order: &Order,
+			collateral_id: u128,
+			collateral_token_decimal: u8,
+			deficit: FixedI128,
+		) {
+			let mut amount_to_cover = deficit;
+			if IsLossSocialisationEnabled::<T>::get() {
+				let (insurance_fund, _) =
+					T::TradingAccountPallet::get_fee_split_details(order.market_id);
+				let insurance_fund_balance = T::TradingAccountPallet::get_insurance_fund_balance(
+					insurance_fund,
+					collateral_id,
+				);
+				amount_to_cover = FixedI128::min(
+					deficit,
+					FixedI128::max(insurance_fund_balance, FixedI128::zero()),
+				);
+
+				// Insurance fund covers the part of the loss that the opposite positions cannot
+				let residual_loss = deficit - amount_to_cover;
+				if residual_loss != FixedI128::zero() {
+					let socialised_loss = Self::socialise_loss(
+						order,
+						collateral_id,
+						collateral_token_decimal,
+						residual_loss,
+					);
+					amount_to_cover = deficit - socialised_loss;
+				}
+			}
+
+			if amount_to_cover != FixedI128::zero() {
+				T::TradingAccountPallet::handle_insurance_fund_update(
+					collateral_id,
+					order.market_id,
+					amount_to_cover,
+					FundModifyType::Decrease,
+				);
+				LiquidationFeeMap::<T>::mutate(collateral_id, |liquidation_fee| {
+					*liquidation_fee = *liquidation_fee - amount_to_cover
+				});
+
+				Self::deposit_event(Event::BankruptcyCovered {
+					account_id: order.account_id,
+					order_id: order.order_id,
+					market_id: order.market_id,
+					amount: amount_to_cover,
+				});
+			}
+		}
+
+		// Deducts the loss from the accounts with a position opposite to the bankrupt position,
+		// in proportion to their size. At most LOSS_SOCIALISATION_COUNT positions share the loss
+		// and the share of an account is capped at its available margin. Returns the loss deducted
+		pub(crate) fn socialise_loss(
+			order: &Order,
+			collateral_id: u128,
+			collateral_token_decimal: u8,
+			loss: FixedI128,
+		) -> FixedI128 {
+			let direction = Self::get_opposite_direction(order.direction);
+			let positions: Vec<(U256, FixedI128)> =
+				MarketToAccountMap::<T>::iter_key_prefix((order.market_id, direction))
+					.filter(|account_id| *account_id != order.account_id)
+					.map(|account_id| {
+						let position =
+							PositionsMap::<T>::get(account_id, (order.market_id, direction));
+						(account_id, position.size)
+					})
+					.filter(|(_, size)| *size != FixedI128::zero())
+					.take(LOSS_SOCIALISATION_COUNT as usize)
+					.collect();
+
+			let total_size =
+				positions.iter().fold(FixedI128::zero(), |total, (_, size)| total + *size);
+			if total_size == FixedI128::zero() {
+				return FixedI128::zero()
+			}
+
+			// Rounding difference is taken from the last position
+			let mut remaining_loss = loss;
+			let mut socialised_loss = FixedI128::zero();
+			for (index, (account_id, size)) in positions.iter().enumerate() {
+				let proportional_share = if index == positions.len() - 1 {
+					remaining_loss
+				} else {
+					(loss * *size / total_size).round_to_precision(collateral_token_decimal.into())
+				};
+				remaining_loss = remaining_loss - proportional_share;
+
+				// Share of an account cannot exceed its available margin, so that the account
+				// does not become liquidatable because of it
+				let (_, _, available_margin, _, _, _) = T::TradingAccountPallet::get_margin_info(
+					*account_id,
+					collateral_id,
+					FixedI128::zero(),
+					FixedI128::zero(),
+				);
+				let share = FixedI128::min(
+					proportional_share,
+					FixedI128::max(available_margin, FixedI128::zero()),
+				);
+				if share == FixedI128::zero() {
+					continue
+				}
+				socialised_loss = socialised_loss + share;
+
+				T::TradingAccountPallet::transfer_from(
+					*account_id,
+					collateral_id,
+					order.market_id,
+					share,
+					BalanceChangeReason::LossSocialisation,
+				);
+			}
+
+			if socialised_loss != FixedI128::zero() {
+				Self::deposit_event(Event::LossSocialised {
+					account_id: order.account_id,
+					order_id: order.order_id,
+					market_id: order.market_id,
+					amount: socialised_loss,
+				});
+			}
+			socialised_loss
+		}
+
 		fn get_opposite_direction(direction: Direction) -> Direction {
 			if direction == Direction::Long {
 				Direction::Short
@@ -2345,6 +3249,14 @@ pub mod pallet {
 
 			let balance = T::TradingAccountPallet::get_balance(account_id, collateral_id);
 
+			// Loss of an isolated position can be covered only by its own margin
+			let is_isolated = IsolatedPositionsMap::<T>::get(account_id, (market_id, direction));
+			let balance = if is_isolated {
+				FixedI128::min(balance, position_details.margin_amount)
+			} else {
+				balance
+			};
+
 			// If user is in loss
 			if pnl.is_negative() {
 				let pnl_abs = pnl.saturating_abs();
@@ -2394,11 +3306,12 @@ pub mod pallet {
 					}
 				}
 				// Deduct loss from user
+				let loss = if is_isolated { FixedI128::min(pnl_abs, balance) } else { pnl_abs };
 				T::TradingAccountPallet::transfer_from(
 					account_id,
 					collateral_id,
 					market_id,
-					pnl_abs,
+					loss,
 					BalanceChangeReason::PnlRealization,
 				);
 			} else {
@@ -2425,13 +3338,18 @@ pub mod pallet {
 
 			// OrderSide::Maker is hardcoded, becuase all open positions of delisted market
 			// are considered as Maker orders for closing
-			let (fee_rate, _) = Self::get_fee_rate(
+			let (mut fee_rate, _) = Self::get_fee_rate(
 				account_id,
+				market_id,
 				&market_fees,
 				Side::Sell,
 				OrderSide::Maker,
 				total_30day_volume,
 			);
+			// There is no taker fee to fund a rebate while closing delisted positions
+			if fee_rate.is_negative() {
+				fee_rate = FixedI128::zero();
+			}
 
 			let mut fee = fee_rate * leveraged_order_value;
 			fee = fee.round_to_precision(collateral_token_decimal.into());
@@ -2446,27 +3364,19 @@ pub mod pallet {
 			);
 
 			// Deduct fee while closing a position
-			if fee != FixedI128::zero() {
-				T::TradingAccountPallet::transfer_from(
-					account_id,
-					collateral_id,
-					market_id,
-					fee,
-					BalanceChangeReason::Fee,
-				);
-
-				T::TradingAccountPallet::handle_fee_split(
-					account_id,
-					collateral_id,
-					market_id,
-					fee,
-					fee_share_amount,
-				);
-			}
+			Self::settle_fee(
+				account_id,
+				collateral_id,
+				market_id,
+				fee,
+				fee_share_amount,
+				FixedI128::zero(),
+			);
 
 			// Since new position size is 0, it means that the position in a certain
 			// market and direction is completely closed
 			MarketToAccountMap::<T>::remove((market_id, direction), account_id);
+			Self::remove_isolated_flags(account_id, market_id, direction);
 
 			let opposite_direction = Self::get_opposite_direction(direction);
 			let opposite_position =
@@ -2483,7 +3393,7 @@ pub mod pallet {
 				// liquidation, then on closure of this position force closure flag should be reset
 				if force_closure_flag.is_some() &&
 					force_closure_flag.unwrap() == ForceClosureFlag::Liquidate &&
-					markets.is_empty()
+					Self::are_all_positions_isolated(account_id, &markets)
 				{
 					// Remove the liquidation flag and check for deferred deposits
 					let validation_response =
@@ -2570,6 +3480,25 @@ pub mod pallet {
 			});
 		}
 
+		fn is_isolated_position(account_id: U256, market_id: u128, direction: Direction) -> bool {
+			IsolatedPositionsMap::<T>::get(account_id, (market_id, direction))
+		}
+
+		fn set_flag_for_isolated_force_order(
+			account_id: U256,
+			market_id: u128,
+			direction: Direction,
+		) {
+			IsolatedForceClosureMap::<T>::insert(account_id, (market_id, direction), true);
+
+			// Emit event
+			Self::deposit_event(Event::IsolatedPositionLiquidatable {
+				account_id,
+				market_id,
+				direction: direction.into(),
+			});
+		}
+
 		fn get_deleveragable_amount(account_id: U256, collateral_id: u128) -> FixedI128 {
 			DeleveragableMap::<T>::get(account_id, collateral_id)
 		}
@@ -2696,6 +3625,7 @@ pub mod pallet {
 			FeeRates {
 				maker_buy: Self::get_fee_rate(
 					account_id,
+					market_id,
 					&fees_details,
 					Side::Buy,
 					OrderSide::Maker,
@@ -2704,6 +3634,7 @@ pub mod pallet {
 				.0,
 				maker_sell: Self::get_fee_rate(
 					account_id,
+					market_id,
 					&fees_details,
 					Side::Sell,
 					OrderSide::Maker,
@@ -2712,6 +3643,7 @@ pub mod pallet {
 				.0,
 				taker_buy: Self::get_fee_rate(
 					account_id,
+					market_id,
 					&fees_details,
 					Side::Buy,
 					OrderSide::Taker,
@@ -2720,6 +3652,7 @@ pub mod pallet {
 				.0,
 				taker_sell: Self::get_fee_rate(
 					account_id,
+					market_id,
 					&fees_details,
 					Side::Sell,
 					OrderSide::Taker,
@@ -2731,11 +3664,24 @@ pub mod pallet {
 
 		fn get_fee_rate(
 			account_id: U256,
+			market_id: u128,
 			base_fees: &BaseFeeAggregate,
 			side: Side,
 			order_side: OrderSide,
 			volume: FixedI128,
 		) -> (FixedI128, u8) {
+			// Negotiated fee rates take precedence over volume tiers and discounts,
+			// they are reported with fee tier 0
+			if let Some(fee_override) =
+				T::TradingFeesPallet::get_fee_override(account_id, market_id)
+			{
+				let fee = match order_side {
+					OrderSide::Maker => fee_override.maker_fee,
+					OrderSide::Taker => fee_override.taker_fee,
+				};
+				return (fee, 0);
+			}
+
 			// Get fee data for given side and orderside
 			let fee_details = match (order_side, side) {
 				(OrderSide::Maker, Side::Buy) => &base_fees.maker_buy,
@@ -2766,7 +3712,10 @@ pub mod pallet {
 				}
 			}
 
-			let fee = fee * (FixedI128::one() - fee_discount);
+			// Discounts apply to fees only, maker rebates are paid out in full
+			if fee.is_positive() {
+				fee = fee * (FixedI128::one() - fee_discount);
+			}
 
 			(fee, fee_tier)
 		}
@@ -2832,6 +3781,28 @@ pub mod pallet {
 			0_u64
 		}
 
+		fn get_order_state(order_id: U256) -> OrderState {
+			let (executed_size, _) = OrderStateMap::<T>::get(order_id);
+			// Cancellation of an order is known only once its owner is known
+			let is_cancelled = OrderAccountMap::<T>::get(order_id)
+				.map_or(false, |owner| CancelledOrdersMap::<T>::get(owner, order_id));
+			OrderState { executed_size, is_cancelled, order_hash: OrderHashMap::<T>::get(order_id) }
+		}
+
+		fn get_batch_status(batch_id: U256) -> bool {
+			BatchStatusMap::<T>::get(batch_id)
+		}
+
+		fn get_accrued_fees(collateral_id: u128) -> AccruedFees {
+			AccruedFees {
+				trading_fee: TradingFeeMap::<T>::get(collateral_id),
+				liquidation_fee: LiquidationFeeMap::<T>::get(collateral_id),
+				unallocated_revenue: T::TradingAccountPallet::get_unallocated_revenue(
+					collateral_id,
+				),
+			}
+		}
+
 		fn get_no_of_delisted_market_positions(market_id: u128) -> u32 {
 			let long_users_count =
 				MarketToAccountMap::<T>::iter_prefix_values((market_id, Direction::Long)).count()
@@ -2841,6 +3812,10 @@ pub mod pallet {
 					as u32;
 			return long_users_count + short_users_count;
 		}
+
+		fn get_open_interest(market_id: u128) -> FixedI128 {
+			OpenInterestMap::<T>::get(market_id)
+		}
 	}
 
 	#[pallet::hooks]
@@ -2879,8 +3854,8 @@ pub mod pallet {
 			// Calls close_delisted_market_positions extrinsic after every
 			// BLOCK_INTERVAL_FOR_DELISTING
 			if block_number % BLOCK_INTERVAL_FOR_DELISTING == 0 {
-				// Get all markets which are non tradable
-				let markets = T::MarketPallet::get_all_markets_by_state(false, false);
+				// Get all markets which are being delisted
+				let markets = T::MarketPallet::get_all_markets_by_state(&[MarketState::Delisting]);
 				if markets.len() != 0 {
 					for market_id in markets {
 						// Get no.of open positions of a delisted market
@@ -2908,6 +3883,42 @@ pub mod pallet {
 					}
 				}
 			}
+
+			// Calls flag_liquidatable_accounts extrinsic after every
+			// BLOCK_INTERVAL_FOR_LIQUIDATION_SCAN, accounts are checked in pages so that all the
+			// accounts are covered over multiple intervals
+			if block_number % BLOCK_INTERVAL_FOR_LIQUIDATION_SCAN == 0 {
+				let scan_index = StorageValueRef::persistent(LIQUIDATION_SCAN_INDEX_KEY);
+				let start_index = scan_index.get::<u128>().unwrap_or(None).unwrap_or(0);
+				let end_index = start_index + ACCOUNTS_SCAN_COUNT as u128;
+
+				let (accounts, next_index) =
+					Self::get_liquidatable_accounts(start_index, end_index);
+
+				// Start again from the first account once all the accounts are checked
+				let next_index = if next_index >= T::TradingAccountPallet::get_accounts_count() {
+					0
+				} else {
+					next_index
+				};
+				scan_index.set(&next_index);
+
+				if accounts.len() != 0 {
+					let results = signer.send_signed_transaction(|_account| {
+						Call::flag_liquidatable_accounts { accounts: accounts.clone() }
+					});
+					for (acc, res) in &results {
+						match res {
+							Ok(()) => log::info!("[{:?}]: Submit transaction success.", acc.id),
+							Err(e) => log::info!(
+								"[{:?}]: Submit transaction failure. Reason: {:?}",
+								acc.id,
+								e
+							),
+						}
+					}
+				}
+			}
 		}
 	}
 }
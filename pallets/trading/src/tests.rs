@@ -1,5 +1,5 @@
-use crate::{mock::*, Event};
-use frame_support::assert_ok;
+use crate::{mock::*, Error, Event, TradingFeeMap};
+use frame_support::{assert_noop, assert_ok};
 use pallet_support::{
 	test_helpers::{
 		accounts_helper::{alice, bob, charlie, dave, get_private_key, get_trading_account_id},
@@ -7,11 +7,13 @@ use pallet_support::{
 		market_helper::{btc_usdc, eth_usdc, link_usdc},
 		setup_fee,
 	},
-	traits::{FixedI128Ext, TradingAccountInterface, TradingInterface},
+	traits::{FixedI128Ext, MarketInterface, TradingAccountInterface, TradingInterface},
 	types::{
-		BalanceChangeReason, BaseFee, BaseFeeAggregate, Direction, FeeRates, FeeShareDetails,
-		FeeSharesInput, FundModifyType, MultiplePrices, Order, OrderType, Position,
-		ReferralDetails, Side,
+		AccruedFees, BalanceChangeReason, BalanceUpdate, BaseFee, BaseFeeAggregate,
+		CancelAllRequest, CancelRequest, Direction, FeeOverride, FeeRates, FeeRecipients,
+		FeeShareDetails, FeeSharesInput, FeeSweepDestination, ForceClosureFlag, FundModifyType,
+		MarketState, MultiplePrices, Order, OrderType, Position, ReferralDetails, Side,
+		TimeInForce,
 	},
 };
 use pallet_trading_account::Event as TradingAccountEvent;
@@ -6630,8 +6632,8 @@ fn test_closing_positions_of_delisted_market() {
 			timestamp
 		));
 
-		// Make market non tradable
-		let btc_usdc_market_updated = btc_usdc().set_is_tradable(false);
+		// Delist the market
+		let btc_usdc_market_updated = btc_usdc().set_state(MarketState::Delisting);
 		assert_ok!(Markets::update_market(
 			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
 			btc_usdc_market_updated.clone()
@@ -6670,7 +6672,7 @@ fn test_closing_positions_of_delisted_market() {
 		let dave_position = Trading::positions(bob_id, (market_id, dave_open_order_1.direction));
 		assert_eq!(expected_position, dave_position);
 
-		// Since is_tradable flag for BTC-USDC is set to false
+		// Since BTC-USDC is being delisted
 		// Check whether the mark price for that market is set
 		let btc_usdc_mark_price = Prices::mark_price_for_ads(market_id);
 		assert_eq!(btc_usdc_mark_price.unwrap(), FixedI128::from_inner(160000000000000000000));
@@ -6835,13 +6837,23 @@ fn test_update_market_to_user_map() {
 		));
 
 		// Invoke extrinsic to populate market to user map
+		assert_noop!(
+			Trading::update_market_to_user_map(
+				RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+				eth_usdc().market.id,
+				1
+			),
+			Error::<Test>::TooManyPositions
+		);
 		assert_ok!(Trading::update_market_to_user_map(
 			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
-			eth_usdc().market.id
+			eth_usdc().market.id,
+			10
 		));
 		assert_ok!(Trading::update_market_to_user_map(
 			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
-			btc_usdc().market.id
+			btc_usdc().market.id,
+			10
 		));
 
 		let result = Trading::market_to_account((btc_usdc().market.id, Direction::Long), alice_id);
@@ -6858,6 +6870,71 @@ fn test_update_market_to_user_map() {
 	})
 }
 
+#[test]
+#[should_panic(expected = "MarketHasOpenPositions")]
+// a delisted market cannot be settled while it has open positions
+fn test_settling_delisted_market_with_open_positions() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		let alice_open_order_1 = Order::new(U256::from(201), alice_id)
+			.set_price(100.into())
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(alice().pub_key));
+
+		let bob_open_order_1 = Order::new(U256::from(202), bob_id)
+			.set_price(100.into())
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// size
+			1.into(),
+			// market_id
+			market_id,
+			// price
+			100.into(),
+			// orders
+			vec![alice_open_order_1, bob_open_order_1],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Set price
+		let prices: Vec<MultiplePrices> =
+			vec![MultiplePrices { market_id, index_price: 100.into(), mark_price: 100.into() }];
+		assert_ok!(Prices::update_prices(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			prices,
+			1699940367000
+		));
+
+		// Delist the market
+		assert_ok!(Markets::set_market_state(
+			RuntimeOrigin::root(),
+			market_id,
+			MarketState::Delisting
+		));
+
+		// Positions of the market are not closed yet
+		assert_ok!(Markets::set_market_state(
+			RuntimeOrigin::root(),
+			market_id,
+			MarketState::Settled
+		));
+	});
+}
+
 #[test]
 // test closing of open positions of a delisted market when one user is underwater
 fn test_closing_positions_of_delisted_market_user_underwater() {
@@ -6917,8 +6994,8 @@ fn test_closing_positions_of_delisted_market_user_underwater() {
 			timestamp
 		));
 
-		// Make market non tradable
-		let btc_usdc_market_updated = btc_usdc().set_is_tradable(false);
+		// Delist the market
+		let btc_usdc_market_updated = btc_usdc().set_state(MarketState::Delisting);
 		assert_ok!(Markets::update_market(
 			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
 			btc_usdc_market_updated.clone()
@@ -6950,7 +7027,7 @@ fn test_closing_positions_of_delisted_market_user_underwater() {
 		let bob_position = Trading::positions(bob_id, (market_id, bob_open_order_1.direction));
 		assert_eq!(expected_position, bob_position);
 
-		// Since is_tradable flag for BTC-USDC is set to false
+		// Since BTC-USDC is being delisted
 		// Check whether the mark price for that market is set
 		let btc_usdc_mark_price = Prices::mark_price_for_ads(market_id);
 		assert_eq!(btc_usdc_mark_price.unwrap(), FixedI128::from_inner(1200000000000000000000));
@@ -7050,8 +7127,8 @@ fn test_delist_market_when_price_not_available() {
 	let mut env = setup();
 
 	env.execute_with(|| {
-		// Make market non tradable
-		let btc_usdc_market_updated = btc_usdc().set_is_tradable(false);
+		// Delist the market
+		let btc_usdc_market_updated = btc_usdc().set_state(MarketState::Delisting);
 		assert_ok!(Markets::update_market(
 			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
 			btc_usdc_market_updated
@@ -7064,3 +7141,2298 @@ fn test_delist_market_when_price_not_available() {
 		));
 	});
 }
+
+#[test]
+fn it_works_for_cancel_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+
+		// Create and sign the cancel request
+		let cancel_request = CancelRequest::new(alice_id, U256::from(201))
+			.sign_request(get_private_key(alice().pub_key));
+
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request
+		));
+
+		// Check the cancellation of the account
+		assert_eq!(Trading::is_order_cancelled(alice_id, U256::from(201)), true);
+		assert_eq!(Trading::cancellations(1699940367), Some(vec![(alice_id, U256::from(201))]));
+
+		assert_has_events(vec![Event::OrderCancelled {
+			account_id: alice_id,
+			order_id: U256::from(201),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "CancelRequestSignatureNotVerified")]
+// cancel request signed with a key that does not belong to the account
+fn it_reverts_for_cancel_order_with_invalid_signature() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+
+		// Sign alice's cancel request with bob's private key
+		let cancel_request = CancelRequest::new(alice_id, U256::from(201))
+			.sign_request(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "OrderAccountMismatch")]
+// cancel request for an executed order of another account
+fn it_reverts_for_cancel_order_of_another_account() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(2.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Bob tries to cancel the resting part of alice's order
+		let cancel_request = CancelRequest::new(bob_id, U256::from(201))
+			.sign_request(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError543")]
+// cancelled taker order sent for execution
+fn it_reverts_for_cancelled_taker_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Bob cancels his order
+		let cancel_request = CancelRequest::new(bob_id, U256::from(202))
+			.sign_request(get_private_key(bob().pub_key));
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request
+		));
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// cancel request of another account neither cancels the order nor is accepted once the owner
+// of the order is known
+fn it_reverts_for_order_cancelled_by_another_account() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Charlie cancels an order id which belongs to alice
+		let cancel_request = CancelRequest::new(charlie_id, U256::from(201))
+			.sign_request(get_private_key(charlie().pub_key));
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request.clone()
+		));
+		assert_eq!(Trading::is_order_cancelled(alice_id, U256::from(201)), false);
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(2.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Alice's order is partially executed and owned by alice
+		let (portion_executed, is_cancelled) = Trading::order_state(U256::from(201));
+		assert_eq!(portion_executed, FixedI128::one());
+		assert_eq!(is_cancelled, false);
+		assert_eq!(Trading::order_account(U256::from(201)), Some(alice_id));
+
+		// Charlie's cancel request for the resting part of alice's order is rejected
+		assert_noop!(
+			Trading::cancel_order(
+				RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+				cancel_request
+			),
+			Error::<Test>::OrderAccountMismatch
+		);
+	});
+}
+
+#[test]
+// cancellations of orders that are never executed are removed in cleanup
+fn it_works_for_cleanup_of_cancelled_orders() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+
+		// Alice cancels an order which is never executed
+		let cancel_request = CancelRequest::new(alice_id, U256::from(201))
+			.sign_request(get_private_key(alice().pub_key));
+		assert_ok!(Trading::cancel_order(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_request
+		));
+		assert_eq!(Trading::start_timestamp(), Some(1699940367));
+
+		Timestamp::set_timestamp(1702359600000);
+
+		assert_ok!(Trading::perform_cleanup(RuntimeOrigin::signed(
+			sp_core::sr25519::Public::from_raw([1u8; 32])
+		)));
+
+		assert_eq!(Trading::is_order_cancelled(alice_id, U256::from(201)), false);
+		assert_eq!(Trading::cancellations(1699940367), None);
+	});
+}
+
+#[test]
+fn it_works_for_cancel_all_orders() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Cancel orders in all markets
+		let cancel_all_request = CancelAllRequest::new(alice_id, None)
+			.set_timestamp(1699940300000)
+			.sign_request(get_private_key(alice().pub_key));
+		assert_ok!(Trading::cancel_all_orders(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_all_request
+		));
+
+		// Cancel orders in btc market
+		let cancel_all_request = CancelAllRequest::new(alice_id, Some(market_id))
+			.set_timestamp(1699940310000)
+			.sign_request(get_private_key(alice().pub_key));
+		assert_ok!(Trading::cancel_all_orders(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_all_request
+		));
+
+		// An older request does not move the timestamp back
+		let cancel_all_request = CancelAllRequest::new(alice_id, None)
+			.set_timestamp(1699940200000)
+			.sign_request(get_private_key(alice().pub_key));
+		assert_ok!(Trading::cancel_all_orders(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_all_request
+		));
+
+		assert_eq!(Trading::cancel_before(alice_id), 1699940300000);
+		assert_eq!(Trading::market_cancel_before(alice_id, market_id), 1699940310000);
+		assert_eq!(Trading::market_cancel_before(alice_id, eth_usdc().market.id), 0);
+
+		assert_has_events(vec![
+			Event::AllOrdersCancelled {
+				account_id: alice_id,
+				market_id: None,
+				timestamp: 1699940300000,
+			}
+			.into(),
+			Event::AllOrdersCancelled {
+				account_id: alice_id,
+				market_id: Some(market_id),
+				timestamp: 1699940310000,
+			}
+			.into(),
+		]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError549")]
+// taker order placed before a cancel all orders request
+fn it_reverts_for_order_placed_before_cancel_all() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Bob cancels all his orders in btc market
+		let cancel_all_request = CancelAllRequest::new(bob_id, Some(market_id))
+			.set_timestamp(1699940300000)
+			.sign_request(get_private_key(bob().pub_key));
+		assert_ok!(Trading::cancel_all_orders(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_all_request
+		));
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// orders placed after the cancel all orders request are executed
+fn it_works_for_order_placed_after_cancel_all() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Bob cancels all his orders
+		let cancel_all_request = CancelAllRequest::new(bob_id, None)
+			.set_timestamp(1699940300000)
+			.sign_request(get_private_key(bob().pub_key));
+		assert_ok!(Trading::cancel_all_orders(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			cancel_all_request
+		));
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_timestamp(1699940350000)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let (portion_executed, _) = Trading::order_state(U256::from(202));
+		assert_eq!(portion_executed, FixedI128::one());
+	});
+}
+
+fn set_mark_price(market_id: u128, mark_price: FixedI128) {
+	let prices: Vec<MultiplePrices> =
+		vec![MultiplePrices { market_id, index_price: mark_price, mark_price }];
+	assert_ok!(Prices::update_prices(
+		RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+		prices,
+		1699940367000
+	));
+}
+
+#[test]
+// stop market sell order is executed once the mark price falls to the trigger price
+fn it_works_for_triggered_stop_market_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		set_mark_price(market_id, 90.into());
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::StopMarket)
+			.set_trigger_price(95.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let (portion_executed, _) = Trading::order_state(U256::from(202));
+		assert_eq!(portion_executed, FixedI128::one());
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError551")]
+// stop market sell order is not executed while the mark price is above the trigger price
+fn it_reverts_for_untriggered_stop_market_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		set_mark_price(market_id, 100.into());
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::StopMarket)
+			.set_trigger_price(95.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// take profit limit sell order is executed once the mark price rises to the trigger price
+fn it_works_for_triggered_take_profit_limit_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		set_mark_price(market_id, 100.into());
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::TakeProfitLimit)
+			.set_trigger_price(95.into())
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let (portion_executed, _) = Trading::order_state(U256::from(202));
+		assert_eq!(portion_executed, FixedI128::one());
+	});
+}
+
+#[test]
+// conditional orders cannot be makers
+fn it_produces_error_when_maker_is_conditional_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		set_mark_price(market_id, 100.into());
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_order_type(OrderType::StopLimit)
+			.set_trigger_price(95.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let charlie_order =
+			Order::new(U256::from(203), charlie_id).sign_order(get_private_key(charlie().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), charlie_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		System::assert_has_event(
+			Event::OrderError { order_id: U256::from(201), account_id: alice_id, error_code: 518 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+// reduce only order is executed only up to the size of the position
+fn it_works_for_reduce_only_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create open orders
+		let alice_open_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(U256::from(202), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order.clone(), bob_open_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Alice tries to sell more than her position
+		let alice_close_order = Order::new(U256::from(203), alice_id)
+			.set_side(Side::Sell)
+			.set_size(2.into())
+			.set_reduce_only(true)
+			.sign_order(get_private_key(alice().pub_key));
+		let charlie_open_order = Order::new(U256::from(204), charlie_id)
+			.set_order_type(OrderType::Market)
+			.set_size(2.into())
+			.sign_order(get_private_key(charlie().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			2.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_close_order.clone(), charlie_open_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Only the size of the position is executed
+		let (portion_executed, _) = Trading::order_state(U256::from(203));
+		assert_eq!(portion_executed, FixedI128::one());
+		let position = Trading::positions(alice_id, (market_id, Direction::Long));
+		assert_eq!(position.size, FixedI128::zero());
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError552")]
+// reduce only order cannot increase a position
+fn it_reverts_for_reduce_only_buy_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_reduce_only(true)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// reduce only maker order without a position is rejected
+fn it_produces_error_when_reduce_only_maker_has_no_position() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_side(Side::Sell)
+			.set_reduce_only(true)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		System::assert_has_event(
+			Event::OrderError { order_id: U256::from(201), account_id: alice_id, error_code: 553 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+// isolated flag of a position is set by the opening order and removed on closure
+fn it_works_for_isolated_position() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create open orders
+		let alice_open_order = Order::new(201.into(), alice_id)
+			.set_is_isolated(true)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(202.into(), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order.clone(), bob_open_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		assert!(Trading::isolated_position(alice_id, (market_id, Direction::Long)));
+		assert!(!Trading::isolated_position(bob_id, (market_id, Direction::Short)));
+
+		// Create close orders
+		let alice_close_order = Order::new(203.into(), alice_id)
+			.set_side(Side::Sell)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_close_order = Order::new(204.into(), bob_id)
+			.set_side(Side::Sell)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_close_order.clone(), bob_close_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		assert!(!Trading::isolated_position(alice_id, (market_id, Direction::Long)));
+	});
+}
+
+#[test]
+// margin mode of an existing position cannot be changed
+fn it_produces_error_when_margin_mode_does_not_match() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create open orders
+		let alice_open_order = Order::new(201.into(), alice_id)
+			.set_is_isolated(true)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(202.into(), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order.clone(), bob_open_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Alice tries to increase the isolated position with a cross margin order
+		let alice_order =
+			Order::new(203.into(), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(204.into(), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		System::assert_has_event(
+			Event::OrderError { order_id: U256::from(203), account_id: alice_id, error_code: 554 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError555")]
+// taker cannot open a position beyond the open interest cap of the market
+fn it_reverts_when_open_interest_exceeds_cap() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Both sides of a trade count towards the open interest
+		assert_ok!(Markets::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_usdc().set_max_open_interest(Some(1.into()))
+		));
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// maker cannot open a position beyond the open interest cap of the market
+fn it_produces_error_when_maker_exceeds_open_interest_cap() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		assert_ok!(Markets::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_usdc().set_max_open_interest(Some(2.into()))
+		));
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+		assert_eq!(Trading::open_interest(market_id), 2.into());
+
+		// Open interest is already at the cap
+		let alice_order =
+			Order::new(U256::from(203), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(204), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		System::assert_has_event(
+			Event::OrderError { order_id: U256::from(203), account_id: alice_id, error_code: 555 }
+				.into(),
+		);
+		assert_eq!(Trading::open_interest(market_id), 2.into());
+	});
+}
+
+#[test]
+// GTT orders are executed until their expiry
+fn it_works_for_unexpired_gtt_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders; block timestamp is 1699940367000
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_time_in_force(TimeInForce::GTT)
+			.set_expiry(1699940367000)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_time_in_force(TimeInForce::GTT)
+			.set_expiry(1699943967000)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let alice_position = Trading::positions(alice_id, (market_id, alice_order.direction));
+		assert_eq!(alice_position.size, 1.into());
+		let bob_position = Trading::positions(bob_id, (market_id, bob_order.direction));
+		assert_eq!(bob_position.size, 1.into());
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError556")]
+// taker GTT order cannot be executed after its expiry
+fn it_reverts_for_expired_gtt_taker_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_time_in_force(TimeInForce::GTT)
+			.set_expiry(1699940366000)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError560")]
+// GTT order cannot expire before it is placed
+fn it_reverts_for_gtt_order_expiring_before_placement() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders; expiry of bob's order is the same as its timestamp
+		let alice_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.set_timestamp(1699940367000)
+			.set_time_in_force(TimeInForce::GTT)
+			.set_expiry(1699940367000)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// maker GTT order is not executed after its expiry
+fn it_produces_error_for_expired_gtt_maker_order() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_time_in_force(TimeInForce::GTT)
+			.set_expiry(1699940366000)
+			.sign_order(get_private_key(alice().pub_key));
+		let charlie_order =
+			Order::new(U256::from(202), charlie_id).sign_order(get_private_key(charlie().pub_key));
+		let bob_order = Order::new(U256::from(203), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), charlie_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		System::assert_has_event(
+			Event::OrderError { order_id: U256::from(201), account_id: alice_id, error_code: 556 }
+				.into(),
+		);
+
+		let alice_position = Trading::positions(alice_id, (market_id, alice_order.direction));
+		assert_eq!(alice_position.size, 0.into());
+		let charlie_position = Trading::positions(charlie_id, (market_id, charlie_order.direction));
+		assert_eq!(charlie_position.size, 1.into());
+	});
+}
+
+#[test]
+// order state and batch status are returned for the runtime api
+fn it_works_for_get_order_state_and_batch_status() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(2.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let order_state = Trading::get_order_state(U256::from(201));
+		assert_eq!(order_state.executed_size, 1.into());
+		assert_eq!(order_state.is_cancelled, false);
+		assert_eq!(order_state.order_hash, Trading::order_hash(U256::from(201)));
+		assert_ne!(order_state.order_hash, U256::zero());
+
+		// Unknown order
+		let order_state = Trading::get_order_state(U256::from(203));
+		assert_eq!(order_state.executed_size, 0.into());
+		assert_eq!(order_state.order_hash, U256::zero());
+
+		assert_eq!(Trading::get_batch_status(U256::from(1_u8)), true);
+		assert_eq!(Trading::get_batch_status(U256::from(2_u8)), false);
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError557")]
+// taker cannot open a position with a leverage above the maximum of its size tier
+fn it_reverts_when_leverage_exceeds_size_tier() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Initial margin fraction for a position of size 5 is 0.1 + 4 * 0.02 = 0.18,
+		// i.e. maximum leverage is ~5.55
+		assert_ok!(Markets::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_usdc()
+				.set_initial_margin_fraction(FixedI128::from_inner(100000000000000000))
+				.set_incremental_initial_margin_fraction(FixedI128::from_inner(20000000000000000))
+				.set_baseline_position_size(1.into())
+				.set_incremental_position_size(1.into())
+		));
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(5.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_size(5.into())
+			.set_leverage(8.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			5.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError557")]
+// taker cannot grow a position opened at a higher leverage beyond the margin of its size tier
+fn it_reverts_when_position_margin_is_below_size_tier() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Initial margin fraction for a position of size 1 is 0.1 and for a position of size 5
+		// it is 0.1 + 4 * 0.02 = 0.18
+		assert_ok!(Markets::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_usdc()
+				.set_initial_margin_fraction(FixedI128::from_inner(100000000000000000))
+				.set_incremental_initial_margin_fraction(FixedI128::from_inner(20000000000000000))
+				.set_baseline_position_size(1.into())
+				.set_incremental_position_size(1.into())
+		));
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(1.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_size(1.into())
+			.set_leverage(8.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Leverage of 5.5 is allowed for a position of size 5, but the position would only
+		// have a margin of 100 / 8 + 400 / 5.5 = ~85.23 out of the 500 * 0.18 = 90 required
+		let alice_order = Order::new(U256::from(203), alice_id)
+			.set_size(4.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(204), bob_id)
+			.set_size(4.into())
+			.set_leverage(FixedI128::from_inner(5500000000000000000))
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			4.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// available margin accounts for the initial margin required by the size tier of a position
+fn it_works_for_initial_margin_of_size_tier() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let collateral_id = usdc().asset.id;
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		set_mark_price(market_id, 100.into());
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(5.into())
+			.set_leverage(8.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_size(5.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			5.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order.clone(), bob_order.clone()],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		let (_, _, available_margin, _, _, _) =
+			TradingAccounts::get_margin_info(alice_id, collateral_id, 0.into(), 0.into());
+
+		// Position of size 5 now requires an initial margin of 500 * 0.18 = 90, while only
+		// 500 / 8 = 62.5 is locked
+		assert_ok!(Markets::update_market(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			btc_usdc()
+				.set_initial_margin_fraction(FixedI128::from_inner(100000000000000000))
+				.set_incremental_initial_margin_fraction(FixedI128::from_inner(20000000000000000))
+				.set_baseline_position_size(1.into())
+				.set_incremental_position_size(1.into())
+		));
+
+		let (_, _, tiered_available_margin, _, _, _) =
+			TradingAccounts::get_margin_info(alice_id, collateral_id, 0.into(), 0.into());
+		assert_eq!(
+			available_margin - tiered_available_margin,
+			FixedI128::from_inner(27500000000000000000)
+		);
+	});
+}
+
+#[test]
+// accounts under the maintenance requirement are flagged, others are left as is
+fn it_works_for_flag_liquidatable_accounts() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+		let charlie_id: U256 = get_trading_account_id(charlie());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(5.into())
+			.set_leverage(5.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_size(5.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			5.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Only the margin of the position is left in alice's account
+		assert_ok!(TradingAccounts::set_balances(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			alice_id,
+			vec![BalanceUpdate { asset_id: collateral_id, balance_value: 100.into() }]
+		));
+		set_mark_price(market_id, 70.into());
+
+		assert_ok!(Trading::flag_liquidatable_accounts(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![(alice_id, collateral_id), (bob_id, collateral_id), (charlie_id, collateral_id)]
+		));
+
+		assert_eq!(
+			Trading::force_closure_flag(alice_id, collateral_id),
+			Some(ForceClosureFlag::Liquidate)
+		);
+		assert_eq!(Trading::force_closure_flag(bob_id, collateral_id), None);
+		assert_eq!(Trading::force_closure_flag(charlie_id, collateral_id), None);
+		System::assert_has_event(
+			Event::ForceClosureFlagsChanged {
+				account_id: alice_id,
+				collateral_id,
+				force_closure_flag: ForceClosureFlag::Liquidate.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+// isolated positions under their own maintenance requirement are flagged
+fn it_works_for_flag_liquidatable_isolated_position() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// Create orders
+		let alice_order = Order::new(U256::from(201), alice_id)
+			.set_size(5.into())
+			.set_leverage(5.into())
+			.set_is_isolated(true)
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(U256::from(202), bob_id)
+			.set_size(5.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			5.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Loss of the position is more than its margin of 100
+		set_mark_price(market_id, 70.into());
+
+		assert_ok!(Trading::flag_liquidatable_accounts(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![(alice_id, collateral_id)]
+		));
+
+		assert!(Trading::isolated_force_closure_flag(alice_id, (market_id, Direction::Long)));
+		assert_eq!(Trading::force_closure_flag(alice_id, collateral_id), None);
+		System::assert_has_event(
+			Event::IsolatedPositionLiquidatable {
+				account_id: alice_id,
+				market_id,
+				direction: Direction::Long.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "TooManyAccountsToFlag")]
+// accounts to be flagged in a call are limited to the accounts checked in a scan
+fn it_reverts_when_too_many_accounts_to_flag() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let alice_id: U256 = get_trading_account_id(alice());
+
+		assert_ok!(Trading::flag_liquidatable_accounts(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			vec![(alice_id, usdc().asset.id); 101]
+		));
+	});
+}
+
+#[test]
+// unallocated revenue is swept to the treasury trading account
+fn it_works_for_sweep_fees_to_trading_account() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let alice_id: U256 = get_trading_account_id(alice());
+		let collateral_id = usdc().asset.id;
+		TradingFeeMap::<Test>::insert(collateral_id, FixedI128::from(100));
+		TradingAccounts::update_unallocated_revenue(collateral_id, 50.into());
+
+		assert_ok!(Trading::sweep_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			40.into(),
+			FeeSweepDestination::TradingAccount(alice_id),
+		));
+
+		// Accrued fees are left as they are
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), 10040.into());
+		assert_eq!(
+			Trading::get_accrued_fees(collateral_id),
+			AccruedFees {
+				trading_fee: 100.into(),
+				liquidation_fee: FixedI128::zero(),
+				unallocated_revenue: 10.into()
+			}
+		);
+		System::assert_has_event(
+			Event::FeesSwept {
+				collateral_id,
+				amount: 40.into(),
+				destination: FeeSweepDestination::TradingAccount(alice_id),
+			}
+			.into(),
+		);
+		System::assert_has_event(
+			TradingAccountEvent::UserBalanceChange {
+				trading_account: alice(),
+				collateral_id,
+				amount: 40.into(),
+				modify_type: FundModifyType::Increase,
+				reason: BalanceChangeReason::FeeSweep.into(),
+				block_number: 1_u64,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+// unallocated revenue is swept to an insurance fund
+fn it_works_for_sweep_fees_to_insurance_fund() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let insurance_fund = U256::from(2_u8);
+		let collateral_id = usdc().asset.id;
+		TradingAccounts::update_unallocated_revenue(collateral_id, 100.into());
+
+		assert_ok!(Trading::sweep_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			100.into(),
+			FeeSweepDestination::InsuranceFund(insurance_fund),
+		));
+
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(insurance_fund, collateral_id),
+			100.into()
+		);
+		assert_eq!(TradingAccounts::unallocated_revenue(collateral_id), FixedI128::zero());
+		System::assert_has_event(
+			TradingAccountEvent::InsuranceFundBalanceUpdated {
+				insurance_fund,
+				collateral_id,
+				amount: 100.into(),
+				modify_type: FundModifyType::Increase.into(),
+				reason: BalanceChangeReason::FeeSweep.into(),
+				previous_balance: FixedI128::zero(),
+				new_balance: 100.into(),
+				block_number: 1_u64,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "InvalidSweepAmount")]
+// amount swept cannot be more than the unallocated revenue, even if more fees are accrued
+fn it_reverts_when_sweep_amount_exceeds_unallocated_revenue() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		let alice_id: U256 = get_trading_account_id(alice());
+		let collateral_id = usdc().asset.id;
+		TradingFeeMap::<Test>::insert(collateral_id, FixedI128::from(200));
+		TradingAccounts::update_unallocated_revenue(collateral_id, 100.into());
+
+		assert_ok!(Trading::sweep_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			101.into(),
+			FeeSweepDestination::TradingAccount(alice_id),
+		));
+	});
+}
+
+#[test]
+// fees are split between the recipients of the fee split policy of the market
+fn it_works_for_fee_split_between_fee_recipients() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// Fee recipients
+		let insurance_fund: U256 = 2.into();
+		let treasury: U256 = 3.into();
+
+		// Add fee data
+		assert_ok!(TradingFees::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_usdc_aggregate_fees()
+		));
+
+		// Half of the fee goes to the insurance fund and a quarter to the treasury
+		assert_ok!(TradingAccounts::update_fee_recipients(
+			RuntimeOrigin::root(),
+			market_id,
+			FeeRecipients::try_from(vec![
+				(insurance_fund, FixedI128::from_inner(500000000000000000)),
+				(treasury, FixedI128::from_inner(250000000000000000)),
+			])
+			.unwrap()
+		));
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_price(1001.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_price(1001.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			1.into(),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			1001.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Fee of each order is 1.001
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(insurance_fund, collateral_id),
+			FixedI128::from_inner(1001000000000000000)
+		);
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(treasury, collateral_id),
+			FixedI128::from_inner(500500000000000000)
+		);
+		// Rest of the fee is revenue that can be swept
+		assert_eq!(
+			TradingAccounts::unallocated_revenue(collateral_id),
+			FixedI128::from_inner(500500000000000000)
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "InvalidFeeRecipients")]
+// fractions of the fee split policy cannot add up to more than 1
+fn it_reverts_when_fee_recipients_exceed_total_fee() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		assert_ok!(TradingAccounts::update_fee_recipients(
+			RuntimeOrigin::root(),
+			btc_usdc().market.id,
+			FeeRecipients::try_from(vec![
+				(U256::from(2_u8), FixedI128::from_inner(500000000000000000)),
+				(U256::from(3_u8), FixedI128::from_inner(600000000000000000)),
+			])
+			.unwrap()
+		));
+	});
+}
+
+fn get_maker_rebate_fees(maker_fee: FixedI128, taker_fee: FixedI128) -> BaseFeeAggregate {
+	let maker_fees = vec![BaseFee { volume: FixedI128::zero(), fee: maker_fee }];
+	let taker_fees = vec![BaseFee { volume: FixedI128::zero(), fee: taker_fee }];
+	BaseFeeAggregate {
+		maker_buy: maker_fees.clone(),
+		maker_sell: maker_fees,
+		taker_buy: taker_fees.clone(),
+		taker_sell: taker_fees,
+	}
+}
+
+#[test]
+// negative maker fee is credited to the maker out of the taker fee
+fn it_works_for_maker_rebate() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+		let insurance_fund: U256 = 2.into();
+
+		// Maker gets a rebate of 0.01%, taker pays 0.05%
+		assert_ok!(TradingFees::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_maker_rebate_fees(
+				FixedI128::from_inner(-100000000000000),
+				FixedI128::from_inner(500000000000000)
+			)
+		));
+		assert_ok!(TradingAccounts::update_fee_split_details(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			market_id,
+			insurance_fund,
+			FixedI128::zero()
+		));
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_price(1001.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_price(1001.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			1.into(),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			1001.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Rebate of 0.1001 is credited to alice, bob pays a fee of 0.5005
+		assert_eq!(
+			TradingAccounts::balances(alice_id, collateral_id),
+			FixedI128::from_inner(10000100100000000000000)
+		);
+		assert_eq!(
+			TradingAccounts::balances(bob_id, collateral_id),
+			FixedI128::from_inner(9999499500000000000000)
+		);
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(insurance_fund, collateral_id),
+			FixedI128::from_inner(400400000000000000)
+		);
+		assert_eq!(
+			TradingFeeMap::<Test>::get(collateral_id),
+			FixedI128::from_inner(400400000000000000)
+		);
+	});
+}
+
+#[test]
+// rebates are funded by the taker fee before it is split, whatever the insurance fund collects
+fn it_works_for_maker_rebate_with_small_insurance_fund_share() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+		let insurance_fund: U256 = 2.into();
+
+		// Maker gets a rebate of 0.01%, taker pays 0.05%
+		assert_ok!(TradingFees::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_maker_rebate_fees(
+				FixedI128::from_inner(-100000000000000),
+				FixedI128::from_inner(500000000000000)
+			)
+		));
+		// Insurance fund collects only 10% of the taker fee
+		assert_ok!(TradingAccounts::update_fee_split_details(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			market_id,
+			insurance_fund,
+			FixedI128::from_inner(900000000000000000)
+		));
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_price(1001.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_price(1001.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			1.into(),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			1001.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Rest of the fee after the rebate of 0.1001 is split between the insurance fund
+		// and the revenue
+		assert_eq!(
+			TradingAccounts::balances(alice_id, collateral_id),
+			FixedI128::from_inner(10000100100000000000000)
+		);
+		assert_eq!(
+			TradingAccounts::insurance_fund_balance(insurance_fund, collateral_id),
+			FixedI128::from_inner(40040000000000000)
+		);
+		assert_eq!(
+			TradingAccounts::unallocated_revenue(collateral_id),
+			FixedI128::from_inner(360360000000000000)
+		);
+		assert_eq!(
+			TradingFeeMap::<Test>::get(collateral_id),
+			FixedI128::from_inner(400400000000000000)
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError558")]
+// taker fee after discount does not cover the maker rebate
+fn it_reverts_when_maker_rebate_exceeds_taker_fee() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// Maker rebate is exactly covered by the taker fee before discounts
+		assert_ok!(TradingFees::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_maker_rebate_fees(
+				FixedI128::from_inner(-500000000000000),
+				FixedI128::from_inner(500000000000000)
+			)
+		));
+
+		// Taker gets a 50% fee discount
+		assert_ok!(TradingAccounts::add_referral(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			bob().account_address,
+			ReferralDetails {
+				master_account_address: charlie().account_address,
+				fee_discount: FixedI128::from_inner(500000000000000000),
+			},
+			U256::from(123),
+		));
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_price(1001.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_price(1001.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			1.into(),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			1001.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+// negotiated fee rates take precedence over the fee tiers until they expire
+fn it_works_for_fee_override() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+		let collateral_id = usdc().asset.id;
+
+		// Add fee data
+		assert_ok!(TradingFees::update_base_fees(
+			RuntimeOrigin::root(),
+			collateral_id,
+			get_usdc_aggregate_fees()
+		));
+
+		// Alice pays no maker fee on every market, bob pays 0.01% as taker on BTC-USDC
+		assert_ok!(TradingFees::set_fee_override(
+			RuntimeOrigin::root(),
+			alice_id,
+			0,
+			FeeOverride {
+				maker_fee: FixedI128::zero(),
+				taker_fee: FixedI128::from_inner(300000000000000),
+				expires_at: 1699940367 + 86400,
+			}
+		));
+		assert_ok!(TradingFees::set_fee_override(
+			RuntimeOrigin::root(),
+			bob_id,
+			market_id,
+			FeeOverride {
+				maker_fee: FixedI128::zero(),
+				taker_fee: FixedI128::from_inner(100000000000000),
+				expires_at: 1699940367 + 86400,
+			}
+		));
+
+		// Create orders
+		let alice_order = Order::new(201.into(), alice_id)
+			.set_price(1001.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_order = Order::new(202.into(), bob_id)
+			.set_price(1001.into())
+			.set_direction(Direction::Short)
+			.set_order_type(OrderType::Market)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			1.into(),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			1001.into(),
+			// orders
+			vec![alice_order, bob_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		assert_eq!(TradingAccounts::balances(alice_id, collateral_id), 10000.into());
+		assert_eq!(
+			TradingAccounts::balances(bob_id, collateral_id),
+			FixedI128::from_inner(9999899900000000000000)
+		);
+
+		// Once expired, the fee tiers apply again
+		Timestamp::set_timestamp((1699940367 + 86400) * 1000);
+		let fees = get_usdc_aggregate_fees();
+		assert_eq!(
+			Trading::get_all_fee_rates(alice_id, market_id, collateral_id, FixedI128::zero())
+				.maker_buy,
+			fees.maker_buy[0].fee
+		);
+	});
+}
+
+#[test]
+// positions can still be closed once the market only accepts reducing orders
+fn it_works_for_close_trade_in_reduce_only_market() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create open orders
+		let alice_open_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(U256::from(202), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order, bob_open_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		assert_ok!(Markets::set_market_state(
+			RuntimeOrigin::root(),
+			market_id,
+			MarketState::ReduceOnly
+		));
+
+		// Create close orders
+		let alice_close_order = Order::new(U256::from(203), alice_id)
+			.set_side(Side::Sell)
+			.set_price(105.into())
+			.sign_order(get_private_key(alice().pub_key));
+		let bob_close_order = Order::new(U256::from(204), bob_id)
+			.set_side(Side::Sell)
+			.set_price(100.into())
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(2_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			105.into(),
+			// orders
+			vec![alice_close_order, bob_close_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		// Check for open interest
+		let open_interest = Trading::open_interest(market_id);
+		assert_eq!(open_interest, FixedI128::zero());
+	});
+}
+
+#[test]
+#[should_panic(expected = "TradeBatchError559")]
+// taker cannot open a position in a reduce only market
+fn it_reverts_for_open_trade_in_reduce_only_market() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		assert_ok!(Markets::set_market_state(
+			RuntimeOrigin::root(),
+			market_id,
+			MarketState::ReduceOnly
+		));
+
+		// Create open orders
+		let alice_open_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(U256::from(202), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order, bob_open_order],
+			// batch_timestamp
+			1699940367000,
+		));
+	});
+}
+
+#[test]
+#[should_panic(expected = "MarketHasOpenInterest")]
+// a market cannot be removed while positions are open in it
+fn it_reverts_for_removing_market_with_open_positions() {
+	let mut env = setup();
+
+	env.execute_with(|| {
+		// Generate account_ids
+		let alice_id: U256 = get_trading_account_id(alice());
+		let bob_id: U256 = get_trading_account_id(bob());
+
+		// market id
+		let market_id = btc_usdc().market.id;
+
+		// Create open orders
+		let alice_open_order =
+			Order::new(U256::from(201), alice_id).sign_order(get_private_key(alice().pub_key));
+		let bob_open_order = Order::new(U256::from(202), bob_id)
+			.set_order_type(OrderType::Market)
+			.set_direction(Direction::Short)
+			.sign_order(get_private_key(bob().pub_key));
+
+		assert_ok!(Trading::execute_trade(
+			RuntimeOrigin::signed(sp_core::sr25519::Public::from_raw([1u8; 32])),
+			// batch_id
+			U256::from(1_u8),
+			// quantity_locked
+			1.into(),
+			// market_id
+			market_id,
+			// oracle_price
+			100.into(),
+			// orders
+			vec![alice_open_order, bob_open_order],
+			// batch_timestamp
+			1699940367000,
+		));
+
+		assert_ok!(Markets::archive_market_internal(market_id));
+	});
+}
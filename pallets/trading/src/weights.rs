@@ -0,0 +1,377 @@
+//! Weights for pallet_trading
+//!
+//! THE VALUES BELOW ARE ESTIMATES DERIVED FROM THE STORAGE ACCESSES OF EACH CALL. REGENERATE THIS
+//! FILE WITH THE SUBSTRATE BENCHMARK CLI ON REFERENCE HARDWARE BEFORE A PRODUCTION RELEASE.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_trading.
+pub trait WeightInfo {
+	fn update_market_to_user_map(p: u32, ) -> Weight;
+	fn execute_trade(n: u32, ) -> Weight;
+	fn add_liquidator_signer() -> Weight;
+	fn remove_liquidator_signer() -> Weight;
+	fn cancel_order() -> Weight;
+	fn cancel_all_orders() -> Weight;
+	fn perform_cleanup() -> Weight;
+	fn set_matching_time_limit() -> Weight;
+	fn close_delisted_market_positions(p: u32, ) -> Weight;
+	fn flag_liquidatable_accounts(n: u32, ) -> Weight;
+	fn set_loss_socialisation() -> Weight;
+	fn socialise_loss(p: u32, ) -> Weight;
+	fn sweep_fees() -> Weight;
+}
+
+/// Weights for pallet_trading using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Trading PositionsMap (r:1 w:0)
+	/// Storage: Trading MarketToAccountMap (r:0 w:1)
+	/// The range of component `p` is `[2, 100]`.
+	fn update_market_to_user_map(p: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Trading BatchStatusMap (r:1 w:1)
+	/// Storage: Trading MatchingTimeLimit (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Prices LastOraclePricesMap (r:1 w:1)
+	/// Storage: TradingFees BaseFeeMap (r:1 w:0)
+	/// Storage: Trading InitialMarginMap (r:2 w:2)
+	/// Storage: Trading OpenInterestMap (r:1 w:1)
+	/// Storage: Trading TradingFeeMap (r:1 w:1)
+	/// Storage: Trading BatchesMap (r:1 w:1)
+	/// Storage: Trading OrdersMap (r:1 w:1)
+	/// Storage: TradingAccount AccountMap (r:1 w:0)
+	/// Storage: Trading OrderStateMap (r:1 w:1)
+	/// Storage: Trading OrderHashMap (r:1 w:1)
+	/// Storage: Trading OrderAccountMap (r:1 w:1)
+	/// Storage: Trading CancelledOrdersMap (r:1 w:0)
+	/// Storage: Trading CancelBeforeMap (r:1 w:0)
+	/// Storage: Trading MarketCancelBeforeMap (r:1 w:0)
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:0)
+	/// Storage: Trading PositionsMap (r:1 w:1)
+	/// Storage: Trading MarketToAccountMap (r:0 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount LockedMarginMap (r:1 w:1)
+	/// Storage: TradingAccount MasterAccountVolumeMap (r:2 w:2)
+	/// Storage: TradingAccount ReferralDetailsMap (r:1 w:0)
+	/// The range of component `n` is `[2, 100]`.
+	fn execute_trade(n: u32, ) -> Weight {
+		Weight::from_parts(210_000_000, 0)
+			.saturating_add(Weight::from_parts(185_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().reads((17_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((12_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Trading IsLiquidatorSignerWhitelisted (r:1 w:1)
+	/// Storage: Trading LiquidatorSigners (r:1 w:1)
+	fn add_liquidator_signer() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Trading IsLiquidatorSignerWhitelisted (r:1 w:1)
+	/// Storage: Trading LiquidatorSigners (r:1 w:1)
+	fn remove_liquidator_signer() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: Trading OrderAccountMap (r:1 w:0)
+	/// Storage: Trading OrderStateMap (r:1 w:1)
+	/// Storage: Trading CancelledOrdersMap (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Trading CancellationsMap (r:1 w:1)
+	/// Storage: Trading StartTimestamp (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Trading MarketCancelBeforeMap (r:1 w:1)
+	fn cancel_all_orders() -> Weight {
+		Weight::from_parts(99_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Trading StartTimestamp (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Trading BatchesMap (r:120 w:120)
+	/// Storage: Trading BatchStatusMap (r:0 w:120)
+	/// Storage: Trading OrdersMap (r:120 w:120)
+	/// Storage: Trading OrderStateMap (r:0 w:240)
+	/// Storage: Trading OrderHashMap (r:0 w:240)
+	/// Storage: Trading OrderAccountMap (r:0 w:240)
+	/// Storage: Trading CancellationsMap (r:120 w:120)
+	/// Storage: Trading CancelledOrdersMap (r:0 w:240)
+	fn perform_cleanup() -> Weight {
+		Weight::from_parts(2_400_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(362_u64))
+			.saturating_add(T::DbWeight::get().writes(1441_u64))
+	}
+	/// Storage: Trading MatchingTimeLimit (r:0 w:1)
+	fn set_matching_time_limit() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: TradingFees BaseFeeMap (r:1 w:0)
+	/// Storage: Prices MarkPricesForADSMap (r:1 w:0)
+	/// Storage: Trading InitialMarginMap (r:2 w:2)
+	/// Storage: Trading OpenInterestMap (r:1 w:1)
+	/// Storage: Trading TradingFeeMap (r:1 w:1)
+	/// Storage: Trading LiquidationFeeMap (r:1 w:1)
+	/// Storage: Trading MarketToAccountMap (r:1 w:1)
+	/// Storage: Trading PositionsMap (r:1 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount LockedMarginMap (r:1 w:1)
+	/// The range of component `p` is `[2, 100]`.
+	fn close_delisted_market_positions(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(48_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:0)
+	/// Storage: Trading PositionsMap (r:2 w:0)
+	/// Storage: Trading IsolatedPositionsMap (r:1 w:0)
+	/// Storage: Markets MarketMap (r:2 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Prices CurrentPricesMap (r:2 w:0)
+	/// Storage: TradingAccount BalancesMap (r:2 w:0)
+	/// Storage: TradingAccount LockedMarginMap (r:2 w:0)
+	/// Storage: Trading DeleveragableMap (r:0 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn flag_liquidatable_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((14_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Trading IsLossSocialisationEnabled (r:0 w:1)
+	fn set_loss_socialisation() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Trading MarketToAccountMap (r:101 w:0)
+	/// Storage: Trading PositionsMap (r:200 w:0)
+	/// Storage: Trading CollateralToMarketMap (r:100 w:0)
+	/// Storage: Assets AssetMap (r:100 w:0)
+	/// Storage: Prices CurrentPricesMap (r:100 w:0)
+	/// Storage: Trading IsolatedPositionsMap (r:200 w:0)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: TradingAccount BalancesMap (r:100 w:100)
+	/// Storage: TradingAccount LockedMarginMap (r:100 w:0)
+	/// Storage: TradingAccount AccountMap (r:100 w:0)
+	/// The range of component `p` is `[1, 100]`.
+	fn socialise_loss(p: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: TradingAccount UnallocatedRevenueMap (r:2 w:1)
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:1)
+	fn sweep_fees() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Trading PositionsMap (r:1 w:0)
+	/// Storage: Trading MarketToAccountMap (r:0 w:1)
+	/// The range of component `p` is `[2, 100]`.
+	fn update_market_to_user_map(p: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(9_500_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Trading BatchStatusMap (r:1 w:1)
+	/// Storage: Trading MatchingTimeLimit (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Prices LastOraclePricesMap (r:1 w:1)
+	/// Storage: TradingFees BaseFeeMap (r:1 w:0)
+	/// Storage: Trading InitialMarginMap (r:2 w:2)
+	/// Storage: Trading OpenInterestMap (r:1 w:1)
+	/// Storage: Trading TradingFeeMap (r:1 w:1)
+	/// Storage: Trading BatchesMap (r:1 w:1)
+	/// Storage: Trading OrdersMap (r:1 w:1)
+	/// Storage: TradingAccount AccountMap (r:1 w:0)
+	/// Storage: Trading OrderStateMap (r:1 w:1)
+	/// Storage: Trading OrderHashMap (r:1 w:1)
+	/// Storage: Trading OrderAccountMap (r:1 w:1)
+	/// Storage: Trading CancelledOrdersMap (r:1 w:0)
+	/// Storage: Trading CancelBeforeMap (r:1 w:0)
+	/// Storage: Trading MarketCancelBeforeMap (r:1 w:0)
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:0)
+	/// Storage: Trading PositionsMap (r:1 w:1)
+	/// Storage: Trading MarketToAccountMap (r:0 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount LockedMarginMap (r:1 w:1)
+	/// Storage: TradingAccount MasterAccountVolumeMap (r:2 w:2)
+	/// Storage: TradingAccount ReferralDetailsMap (r:1 w:0)
+	/// The range of component `n` is `[2, 100]`.
+	fn execute_trade(n: u32, ) -> Weight {
+		Weight::from_parts(210_000_000, 0)
+			.saturating_add(Weight::from_parts(185_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().reads((17_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((12_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Trading IsLiquidatorSignerWhitelisted (r:1 w:1)
+	/// Storage: Trading LiquidatorSigners (r:1 w:1)
+	fn add_liquidator_signer() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Trading IsLiquidatorSignerWhitelisted (r:1 w:1)
+	/// Storage: Trading LiquidatorSigners (r:1 w:1)
+	fn remove_liquidator_signer() -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: Trading OrderAccountMap (r:1 w:0)
+	/// Storage: Trading OrderStateMap (r:1 w:1)
+	/// Storage: Trading CancelledOrdersMap (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Trading CancellationsMap (r:1 w:1)
+	/// Storage: Trading StartTimestamp (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Trading MarketCancelBeforeMap (r:1 w:1)
+	fn cancel_all_orders() -> Weight {
+		Weight::from_parts(99_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Trading StartTimestamp (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: Trading BatchesMap (r:120 w:120)
+	/// Storage: Trading BatchStatusMap (r:0 w:120)
+	/// Storage: Trading OrdersMap (r:120 w:120)
+	/// Storage: Trading OrderStateMap (r:0 w:240)
+	/// Storage: Trading OrderHashMap (r:0 w:240)
+	/// Storage: Trading OrderAccountMap (r:0 w:240)
+	/// Storage: Trading CancellationsMap (r:120 w:120)
+	/// Storage: Trading CancelledOrdersMap (r:0 w:240)
+	fn perform_cleanup() -> Weight {
+		Weight::from_parts(2_400_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(362_u64))
+			.saturating_add(RocksDbWeight::get().writes(1441_u64))
+	}
+	/// Storage: Trading MatchingTimeLimit (r:0 w:1)
+	fn set_matching_time_limit() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Markets MarketMap (r:1 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: TradingFees BaseFeeMap (r:1 w:0)
+	/// Storage: Prices MarkPricesForADSMap (r:1 w:0)
+	/// Storage: Trading InitialMarginMap (r:2 w:2)
+	/// Storage: Trading OpenInterestMap (r:1 w:1)
+	/// Storage: Trading TradingFeeMap (r:1 w:1)
+	/// Storage: Trading LiquidationFeeMap (r:1 w:1)
+	/// Storage: Trading MarketToAccountMap (r:1 w:1)
+	/// Storage: Trading PositionsMap (r:1 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:1)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount LockedMarginMap (r:1 w:1)
+	/// The range of component `p` is `[2, 100]`.
+	fn close_delisted_market_positions(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(48_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(p.into())))
+	}
+	/// Storage: Trading ForceClosureFlagMap (r:1 w:1)
+	/// Storage: Trading CollateralToMarketMap (r:1 w:0)
+	/// Storage: Trading PositionsMap (r:2 w:0)
+	/// Storage: Trading IsolatedPositionsMap (r:1 w:0)
+	/// Storage: Markets MarketMap (r:2 w:0)
+	/// Storage: Assets AssetMap (r:1 w:0)
+	/// Storage: Prices CurrentPricesMap (r:2 w:0)
+	/// Storage: TradingAccount BalancesMap (r:2 w:0)
+	/// Storage: TradingAccount LockedMarginMap (r:2 w:0)
+	/// Storage: Trading DeleveragableMap (r:0 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn flag_liquidatable_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((14_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Trading IsLossSocialisationEnabled (r:0 w:1)
+	fn set_loss_socialisation() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Trading MarketToAccountMap (r:101 w:0)
+	/// Storage: Trading PositionsMap (r:200 w:0)
+	/// Storage: Trading CollateralToMarketMap (r:100 w:0)
+	/// Storage: Assets AssetMap (r:100 w:0)
+	/// Storage: Prices CurrentPricesMap (r:100 w:0)
+	/// Storage: Trading IsolatedPositionsMap (r:200 w:0)
+	/// Storage: Markets MarketMap (r:100 w:0)
+	/// Storage: TradingAccount BalancesMap (r:100 w:100)
+	/// Storage: TradingAccount LockedMarginMap (r:100 w:0)
+	/// Storage: TradingAccount AccountMap (r:100 w:0)
+	/// The range of component `p` is `[1, 100]`.
+	fn socialise_loss(p: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: TradingAccount UnallocatedRevenueMap (r:2 w:1)
+	/// Storage: TradingAccount AccountMap (r:2 w:0)
+	/// Storage: TradingAccount BalancesMap (r:1 w:1)
+	/// Storage: TradingAccount AssetHoldersCount (r:1 w:1)
+	fn sweep_fees() -> Weight {
+		Weight::from_parts(28_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
@@ -61,7 +61,9 @@ pub use pallet_prices;
 pub use pallet_risk_management;
 use pallet_support::traits::{PricesInterface, TradingInterface};
 pub use pallet_support::types::{
-	ABRDetails, ABRState, AccountInfo, FeeRates, MarginInfo, PositionExtended,
+	ABRDetails, ABRState, AccountInfo, AccruedFees, AssetInfo, Candle, CandleInterval,
+	CurrentPriceInfo, FeeRates, HistoricalPricesPage, MarginInfo, MarketInfo, MarketState,
+	OrderState, PositionExtended, ScheduledMarketChange,
 };
 pub use pallet_sync_facade;
 pub use pallet_trading;
@@ -123,10 +125,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 114,
+	spec_version: 115,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -317,12 +319,18 @@ impl pallet_risk_management::Config for Runtime {
 
 impl pallet_asset::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type MarketPallet = Markets;
+	type TradingAccountPallet = TradingAccount;
+	type WeightInfo = pallet_asset::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_market::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type PricesPallet = Prices;
+	type TradingPallet = Trading;
+	type TimeProvider = Timestamp;
+	type WeightInfo = pallet_market::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_prices::Config for Runtime {
@@ -333,12 +341,15 @@ impl pallet_prices::Config for Runtime {
 	type TradingPallet = Trading;
 	type TradingAccountPallet = TradingAccount;
 	type AuthorityId = AuthId;
+	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_trading_fees::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetPallet = Assets;
 	type MarketPallet = Markets;
+	type TimeProvider = Timestamp;
+	type WeightInfo = pallet_trading_fees::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_sync_facade::Config for Runtime {
@@ -348,6 +359,7 @@ impl pallet_sync_facade::Config for Runtime {
 	type MarketPallet = Markets;
 	type TradingFeesPallet = TradingFees;
 	type PricesPallet = Prices;
+	type WeightInfo = pallet_sync_facade::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_trading::Config for Runtime {
@@ -360,6 +372,7 @@ impl pallet_trading::Config for Runtime {
 	type RiskManagementPallet = RiskManagement;
 	type TimeProvider = Timestamp;
 	type AuthorityId = AuthId;
+	type WeightInfo = pallet_trading::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_node_authorization::Config for Runtime {
@@ -517,6 +530,12 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_trading_account, TradingAccount]
+		[pallet_trading, Trading]
+		[pallet_asset, Assets]
+		[pallet_market, Markets]
+		[pallet_prices, Prices]
+		[pallet_trading_fees, TradingFees]
+		[pallet_sync_facade, SyncFacade]
 	);
 }
 
@@ -552,6 +571,18 @@ impl_runtime_apis! {
 		fn get_remaining_trading_cleanup_calls() -> u64 {
 			Trading::get_remaining_trading_cleanup_calls()
 		}
+
+		fn get_order_state(order_id: U256) -> OrderState {
+			Trading::get_order_state(order_id)
+		}
+
+		fn get_batch_status(batch_id: U256) -> bool {
+			Trading::get_batch_status(batch_id)
+		}
+
+		fn get_accrued_fees(collateral_id: u128) -> AccruedFees {
+			Trading::get_accrued_fees(collateral_id)
+		}
 	}
 
 	impl pallet_prices_runtime_api::PricesApi<Block> for Runtime {
@@ -600,6 +631,75 @@ impl_runtime_apis! {
 		fn get_remaining_prices_cleanup_calls() -> u64 {
 			Prices::get_remaining_prices_cleanup_calls()
 		}
+
+		fn get_current_price(market_id: U256) -> Option<CurrentPriceInfo> {
+
+			// market_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Prices::get_current_price_info(market_id.as_u128())
+		}
+
+		fn get_all_current_prices() -> Vec<CurrentPriceInfo> {
+			Prices::get_all_current_prices()
+		}
+
+		fn get_historical_prices(market_id: U256, start_timestamp: u64, end_timestamp: u64, page_size: u32) -> HistoricalPricesPage {
+
+			// market_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Prices::get_historical_prices(market_id.as_u128(), start_timestamp, end_timestamp, page_size)
+		}
+
+		fn get_candles(market_id: U256, interval: CandleInterval, start_timestamp: u64, end_timestamp: u64) -> Vec<Candle> {
+
+			// market_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Prices::get_candles(market_id.as_u128(), interval, start_timestamp, end_timestamp)
+		}
+
+		fn get_twap(market_id: U256, start_timestamp: u64, end_timestamp: u64) -> Option<FixedI128> {
+
+			// market_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Prices::get_twap(market_id.as_u128(), start_timestamp, end_timestamp)
+		}
+	}
+
+	impl pallet_market_runtime_api::MarketsApi<Block> for Runtime {
+		fn get_market(market_id: U256) -> Option<MarketInfo> {
+
+			// market_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Markets::markets(market_id.as_u128()).map(MarketInfo::from)
+		}
+
+		fn get_markets_by_state(states: Vec<MarketState>) -> Vec<MarketInfo> {
+			Markets::get_markets_by_state(&states).into_iter().map(MarketInfo::from).collect()
+		}
+
+		fn get_pending_market_changes() -> Vec<ScheduledMarketChange> {
+			Markets::get_pending_market_changes()
+		}
+	}
+
+	impl pallet_asset_runtime_api::AssetsApi<Block> for Runtime {
+		fn get_asset(asset_id: U256) -> Option<AssetInfo> {
+
+			// asset_id is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Assets::assets(asset_id.as_u128()).map(AssetInfo::from)
+		}
+
+		fn get_collaterals() -> Vec<AssetInfo> {
+			Assets::get_collaterals().into_iter().map(AssetInfo::from).collect()
+		}
+
+		fn get_asset_by_address(chain: U256, address: U256) -> Option<AssetInfo> {
+
+			// chain is internally a u128 value hence conversion is required from U256 to u128
+			// call to as_u128() will panic if value is > 2^128
+			Assets::get_asset_by_address(chain.as_u128(), address).map(AssetInfo::from)
+		}
 	}
 
 	impl sp_api::Core<Block> for Runtime {